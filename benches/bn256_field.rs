@@ -40,6 +40,21 @@ pub fn bench_bn256_field(c: &mut Criterion) {
     group.bench_function("bn256_fq_square", |bencher| {
         bencher.iter(|| black_box(&a).square())
     });
+
+    // The binary GCD of `legendre` against Euler's criterion a^((p - 1) / 2).
+    let p = Fq::MODULUS_LIMBS;
+    let half = [
+        (p[0] >> 1) | (p[1] << 63),
+        (p[1] >> 1) | (p[2] << 63),
+        (p[2] >> 1) | (p[3] << 63),
+        p[3] >> 1,
+    ];
+    group.bench_function("bn256_fq_legendre", |bencher| {
+        bencher.iter(|| black_box(&a).legendre())
+    });
+    group.bench_function("bn256_fq_legendre_euler", |bencher| {
+        bencher.iter(|| black_box(&a).pow(half))
+    });
 }
 
 criterion_group!(benches, bench_bn256_field);
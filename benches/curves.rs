@@ -0,0 +1,143 @@
+//! Benchmarks of the main operations of every curve, for tracking
+//! performance over time.
+//!
+//! Every benchmark is named `<curve>/<operation>/<size>`, so that a filter
+//! selects a curve or an operation:
+//!
+//! ```sh
+//! cargo bench --bench curves -- 'secp256k1/'
+//! cargo bench --bench curves -- '/msm/'
+//! ```
+//!
+//! Criterion writes its estimates for each benchmark as JSON, in
+//! `target/criterion/<curve>/<operation>/<size>/new/estimates.json`, and
+//! `cargo criterion --message-format=json` streams them for CI.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ff::PrimeField;
+use group::{Curve, Group};
+use halo2curves::msm::msm;
+use halo2curves::{bn256, grumpkin, pasta, secp256k1, secp256r1, secq256k1, CurveExt};
+use pairing::Engine;
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+const MSM_SIZES: [usize; 3] = [1 << 8, 1 << 10, 1 << 12];
+const FFT_LOG_SIZES: [u32; 3] = [10, 12, 14];
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ])
+}
+
+/// In-place radix-2 FFT of `values` over the domain of size
+/// `values.len()`, to measure the field arithmetic of provers.
+fn fft<F: PrimeField>(values: &mut [F], log_n: u32) {
+    let n = values.len();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let omega = F::ROOT_OF_UNITY.pow_vartime([1u64 << (F::S - log_n)]);
+    let mut m = 1;
+    while m < n {
+        let w_m = omega.pow_vartime([(n / (2 * m)) as u64]);
+        for chunk in values.chunks_mut(2 * m) {
+            let (lo, hi) = chunk.split_at_mut(m);
+            let mut w = F::ONE;
+            for (a, b) in lo.iter_mut().zip(hi) {
+                let t = w * *b;
+                *b = *a - t;
+                *a += t;
+                w *= w_m;
+            }
+        }
+        m *= 2;
+    }
+}
+
+fn bench_curve<C: CurveExt>(c: &mut Criterion, name: &str) {
+    let mut rng = rng();
+    let mut group = c.benchmark_group(name);
+
+    let p = C::random(&mut rng);
+    let q = C::random(&mut rng);
+    let q_affine = q.to_affine();
+    let s = C::ScalarExt::random(&mut rng);
+    group.bench_function(BenchmarkId::new("add", 1), |b| {
+        b.iter(|| black_box(p) + black_box(q))
+    });
+    group.bench_function(BenchmarkId::new("add_mixed", 1), |b| {
+        b.iter(|| black_box(p) + black_box(q_affine))
+    });
+    group.bench_function(BenchmarkId::new("double", 1), |b| {
+        b.iter(|| black_box(p).double())
+    });
+    group.bench_function(BenchmarkId::new("mul", 1), |b| {
+        b.iter(|| black_box(p) * black_box(s))
+    });
+    group.bench_function(BenchmarkId::new("to_affine", 1), |b| {
+        b.iter(|| black_box(p).to_affine())
+    });
+
+    let max = MSM_SIZES[MSM_SIZES.len() - 1];
+    let scalars: Vec<_> = (0..max).map(|_| C::ScalarExt::random(&mut rng)).collect();
+    let points: Vec<_> = (0..max).map(|_| C::random(&mut rng)).collect();
+    let mut bases = vec![C::AffineExt::default(); max];
+    C::batch_normalize(&points, &mut bases);
+    for size in MSM_SIZES {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("msm", size), &size, |b, &size| {
+            b.iter(|| msm(&scalars[..size], &bases[..size]))
+        });
+    }
+
+    for log_n in FFT_LOG_SIZES
+        .into_iter()
+        .filter(|log_n| *log_n <= C::ScalarExt::S)
+    {
+        let size = 1usize << log_n;
+        let values: Vec<_> = (0..size).map(|_| C::ScalarExt::random(&mut rng)).collect();
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("fft", size), &log_n, |b, &log_n| {
+            b.iter_batched_ref(
+                || values.clone(),
+                |values| fft(values, log_n),
+                criterion::BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_pairing(c: &mut Criterion) {
+    let mut rng = rng();
+    let mut group = c.benchmark_group("bn256");
+    let p = bn256::G1::random(&mut rng).to_affine();
+    let q = bn256::G2::random(&mut rng).to_affine();
+    group.bench_function(BenchmarkId::new("pairing", 1), |b| {
+        b.iter(|| bn256::Bn256::pairing(black_box(&p), black_box(&q)))
+    });
+    group.finish();
+}
+
+fn bench_curves(c: &mut Criterion) {
+    bench_curve::<bn256::G1>(c, "bn256_g1");
+    bench_curve::<bn256::G2>(c, "bn256_g2");
+    bench_curve::<grumpkin::G1>(c, "grumpkin");
+    bench_curve::<secp256k1::Secp256k1>(c, "secp256k1");
+    bench_curve::<secp256r1::Secp256r1>(c, "secp256r1");
+    bench_curve::<secq256k1::Secq256k1>(c, "secq256k1");
+    bench_curve::<pasta::Ep>(c, "pallas");
+    bench_curve::<pasta::Eq>(c, "vesta");
+    bench_pairing(c);
+}
+
+criterion_group!(benches, bench_curves);
+criterion_main!(benches);
@@ -4,7 +4,9 @@
 //! This module is temporary, and the extension traits defined here are expected to be
 //! upstreamed into the `ff` and `group` crates after some refactoring.
 
-use crate::CurveExt;
+use crate::{CurveAffine, CurveExt};
+use group::prime::PrimeCurveAffine;
+use group::Curve;
 
 pub(crate) struct EndoParameters {
     pub(crate) gamma1: [u64; 4],
@@ -17,6 +19,80 @@ pub trait CurveEndo: CurveExt {
     fn decompose_scalar(e: &Self::ScalarExt) -> (u128, bool, u128, bool);
 }
 
+pub trait CurveDoubling: CurveExt {
+    /// Returns `2^k * self`, with `k` successive doublings. Implementations
+    /// may skip the per-step handling of the identity that `double` does,
+    /// as a fixed-base window or a cofactor clearing only needs the end
+    /// result.
+    fn double_n(&self, k: usize) -> Self {
+        let mut acc = *self;
+        for _ in 0..k {
+            acc = acc.double();
+        }
+        acc
+    }
+}
+
+pub trait CurveAffineExt: CurveAffine {
+    /// Adds pairs of points in place with a single inversion, as used by the
+    /// MSM of `halo2_proofs`. For every even `i < num_points`, the sum
+    /// `points[i] + points[i + 1]` is written to
+    /// `points[output_indices[i / 2] - offset]`.
+    ///
+    /// With `LOAD_POINTS`, `points[i]` is first loaded from
+    /// `bases[base_positions[i] & 0x7fffffff]`, negated when the top bit of
+    /// the position is set. Without `COMPLETE`, every pair must consist of two
+    /// distinct points that are neither the identity nor opposite.
+    fn batch_add<const COMPLETE: bool, const LOAD_POINTS: bool>(
+        points: &mut [Self],
+        output_indices: &[u32],
+        num_points: usize,
+        offset: usize,
+        bases: &[Self],
+        base_positions: &[u32],
+    );
+
+    /// Unlike the `Coordinates` trait, this just returns the raw affine
+    /// coordinates without checking `is_on_curve`.
+    fn into_coordinates(self) -> (Self::Base, Self::Base) {
+        // fallback implementation
+        let coordinates = self.coordinates().unwrap();
+        (*coordinates.x(), *coordinates.y())
+    }
+}
+
+/// `CurveAffineExt::batch_add` for curves whose coordinates are not
+/// accessible, through projective additions and a batch normalization.
+pub(crate) fn batch_add_projective<C: CurveAffine, const LOAD_POINTS: bool>(
+    points: &mut [C],
+    output_indices: &[u32],
+    num_points: usize,
+    offset: usize,
+    bases: &[C],
+    base_positions: &[u32],
+) {
+    if LOAD_POINTS {
+        for (point, position) in points[..num_points].iter_mut().zip(base_positions) {
+            let base = bases[(position & 0x7FFFFFFF) as usize];
+            *point = if position & 0x80000000 != 0 {
+                -base
+            } else {
+                base
+            };
+        }
+    }
+
+    let sums: Vec<C::CurveExt> = points[..num_points]
+        .chunks(2)
+        .map(|pair| pair[0].to_curve() + pair[1])
+        .collect();
+    let mut affine = vec![<C as PrimeCurveAffine>::identity(); sums.len()];
+    <C::CurveExt as Curve>::batch_normalize(&sums, &mut affine);
+    for (sum, out_idx) in affine.into_iter().zip(output_indices) {
+        points[*out_idx as usize - offset] = sum;
+    }
+}
+
 /// Compute a + b + carry, returning the result and the new carry over.
 #[inline(always)]
 pub(crate) const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
@@ -45,6 +121,248 @@ pub(crate) const fn macx(a: u64, b: u64, c: u64) -> (u64, u64) {
     (res as u64, (res >> 64) as u64)
 }
 
+/// Montgomery reduction of the 512-bit little-endian integer `r` modulo the
+/// four-limb `modulus`, with `inv = -modulus^-1 mod 2^64`.
+///
+/// This is the loop form of the `montgomery_reduce` of the field macros,
+/// shared by every field when the `small` feature is enabled.
+pub(crate) const fn montgomery_reduce_loop(r: &[u64; 8], modulus: &[u64; 4], inv: u64) -> [u64; 4] {
+    let mut r = *r;
+    let mut carry2 = 0;
+    let mut i = 0;
+    while i < 4 {
+        let k = r[i].wrapping_mul(inv);
+        let (_, mut carry) = mac(r[i], k, modulus[0], 0);
+        let mut j = 1;
+        while j < 4 {
+            let (limb, c) = mac(r[i + j], k, modulus[j], carry);
+            r[i + j] = limb;
+            carry = c;
+            j += 1;
+        }
+        let (limb, c) = adc(r[i + 4], carry2, carry);
+        r[i + 4] = limb;
+        carry2 = c;
+        i += 1;
+    }
+
+    // Result may be within MODULUS of the correct value
+    let mut d = [0u64; 4];
+    let mut borrow = 0;
+    let mut j = 0;
+    while j < 4 {
+        let (limb, b) = sbb(r[4 + j], modulus[j], borrow);
+        d[j] = limb;
+        borrow = b;
+        j += 1;
+    }
+    let (_, borrow) = sbb(carry2, 0, borrow);
+
+    let mut carry = 0;
+    let mut j = 0;
+    while j < 4 {
+        let (limb, c) = adc(d[j], modulus[j] & borrow, carry);
+        d[j] = limb;
+        carry = c;
+        j += 1;
+    }
+    d
+}
+
+/// Montgomery multiplication `a * b / 2^256 mod modulus` of four-limb
+/// integers smaller than `modulus`, as a schoolbook loop followed by
+/// `montgomery_reduce_loop`. It also computes the constant tables of the
+/// fields, whatever the features.
+pub(crate) const fn montgomery_mul_loop(
+    a: &[u64; 4],
+    b: &[u64; 4],
+    modulus: &[u64; 4],
+    inv: u64,
+) -> [u64; 4] {
+    let mut r = [0u64; 8];
+    let mut i = 0;
+    while i < 4 {
+        let mut carry = 0;
+        let mut j = 0;
+        while j < 4 {
+            let (limb, c) = mac(r[i + j], a[i], b[j], carry);
+            r[i + j] = limb;
+            carry = c;
+            j += 1;
+        }
+        r[i + 4] = carry;
+        i += 1;
+    }
+    montgomery_reduce_loop(&r, modulus, inv)
+}
+
+/// The Legendre symbol of a field element, as returned by the `legendre`
+/// method of every prime field.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LegendreSymbol {
+    Zero = 0,
+    QuadraticResidue = 1,
+    QuadraticNonResidue = -1,
+}
+
+/// Returns a `Choice` that is set when the Jacobi symbol `(a | n)` is `-1`, for
+/// an odd `n` and `0 < a < n` coprime to `n`, in constant time.
+///
+/// This is the binary GCD with the batching of Pornin's optimized binary GCD,
+/// in the spirit of the jump divsteps of Bernstein and Yang: each batch runs
+/// `JACOBI_BATCH` steps on 64-bit approximations of `a` and `n`, made of their
+/// top 33 bits and their exact low 31 bits, and applies the resulting linear
+/// combination to the full values once. The low bits the sign updates look at
+/// stay exact through the batch, and a value that comes out negative because
+/// of the approximations is negated, which is accounted for by the first
+/// supplement. Unlike Euler's criterion, no field multiplication is involved.
+pub(crate) fn jacobi_is_negative(a: &[u64; 4], n: &[u64; 4]) -> subtle::Choice {
+    /// The number of steps of a batch, which leaves the three low bits of
+    /// the approximations exact until the last step.
+    const JACOBI_BATCH: usize = 29;
+    /// The exact binary GCD needs at most `2 * 256 - 1` steps to reach
+    /// `a = 0` and `n = 1`, after which the sign no longer changes. The
+    /// approximations rarely need one more batch, and never two in tests.
+    const JACOBI_BATCHES: usize = 20;
+    const LOW: u64 = (1 << 31) - 1;
+
+    /// Returns a mask that is set when `x` is zero.
+    fn is_zero(x: u64) -> u64 {
+        ((x | x.wrapping_neg()) >> 63).wrapping_sub(1)
+    }
+
+    /// Returns the approximations of `a` and `b`: their top 33 bits, aligned
+    /// on the length of the larger one, above their low 31 bits. Values
+    /// shorter than 64 bits are returned exactly.
+    fn approximate(a: &[u64; 4], b: &[u64; 4]) -> (u64, u64) {
+        let (mut a_hi, mut a_lo, mut b_hi, mut b_lo) = (a[3], a[2], b[3], b[2]);
+        for j in [1, 0] {
+            let zero = is_zero(a_hi | b_hi);
+            a_hi = (zero & a_lo) | (!zero & a_hi);
+            a_lo = (zero & a[j]) | (!zero & a_lo);
+            b_hi = (zero & b_lo) | (!zero & b_hi);
+            b_lo = (zero & b[j]) | (!zero & b_lo);
+        }
+        let zero = is_zero(a_hi | b_hi);
+        let s = (a_hi | b_hi).leading_zeros() & 63;
+        let top = |hi: u64, lo: u64| (hi << s) | ((lo >> 1) >> (63 - s));
+        let xa = (top(a_hi, a_lo) & !LOW) | (a[0] & LOW);
+        let xb = (top(b_hi, b_lo) & !LOW) | (b[0] & LOW);
+        ((zero & a_lo) | (!zero & xa), (zero & b_lo) | (!zero & xb))
+    }
+
+    /// Returns the absolute value of `(f * a + g * b) / 2^JACOBI_BATCH`, which
+    /// is exact and smaller than `2^256`, and a mask set if it is negative.
+    fn combine(a: &[u64; 4], b: &[u64; 4], f: i64, g: i64) -> ([u64; 4], u64) {
+        let mut r = [0u64; 5];
+        let mut carry = 0i128;
+        for (r, (a, b)) in r.iter_mut().zip(a.iter().zip(b)) {
+            let t = *a as i128 * f as i128 + *b as i128 * g as i128 + carry;
+            *r = t as u64;
+            carry = t >> 64;
+        }
+        r[4] = carry as u64;
+        let negative = ((r[4] as i64) >> 63) as u64;
+
+        let mut d = [0u64; 4];
+        let mut borrow = 0;
+        for (d, r) in d.iter_mut().zip(r.windows(2)) {
+            let limb = (r[0] >> JACOBI_BATCH) | (r[1] << (64 - JACOBI_BATCH));
+            let (limb, b) = sbb(limb ^ negative, negative, borrow);
+            *d = limb;
+            borrow = b;
+        }
+        (d, negative)
+    }
+
+    let (mut a, mut n) = (*a, *n);
+    let mut sign = 0u64;
+    for _ in 0..JACOBI_BATCHES {
+        let (mut xa, mut xn) = approximate(&a, &n);
+
+        // The factors of a and n in the batched values, with
+        // a = (f0 * a + g0 * n) / 2^j and n = (f1 * a + g1 * n) / 2^j after
+        // j steps.
+        let (mut f0, mut g0, mut f1, mut g1) = (1i64, 0i64, 0i64, 1i64);
+        for _ in 0..JACOBI_BATCH {
+            let odd = (xa & 1).wrapping_neg();
+
+            // If a is odd and smaller than n, swap them, flipping the sign
+            // when both are 3 mod 4.
+            let (_, borrow) = sbb(xa, xn, 0);
+            let swap = odd & borrow;
+            sign ^= (swap & xa & xn) >> 1;
+            let t = swap & (xa ^ xn);
+            xa ^= t;
+            xn ^= t;
+            let t = swap as i64 & (f0 ^ f1);
+            f0 ^= t;
+            f1 ^= t;
+            let t = swap as i64 & (g0 ^ g1);
+            g0 ^= t;
+            g1 ^= t;
+
+            // If a is odd, subtract n, which leaves it even.
+            xa = xa.wrapping_sub(odd & xn);
+            f0 -= odd as i64 & f1;
+            g0 -= odd as i64 & g1;
+
+            // Halve a, flipping the sign when n is 3 or 5 mod 8.
+            xa >>= 1;
+            f1 <<= 1;
+            g1 <<= 1;
+            sign ^= (xn >> 1) ^ (xn >> 2);
+        }
+
+        // Apply the batch to the full values. A negative n is negated for
+        // free, and a negative a flips the sign when n is 3 mod 4.
+        let (new_a, negative_a) = combine(&a, &n, f0, g0);
+        let (new_n, _) = combine(&a, &n, f1, g1);
+        a = new_a;
+        n = new_n;
+        sign ^= negative_a & (n[0] >> 1);
+    }
+    subtle::Choice::from((sign & 1) as u8)
+}
+
+/// Splits `v` into chunks and calls `f` on each of them in parallel, along
+/// with the index of the first element of the chunk.
+pub(crate) fn parallelize<T: Send, F: Fn(&mut [T], usize) + Send + Sync + Clone>(
+    v: &mut [T],
+    f: F,
+) {
+    parallelize_with(None, v, f)
+}
+
+/// Same as `parallelize`, with at most `num_threads` threads, or one thread
+/// per core if `None`.
+pub(crate) fn parallelize_with<T: Send, F: Fn(&mut [T], usize) + Send + Sync + Clone>(
+    num_threads: Option<usize>,
+    v: &mut [T],
+    f: F,
+) {
+    let n = v.len();
+    let num_threads = num_threads
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        })
+        .max(1);
+    let chunk_size = (n + num_threads - 1) / num_threads;
+    if chunk_size < 2 || num_threads == 1 {
+        f(v, 0);
+        return;
+    }
+
+    std::thread::scope(|scope| {
+        for (chunk_num, chunk) in v.chunks_mut(chunk_size).enumerate() {
+            let f = f.clone();
+            scope.spawn(move || f(chunk, chunk_num * chunk_size));
+        }
+    });
+}
+
 /// Compute a * b, returning the result.
 #[inline(always)]
 pub(crate) fn mul_512(a: [u64; 4], b: [u64; 4]) -> [u64; 8] {
@@ -70,3 +388,87 @@ pub(crate) fn mul_512(a: [u64; 4], b: [u64; 4]) -> [u64; 8] {
 
     [r0, r1, r2, r3, r4, r5, r6, carry_out]
 }
+
+#[cfg(all(test, feature = "small"))]
+mod tests {
+    use super::{montgomery_mul_loop, montgomery_reduce_loop, mul_512, sbb};
+    use rand_core::{RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    /// Returns `x mod modulus`, one bit at a time.
+    fn reduce_slow(x: &[u64; 8], modulus: &[u64; 4]) -> [u64; 4] {
+        let modulus = [modulus[0], modulus[1], modulus[2], modulus[3], 0];
+        let mut r = [0u64; 5];
+        for i in (0..512).rev() {
+            let mut bit = (x[i / 64] >> (i % 64)) & 1;
+            for limb in r.iter_mut() {
+                let top = *limb >> 63;
+                *limb = (*limb << 1) | bit;
+                bit = top;
+            }
+            if r.iter().rev().ge(modulus.iter().rev()) {
+                let mut borrow = 0;
+                for (limb, m) in r.iter_mut().zip(modulus) {
+                    let (d, b) = sbb(*limb, m, borrow);
+                    *limb = d;
+                    borrow = b;
+                }
+            }
+        }
+        [r[0], r[1], r[2], r[3]]
+    }
+
+    #[test]
+    fn test_montgomery_loops() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // The base fields of BN254 and secp256k1, the latter filling all
+        // four limbs.
+        let moduli: [[u64; 4]; 2] = [
+            [
+                0x3c208c16d87cfd47,
+                0x97816a916871ca8d,
+                0xb85045b68181585d,
+                0x30644e72e131a029,
+            ],
+            [
+                0xfffffffefffffc2f,
+                0xffffffffffffffff,
+                0xffffffffffffffff,
+                0xffffffffffffffff,
+            ],
+        ];
+        for modulus in moduli {
+            // -modulus^-1 mod 2^64, by Newton iteration.
+            let mut inv = 1u64;
+            for _ in 0..6 {
+                inv = inv.wrapping_mul(2u64.wrapping_sub(modulus[0].wrapping_mul(inv)));
+            }
+            let inv = inv.wrapping_neg();
+
+            let mut minus_one = modulus;
+            minus_one[0] -= 1;
+            let mut values = vec![[0; 4], [1, 0, 0, 0], minus_one];
+            values.extend((0..20).map(|_| {
+                let mut x = [0; 8];
+                x[..4].iter_mut().for_each(|limb| *limb = rng.next_u64());
+                reduce_slow(&x, &modulus)
+            }));
+
+            // Both loops return the reduced value of t / 2^256 mod modulus.
+            for a in values.iter() {
+                for b in values.iter() {
+                    let t = mul_512(*a, *b);
+                    let c = montgomery_mul_loop(a, b, &modulus, inv);
+                    assert_eq!(montgomery_reduce_loop(&t, &modulus, inv), c);
+                    assert!(c.iter().rev().lt(modulus.iter().rev()));
+                    let shifted = [0, 0, 0, 0, c[0], c[1], c[2], c[3]];
+                    assert_eq!(reduce_slow(&shifted, &modulus), reduce_slow(&t, &modulus));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use crate::babyjubjub::{Fq, Fr};
+use crate::ff::{Field, PrimeField};
+use crate::group::{cofactor::CofactorCurveAffine, Curve, Group, GroupEncoding};
+use crate::{
+    impl_add_binop_specify_output, impl_binops_additive, impl_binops_additive_specify_output,
+    impl_binops_multiplicative, impl_binops_multiplicative_mixed, impl_sub_binop_specify_output,
+    new_edwards_curve_impl,
+};
+use core::cmp;
+use core::iter::Sum;
+use core::ops::{Add, Mul, Neg, Sub};
+use rand::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+#[cfg(feature = "derive_serde")]
+use serde::{Deserialize, Serialize};
+
+new_edwards_curve_impl!(
+    (pub),
+    BabyJubjub,
+    BabyJubjubAffine,
+    BabyJubjubSubgroup,
+    Fq,
+    Fr,
+    (GENERATOR_X, GENERATOR_Y),
+    EDWARDS_A,
+    EDWARDS_D,
+    8,
+    (TORSION_X, TORSION_Y),
+    "babyjubjub",
+);
+
+// Parameters taken from EIP-2494. The generator is the point `Base8`, which
+// generates the subgroup of prime order.
+const GENERATOR_X: Fq = Fq::from_raw([
+    0x2893f3f6bb957051,
+    0x2ab8d8010534e0b6,
+    0x4eacb2e09d6277c1,
+    0x0bb77a6ad63e739b,
+]);
+const GENERATOR_Y: Fq = Fq::from_raw([
+    0x4b3c257a872d7d8b,
+    0xfce0051fb9e13377,
+    0x25572e1cd16bf9ed,
+    0x25797203f7a0b249,
+]);
+// A point of order 8, i.e. `[r] P` for a point `P` lifted from `y = 9`.
+const TORSION_X: Fq = Fq::from_raw([
+    0xd6a1e0e5854c60e3,
+    0xd006d8f522066be0,
+    0x8e9f746b5c616985,
+    0x26ca6a00b96d21a1,
+]);
+const TORSION_Y: Fq = Fq::from_raw([
+    0xabfd1e81afd0d677,
+    0xb791c56d8234b5a0,
+    0x63c1127d4ba6942c,
+    0x0aabb7211172d314,
+]);
+const EDWARDS_A: Fq = Fq::from_raw([168700, 0, 0, 0]);
+const EDWARDS_D: Fq = Fq::from_raw([168696, 0, 0, 0]);
+
+/// Returns the constants of the Baby Jubjub curve and of its fields.
+pub fn params() -> crate::params::EdwardsCurveParams {
+    crate::params::EdwardsCurveParams::of::<Fq, Fr>(
+        BabyJubjub::CURVE_ID,
+        EDWARDS_A,
+        EDWARDS_D,
+        BabyJubjub::COFACTOR,
+        (GENERATOR_X, GENERATOR_Y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generic_curve::EdwardsCurve;
+    use crate::group::cofactor::CofactorGroup;
+    use crate::group::UncompressedEncoding;
+    use rand_core::OsRng;
+
+    fn point(x: &str, y: &str) -> BabyJubjubAffine {
+        BabyJubjubAffine::from_xy(
+            Fq::from_str_vartime(x).unwrap(),
+            Fq::from_str_vartime(y).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_curve() {
+        let g = BabyJubjub::generator();
+        assert!(bool::from(g.is_on_curve()));
+        assert!(bool::from(g.is_torsion_free()));
+        assert!(bool::from(BabyJubjub::identity().is_on_curve()));
+        assert_eq!(g * -Fr::ONE + g, BabyJubjub::identity());
+        assert_eq!(g - g, BabyJubjub::identity());
+        assert_eq!(g.double(), g + g);
+        assert_eq!(g.double(), g + BabyJubjubAffine::generator());
+        assert_eq!(g + BabyJubjub::identity(), g);
+
+        // Baby Jubjub does not take the `a = -1` shortcut of `mul_by_a`.
+        assert_eq!(Fq::minus_one(), -Fq::ONE);
+        assert!(!EDWARDS_A.eq_vartime(&Fq::minus_one()));
+
+        for _ in 0..20 {
+            let a = Fr::random(OsRng);
+            let b = Fr::random(OsRng);
+            assert_eq!(g * a + g * b, g * (a + b));
+            assert_eq!((g * a) * b, g * (a * b));
+            assert!(bool::from((g * a).is_on_curve()));
+
+            let p = BabyJubjub::random(OsRng);
+            assert!(bool::from(p.is_on_curve()));
+            assert_eq!(p.to_affine(), BabyJubjubAffine::from(p));
+        }
+
+        // Random points cover the whole curve, not only the subgroup.
+        assert!((0..20).any(|_| !bool::from(BabyJubjub::random(OsRng).is_torsion_free())));
+
+        let points: Vec<_> = (1..10u64).map(|i| g * Fr::from(i)).collect();
+        let mut affine = vec![BabyJubjubAffine::identity(); points.len()];
+        BabyJubjub::batch_normalize(&points, &mut affine);
+        for (p, q) in points.iter().zip(affine.iter()) {
+            assert_eq!(p.to_affine(), *q);
+        }
+    }
+
+    #[test]
+    fn test_addition_vector() {
+        // The addition test vector of EIP-2494.
+        let p1 = point(
+            "17777552123799933955779906779655732241715742912184938656739573121738514868268",
+            "2626589144620713026669568689430873010625803728049924121243784502389097019475",
+        );
+        let p2 = point(
+            "16540640123574156134436876038791482806971768689494387082833631921987005038935",
+            "20819045374670962167435360035096875258406992893633759881276124905556507972311",
+        );
+        let sum = point(
+            "7916061937171219682591368294088513039687205273691143098332585753343424131937",
+            "14035240266687799601661095864649209771790948434046947201833777492504781204499",
+        );
+        assert_eq!((p1 + p2).to_affine(), sum);
+        assert_eq!((p1.to_curve() + p2.to_curve()).to_affine(), sum);
+
+        let edwards = EdwardsCurve::new(BabyJubjubAffine::a(), BabyJubjubAffine::d()).unwrap();
+        let (x, y) = edwards.add(&(p1.x, p1.y), &(p2.x, p2.y));
+        assert_eq!(BabyJubjubAffine { x, y }, sum);
+        let (x, y) = edwards.add(&(p1.x, p1.y), &(p1.x, p1.y));
+        assert_eq!(
+            BabyJubjubAffine { x, y },
+            p1.to_curve().double().to_affine()
+        );
+
+        // The full group is generated by a point whose multiple by the
+        // cofactor is the generator of the subgroup.
+        let g = point(
+            "995203441582195749578291179787384436505546430278305826713579947235728471134",
+            "5472060717959818805561601436314318772137091100104008585924551046643952123905",
+        )
+        .to_curve();
+        assert!(!bool::from(g.is_torsion_free()));
+        assert_eq!(
+            BabyJubjub::from(g.clear_cofactor()),
+            BabyJubjub::generator()
+        );
+    }
+
+    #[test]
+    fn test_encoding() {
+        for _ in 0..20 {
+            let p = BabyJubjub::random(OsRng);
+            let bytes = p.to_bytes();
+            assert_eq!(BabyJubjub::from_bytes(&bytes).unwrap(), p);
+            assert_eq!(BabyJubjub::try_from(bytes.as_ref()), Ok(p));
+            assert_eq!(
+                BabyJubjubAffine::from_bytes(&(-p).to_bytes()).unwrap(),
+                (-p).to_affine()
+            );
+        }
+        let identity = BabyJubjub::identity();
+        assert_eq!(
+            BabyJubjub::from_bytes(&identity.to_bytes()).unwrap(),
+            identity
+        );
+
+        // The point (0, -1) of order 2 is on the curve but not in the
+        // subgroup, so only the subgroup type rejects it.
+        let torsion = BabyJubjubAffine {
+            x: Fq::ZERO,
+            y: -Fq::ONE,
+        };
+        let bytes = torsion.to_bytes();
+        assert_eq!(BabyJubjub::from_bytes(&bytes).unwrap(), torsion.to_curve());
+        assert_eq!(
+            BabyJubjubAffine::from_bytes_unchecked(&bytes).unwrap(),
+            torsion
+        );
+        assert!(bool::from(BabyJubjubSubgroup::from_bytes(&bytes).is_none()));
+        assert_eq!(torsion.to_curve().double(), BabyJubjub::identity());
+        assert!(bool::from(
+            torsion.to_curve().clear_cofactor().is_identity()
+        ));
+        assert!(bool::from(torsion.to_curve().into_subgroup().is_none()));
+        assert!(bool::from(
+            BabyJubjubSubgroup::from_bytes_unchecked(&bytes).is_none()
+        ));
+        let uncompressed = torsion.to_uncompressed();
+        assert_eq!(
+            BabyJubjubAffine::from_uncompressed(&uncompressed).unwrap(),
+            torsion
+        );
+        assert_eq!(
+            BabyJubjubAffine::from_uncompressed_unchecked(&uncompressed).unwrap(),
+            torsion
+        );
+
+        // The uncompressed encoding is checked to be on the curve.
+        let mut uncompressed = BabyJubjubAffine::generator().to_uncompressed();
+        uncompressed.as_mut()[0] ^= 1;
+        assert!(bool::from(
+            BabyJubjubAffine::from_uncompressed(&uncompressed).is_none()
+        ));
+
+        // circomlib's `packPoint` of the first point of the EIP-2494 addition
+        // vector, whose x is even but larger than (p - 1) / 2.
+        let p1 = point(
+            "17777552123799933955779906779655732241715742912184938656739573121738514868268",
+            "2626589144620713026669568689430873010625803728049924121243784502389097019475",
+        );
+        let packed = [
+            0x53, 0xb8, 0x1e, 0xd5, 0xbf, 0xfe, 0x95, 0x45, 0xb5, 0x40, 0x16, 0x23, 0x46, 0x82,
+            0xe7, 0xb2, 0xf6, 0x99, 0xbd, 0x42, 0xa5, 0xe9, 0xea, 0xe2, 0x7f, 0xf4, 0x05, 0x1b,
+            0xc6, 0x98, 0xce, 0x85,
+        ];
+        assert_eq!(p1.to_bytes().as_ref(), &packed[..]);
+        let repr = BabyJubjubCompressed::try_from(&packed[..]).unwrap();
+        assert_eq!(BabyJubjubAffine::from_bytes_unchecked(&repr).unwrap(), p1);
+        // The generator has an odd x smaller than (p - 1) / 2.
+        assert_eq!(
+            BabyJubjubAffine::generator().to_bytes().as_ref()[31] & 0x80,
+            0
+        );
+
+        // There is no negative zero.
+        let mut bytes = BabyJubjub::identity().to_bytes();
+        bytes.as_mut()[31] |= 0x80;
+        assert!(bool::from(
+            BabyJubjub::from_bytes_unchecked(&bytes).is_none()
+        ));
+    }
+
+    #[test]
+    fn test_group_traits() {
+        crate::tests::curve::cofactor_group_traits_test::<BabyJubjub>();
+    }
+
+    #[test]
+    fn test_subgroup() {
+        let g = BabyJubjubSubgroup::generator();
+        assert_eq!(BabyJubjub::generator().into_subgroup().unwrap(), g);
+        assert_eq!(BabyJubjub::from(g), BabyJubjub::generator());
+
+        for _ in 0..10 {
+            let a = Fr::random(OsRng);
+            let b = Fr::random(OsRng);
+            assert_eq!(g * a + g * b, g * (a + b));
+            assert_eq!(BabyJubjub::from(g * a), BabyJubjub::generator() * a);
+            assert_eq!(g * a - g * a, BabyJubjubSubgroup::identity());
+
+            let p = BabyJubjubSubgroup::random(OsRng);
+            assert_eq!(BabyJubjubSubgroup::from_bytes(&p.to_bytes()).unwrap(), p);
+            assert_eq!(
+                BabyJubjub::from(p).clear_cofactor(),
+                p * Fr::from(BabyJubjub::COFACTOR)
+            );
+        }
+        assert_eq!(g * -Fr::ONE + g, BabyJubjubSubgroup::identity());
+    }
+}
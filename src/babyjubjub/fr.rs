@@ -0,0 +1,376 @@
+use crate::arithmetic::{adc, mac, sbb};
+use crate::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use crate::sqrt::SqrtPrecomputation;
+use crate::{
+    field_arithmetic, field_bits, field_common, field_specific, impl_add_binop_specify_output,
+    impl_binops_additive, impl_binops_additive_specify_output, impl_binops_multiplicative,
+    impl_binops_multiplicative_mixed, impl_from_u64, impl_sub_binop_specify_output, impl_sum_prod,
+};
+use core::convert::TryInto;
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+use rand::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+#[cfg(feature = "derive_serde")]
+use serde::{Deserialize, Serialize};
+
+/// This represents an element of $\mathbb{F}_r$ where
+///
+/// `r = 0x060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f1`
+///
+/// is the order of the prime subgroup of Baby Jubjub.
+// The internal representation of this type is four 64-bit unsigned
+// integers in little-endian order. `Fr` values are always in
+// Montgomery form; i.e., Fr(a) = aR mod r, with R = 2^256.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "derive_serde", derive(Serialize, Deserialize))]
+pub struct Fr(pub(crate) [u64; 4]);
+
+/// Constant representing the modulus
+/// r = 0x060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f1
+const MODULUS: Fr = Fr([
+    0x677297dc392126f1,
+    0xab3eedb83920ee0a,
+    0x370a08b6d0302b0b,
+    0x060c89ce5c263405,
+]);
+
+/// The modulus as u32 limbs.
+#[cfg(not(target_pointer_width = "64"))]
+const MODULUS_LIMBS_32: [u32; 8] = [
+    0x3921_26f1,
+    0x6772_97dc,
+    0x3920_ee0a,
+    0xab3e_edb8,
+    0xd030_2b0b,
+    0x370a_08b6,
+    0x5c26_3405,
+    0x060c_89ce,
+];
+
+///Constant representing the modulus as static str
+const MODULUS_STR: &str = "0x060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f1";
+
+/// INV = -(r^{-1} mod 2^64) mod 2^64
+const INV: u64 = 0x532ce5aebc48f5ef;
+
+/// R = 2^256 mod r
+/// 0x1f16424e1bb7724f85a9201d818f015e7acffc6a098f24b073315dea08f9c76
+const R: Fr = Fr([
+    0x073315dea08f9c76,
+    0xe7acffc6a098f24b,
+    0xf85a9201d818f015,
+    0x01f16424e1bb7724,
+]);
+
+/// R^2 = 2^512 mod r
+/// 0x445b524f1ba50a8e472df203faa158f74646cacf5f84ec435e44abee7ecb21e
+const R2: Fr = Fr([
+    0x35e44abee7ecb21e,
+    0x74646cacf5f84ec4,
+    0xe472df203faa158f,
+    0x0445b524f1ba50a8,
+]);
+
+/// R^3 = 2^768 mod r
+/// 0x2b4dbffb2bc97aaeefe3d08cc608b7b98dae87b55d7cd2a30886e7b42917c21
+const R3: Fr = Fr([
+    0x30886e7b42917c21,
+    0x98dae87b55d7cd2a,
+    0xeefe3d08cc608b7b,
+    0x02b4dbffb2bc97aa,
+]);
+
+/// `GENERATOR = 31 mod r` is a generator of the `r - 1` order multiplicative
+/// subgroup, or in other words a primitive root of the field.
+const GENERATOR: Fr = Fr::from_raw([0x1f, 0x00, 0x00, 0x00]);
+
+const S: u32 = 4;
+
+/// GENERATOR^t where t * 2^s + 1 = r with t odd. In other words, this is a 2^s root of unity.
+/// `0x17607cccda766fb904adf8e5a770f6e3fe66e76e0a41338daf49a41ac514473`
+const ROOT_OF_UNITY: Fr = Fr::from_raw([
+    0xdaf49a41ac514473,
+    0x3fe66e76e0a41338,
+    0x904adf8e5a770f6e,
+    0x017607cccda766fb,
+]);
+
+/// 1 / ROOT_OF_UNITY mod r
+const ROOT_OF_UNITY_INV: Fr = Fr::from_raw([
+    0xdfb451b0464fef87,
+    0xb3fc84235d486f20,
+    0xb8f9db808928a940,
+    0x024e71baef132586,
+]);
+
+/// 1 / 2 mod r
+const TWO_INV: Fr = Fr::from_raw([
+    0x33b94bee1c909379,
+    0xd59f76dc1c907705,
+    0x9b85045b68181585,
+    0x030644e72e131a02,
+]);
+
+/// GENERATOR^((r - 1) / 3), an element of multiplicative order 3.
+const ZETA: Fr = Fr::from_raw([
+    0x034a42b2c7fe2d32,
+    0x61c35148b65ec763,
+    0xee0dfa0397420b71,
+    0x05ad052060e4f041,
+]);
+
+/// Generator of the t-order multiplicative subgroup.
+/// Computed by exponentiating Self::MULTIPLICATIVE_GENERATOR by 2^s, where s is Self::S.
+const DELTA: Fr = Fr::from_raw([0xafbae83050a9de01, 0x9a09, 0, 0]);
+
+impl_binops_additive!(Fr, Fr);
+impl_binops_multiplicative!(Fr, Fr);
+field_common!(
+    Fr,
+    MODULUS,
+    INV,
+    MODULUS_STR,
+    TWO_INV,
+    ROOT_OF_UNITY_INV,
+    DELTA,
+    ZETA,
+    R,
+    R2,
+    R3
+);
+impl_from_u64!(Fr, R2);
+field_arithmetic!(Fr, MODULUS, INV, sparse);
+impl_sum_prod!(Fr);
+
+#[cfg(target_pointer_width = "64")]
+field_bits!(Fr, MODULUS);
+#[cfg(not(target_pointer_width = "64"))]
+field_bits!(Fr, MODULUS, MODULUS_LIMBS_32);
+
+impl Fr {
+    pub const fn size() -> usize {
+        32
+    }
+}
+
+impl ff::Field for Fr {
+    const ZERO: Self = Self::zero();
+    const ONE: Self = Self::one();
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Self::from_u512([
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+        ])
+    }
+
+    fn double(&self) -> Self {
+        self.double()
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self.square()
+    }
+
+    /// Computes the multiplicative inverse of this element,
+    /// failing if the element is zero.
+    fn invert(&self) -> CtOption<Self> {
+        #[cfg(feature = "op-count")]
+        crate::stats::record_invert();
+
+        let tmp = self.pow([
+            0x677297dc392126ef,
+            0xab3eedb83920ee0a,
+            0x370a08b6d0302b0b,
+            0x060c89ce5c263405,
+        ]);
+
+        CtOption::new(tmp, !self.ct_eq(&Self::zero()))
+    }
+
+    fn pow_vartime<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        let mut res = Self::one();
+        let mut found_one = false;
+        for e in exp.as_ref().iter().rev() {
+            for i in (0..64).rev() {
+                if found_one {
+                    res = res.square();
+                }
+
+                if ((*e >> i) & 1) == 1 {
+                    found_one = true;
+                    res *= self;
+                }
+            }
+        }
+        res
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        lazy_static::lazy_static! {
+            static ref SQRT: SqrtPrecomputation<Fr> = SqrtPrecomputation::new();
+        }
+        SQRT.sqrt(self)
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+}
+
+impl ff::PrimeField for Fr {
+    type Repr = [u8; 32];
+
+    const NUM_BITS: u32 = 251;
+    const CAPACITY: u32 = 250;
+    const MODULUS: &'static str = MODULUS_STR;
+    const MULTIPLICATIVE_GENERATOR: Self = GENERATOR;
+    const ROOT_OF_UNITY: Self = ROOT_OF_UNITY;
+    const ROOT_OF_UNITY_INV: Self = ROOT_OF_UNITY_INV;
+    const TWO_INV: Self = TWO_INV;
+    const DELTA: Self = DELTA;
+    const S: u32 = S;
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        let mut tmp = Fr([0, 0, 0, 0]);
+
+        tmp.0[0] = u64::from_le_bytes(repr[0..8].try_into().unwrap());
+        tmp.0[1] = u64::from_le_bytes(repr[8..16].try_into().unwrap());
+        tmp.0[2] = u64::from_le_bytes(repr[16..24].try_into().unwrap());
+        tmp.0[3] = u64::from_le_bytes(repr[24..32].try_into().unwrap());
+
+        // Try to subtract the modulus
+        let (_, borrow) = sbb(tmp.0[0], MODULUS.0[0], 0);
+        let (_, borrow) = sbb(tmp.0[1], MODULUS.0[1], borrow);
+        let (_, borrow) = sbb(tmp.0[2], MODULUS.0[2], borrow);
+        let (_, borrow) = sbb(tmp.0[3], MODULUS.0[3], borrow);
+
+        // If the element is smaller than MODULUS then the
+        // subtraction will underflow, producing a borrow value
+        // of 0xffff...ffff. Otherwise, it'll be zero.
+        let is_some = (borrow as u8) & 1;
+
+        // Convert to Montgomery form by computing
+        // (a.R^0 * R^2) / R = a.R
+        tmp *= &R2;
+
+        CtOption::new(tmp, Choice::from(is_some))
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        // Turn into canonical form by computing
+        // (a.R) / R = a
+        let tmp = Fr::montgomery_reduce(&[self.0[0], self.0[1], self.0[2], self.0[3], 0, 0, 0, 0]);
+
+        let mut res = [0; 32];
+        res[0..8].copy_from_slice(&tmp.0[0].to_le_bytes());
+        res[8..16].copy_from_slice(&tmp.0[1].to_le_bytes());
+        res[16..24].copy_from_slice(&tmp.0[2].to_le_bytes());
+        res[24..32].copy_from_slice(&tmp.0[3].to_le_bytes());
+
+        res
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from(self.to_repr()[0] & 1)
+    }
+}
+
+impl FromUniformBytes<64> for Fr {
+    /// Converts a 512-bit little endian integer into
+    /// an `Fr` by reducing by the modulus.
+    fn from_uniform_bytes(bytes: &[u8; 64]) -> Self {
+        Self::from_u512([
+            u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            u64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+            u64::from_le_bytes(bytes[32..40].try_into().unwrap()),
+            u64::from_le_bytes(bytes[40..48].try_into().unwrap()),
+            u64::from_le_bytes(bytes[48..56].try_into().unwrap()),
+            u64::from_le_bytes(bytes[56..64].try_into().unwrap()),
+        ])
+    }
+}
+
+impl WithSmallOrderMulGroup<3> for Fr {
+    const ZETA: Self = ZETA;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ff::Field;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_sqrt() {
+        // NB: TWO_INV is standing in as a "random" field element
+        let v = (Fr::TWO_INV).square().sqrt().unwrap();
+        assert!(v == Fr::TWO_INV || (-v) == Fr::TWO_INV);
+
+        for _ in 0..10000 {
+            let a = Fr::random(OsRng);
+            let mut b = a;
+            b = b.square();
+
+            let b = b.sqrt().unwrap();
+            let mut negb = b;
+            negb = negb.neg();
+
+            assert!(a == b || a == negb);
+        }
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(
+            Fr::MODULUS,
+            "0x060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f1",
+        );
+
+        assert_eq!(Fr::from(2) * Fr::TWO_INV, Fr::ONE);
+    }
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(Fr::DELTA, Fr::MULTIPLICATIVE_GENERATOR.pow([1u64 << Fr::S]));
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        assert_eq!(Fr::ROOT_OF_UNITY.pow_vartime([1 << Fr::S]), Fr::one());
+    }
+
+    #[test]
+    fn test_inv_root_of_unity() {
+        assert_eq!(Fr::ROOT_OF_UNITY_INV, Fr::ROOT_OF_UNITY.invert().unwrap());
+    }
+
+    #[test]
+    fn test_field() {
+        crate::tests::field::random_field_tests::<Fr>("babyjubjub scalar".to_string());
+    }
+
+    #[test]
+    #[cfg(feature = "bits")]
+    fn test_bits() {
+        crate::tests::field::random_bits_tests::<Fr>("babyjubjub scalar".to_string());
+    }
+
+    #[test]
+    fn test_serialization() {
+        crate::tests::field::random_serialization_test::<Fr>("babyjubjub scalar".to_string());
+        #[cfg(feature = "derive_serde")]
+        crate::tests::field::random_serde_test::<Fr>("babyjubjub scalar".to_string());
+    }
+}
@@ -0,0 +1,9 @@
+mod curve;
+mod fr;
+
+// The base field is the scalar field of BN254, as the same type, so that the
+// coordinates of Baby Jubjub points are native values of BN254 circuits.
+// `embedded::EmbeddedCurve` relies on this.
+pub use crate::bn256::Fr as Fq;
+pub use curve::*;
+pub use fr::*;
@@ -0,0 +1,246 @@
+//! Randomized batch verification of Schnorr and ECDSA signatures.
+//!
+//! Every signature gives an equation `sum_j a_j P_j = 0` between points. A
+//! batch is checked by folding the equations with random 128-bit coefficients
+//! into a single multi-scalar multiplication, so that a batch containing an
+//! invalid signature passes with probability about `2^-128`. A failing batch
+//! does not tell which signatures are invalid; callers that need to know
+//! verify them one by one.
+//!
+//! The challenges and message hashes are computed by the caller, as the hash
+//! functions differ from one scheme to the next.
+
+use crate::msm::msm;
+use crate::normalize::Normalization;
+use crate::CurveAffine;
+use ff::{BatchInvert, Field, FromUniformBytes, PrimeField};
+use group::prime::PrimeCurveAffine;
+use group::Group;
+use rand::RngCore;
+
+/// A Schnorr signature `(R, s)` on a message with challenge `e` under the
+/// public key `P`, valid when `s * G = R + e * P`.
+#[derive(Copy, Clone, Debug)]
+pub struct SchnorrEquation<C: CurveAffine> {
+    pub public_key: C,
+    pub nonce: C,
+    pub challenge: C::ScalarExt,
+    pub response: C::ScalarExt,
+}
+
+impl<C: CurveAffine> SchnorrEquation<C> {
+    pub fn verify(&self) -> bool {
+        C::generator() * self.response == self.nonce.to_curve() + self.public_key * self.challenge
+    }
+}
+
+/// An ECDSA signature `(r, s)` on the message hash `z` under the public key
+/// `P`, together with the point `R` whose x-coordinate gives `r`. It is valid
+/// when `z / s * G + r / s * P = R`. Plain ECDSA signatures only carry `r`,
+/// so `R` has to be transmitted alongside or recovered from `r` and a parity
+/// bit.
+#[derive(Copy, Clone, Debug)]
+pub struct EcdsaEquation<C: CurveAffine> {
+    pub public_key: C,
+    pub nonce: C,
+    pub msg_hash: C::ScalarExt,
+    pub s: C::ScalarExt,
+}
+
+impl<C: CurveAffine> EcdsaEquation<C>
+where
+    C::ScalarExt: FromUniformBytes<64>,
+{
+    /// Returns `r`, the x-coordinate of `R` reduced modulo the group order,
+    /// or `None` if `R` is the identity.
+    pub fn r(&self) -> Option<C::ScalarExt> {
+        let coordinates: Option<_> = self.nonce.coordinates().into();
+        let x = coordinates?.x().to_repr();
+        let mut bytes = [0u8; 64];
+        bytes[..x.as_ref().len()].copy_from_slice(x.as_ref());
+        Some(C::ScalarExt::from_uniform_bytes(&bytes))
+    }
+
+    /// Returns the same signature with `s` normalized by `policy`, such as
+    /// `Normalization::LowS` for BIP-146. `R` is negated along with `s`, which
+    /// keeps `r` and the validity of the equation.
+    pub fn normalized(&self, policy: Normalization) -> Self {
+        let (s, negated) = policy.normalize(&self.s);
+        EcdsaEquation {
+            nonce: C::conditional_select(&self.nonce, &-self.nonce, negated),
+            s,
+            ..*self
+        }
+    }
+
+    pub fn verify(&self) -> bool {
+        let (r, s_inv) = match (self.r(), Option::<C::ScalarExt>::from(self.s.invert())) {
+            (Some(r), Some(s_inv)) => (r, s_inv),
+            _ => return false,
+        };
+        C::generator() * (self.msg_hash * s_inv) + self.public_key * (r * s_inv)
+            == self.nonce.to_curve()
+    }
+}
+
+/// Draws a random 128-bit coefficient.
+fn coefficient<F: PrimeField>(rng: &mut impl RngCore) -> F {
+    F::from_u128(((rng.next_u64() as u128) << 64) | rng.next_u64() as u128)
+}
+
+/// Returns true if all the Schnorr signatures are valid, except with
+/// probability about `2^-128`.
+pub fn batch_verify_schnorr<C: CurveAffine>(
+    equations: &[SchnorrEquation<C>],
+    mut rng: impl RngCore,
+) -> bool {
+    // sum_i c_i (s_i G - R_i - e_i P_i) = 0
+    let mut scalars = Vec::with_capacity(2 * equations.len() + 1);
+    let mut bases = Vec::with_capacity(2 * equations.len() + 1);
+    let mut generator = C::ScalarExt::ZERO;
+    for equation in equations {
+        let c: C::ScalarExt = coefficient(&mut rng);
+        generator += c * equation.response;
+        scalars.push(-c);
+        bases.push(equation.nonce);
+        scalars.push(-c * equation.challenge);
+        bases.push(equation.public_key);
+    }
+    scalars.push(generator);
+    bases.push(C::generator());
+
+    bool::from(msm(&scalars, &bases).is_identity())
+}
+
+/// Returns true if all the ECDSA signatures are valid, except with
+/// probability about `2^-128`.
+pub fn batch_verify_ecdsa<C: CurveAffine>(
+    equations: &[EcdsaEquation<C>],
+    mut rng: impl RngCore,
+) -> bool
+where
+    C::ScalarExt: FromUniformBytes<64>,
+{
+    let mut s_inv: Vec<_> = equations.iter().map(|equation| equation.s).collect();
+    if s_inv.iter().any(|s| bool::from(s.is_zero())) {
+        return false;
+    }
+    s_inv.iter_mut().batch_invert();
+
+    // sum_i c_i (z_i / s_i G + r_i / s_i P_i - R_i) = 0
+    let mut scalars = Vec::with_capacity(2 * equations.len() + 1);
+    let mut bases = Vec::with_capacity(2 * equations.len() + 1);
+    let mut generator = C::ScalarExt::ZERO;
+    for (equation, s_inv) in equations.iter().zip(s_inv) {
+        let r = match equation.r() {
+            Some(r) => r,
+            None => return false,
+        };
+        let c: C::ScalarExt = coefficient(&mut rng);
+        let c_s = c * s_inv;
+        generator += c_s * equation.msg_hash;
+        scalars.push(c_s * r);
+        bases.push(equation.public_key);
+        scalars.push(-c);
+        bases.push(equation.nonce);
+    }
+    scalars.push(generator);
+    bases.push(C::generator());
+
+    bool::from(msm(&scalars, &bases).is_identity())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bn256, secp256k1, secp256r1};
+    use group::Curve;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn public_key<C: CurveAffine>(sk: &C::ScalarExt) -> C {
+        (C::generator() * sk).to_affine()
+    }
+
+    fn schnorr_equations<C: CurveAffine>(
+        rng: &mut XorShiftRng,
+        n: usize,
+    ) -> Vec<SchnorrEquation<C>> {
+        (0..n)
+            .map(|_| {
+                let sk = C::ScalarExt::random(&mut *rng);
+                let k = C::ScalarExt::random(&mut *rng);
+                let challenge = C::ScalarExt::random(&mut *rng);
+                SchnorrEquation {
+                    public_key: public_key(&sk),
+                    nonce: public_key(&k),
+                    challenge,
+                    response: k + challenge * sk,
+                }
+            })
+            .collect()
+    }
+
+    fn ecdsa_equations<C: CurveAffine>(rng: &mut XorShiftRng, n: usize) -> Vec<EcdsaEquation<C>>
+    where
+        C::ScalarExt: FromUniformBytes<64>,
+    {
+        (0..n)
+            .map(|_| {
+                let sk = C::ScalarExt::random(&mut *rng);
+                let k = C::ScalarExt::random(&mut *rng);
+                let msg_hash = C::ScalarExt::random(&mut *rng);
+                let mut equation = EcdsaEquation {
+                    public_key: public_key(&sk),
+                    nonce: public_key(&k),
+                    msg_hash,
+                    s: C::ScalarExt::ZERO,
+                };
+                equation.s = k.invert().unwrap() * (msg_hash + equation.r().unwrap() * sk);
+                equation
+            })
+            .collect()
+    }
+
+    fn batch_verify_test<C: CurveAffine>(rng: &mut XorShiftRng)
+    where
+        C::ScalarExt: FromUniformBytes<64>,
+    {
+        assert!(batch_verify_schnorr::<C>(&[], &mut *rng));
+        assert!(batch_verify_ecdsa::<C>(&[], &mut *rng));
+
+        let mut schnorr = schnorr_equations::<C>(rng, 20);
+        assert!(schnorr.iter().all(SchnorrEquation::verify));
+        assert!(batch_verify_schnorr(&schnorr, &mut *rng));
+        schnorr[7].response += C::ScalarExt::ONE;
+        assert!(!schnorr[7].verify());
+        assert!(!batch_verify_schnorr(&schnorr, &mut *rng));
+
+        let mut ecdsa = ecdsa_equations::<C>(rng, 20);
+        assert!(ecdsa.iter().all(EcdsaEquation::verify));
+        assert!(batch_verify_ecdsa(&ecdsa, &mut *rng));
+        ecdsa[3].msg_hash += C::ScalarExt::ONE;
+        assert!(!ecdsa[3].verify());
+        assert!(!batch_verify_ecdsa(&ecdsa, &mut *rng));
+        ecdsa[3].msg_hash -= C::ScalarExt::ONE;
+        for equation in ecdsa.iter_mut() {
+            *equation = equation.normalized(Normalization::LowS);
+            assert!(!bool::from(Normalization::LowS.needs_negation(&equation.s)));
+        }
+        assert!(ecdsa.iter().all(EcdsaEquation::verify));
+        ecdsa[5].nonce = -ecdsa[5].nonce;
+        assert!(!ecdsa[5].verify());
+        assert!(!batch_verify_ecdsa(&ecdsa, &mut *rng));
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        batch_verify_test::<secp256k1::Secp256k1Affine>(&mut rng);
+        batch_verify_test::<secp256r1::Secp256r1Affine>(&mut rng);
+        batch_verify_test::<bn256::G1Affine>(&mut rng);
+    }
+}
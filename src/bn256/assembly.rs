@@ -65,7 +65,21 @@ macro_rules! field_arithmetic_asm {
             /// Squares this element.
             #[inline]
             pub fn square(&self) -> $field {
-                self.mul(self)
+                #[cfg(feature = "op-count")]
+                $crate::stats::record_square();
+                self.mul_inner(self)
+            }
+
+            /// Montgomery reduction of the 512-bit little-endian integer `r`,
+            /// returning `r / 2^256 mod p`. `r` must be smaller than `p * 2^256`,
+            /// which holds for the product of two field elements' limbs, so
+            /// wide products computed elsewhere can be reduced with the
+            /// constants of this field.
+            #[inline]
+            pub fn montgomery_reduce(r: &[u64; 8]) -> $field {
+                // r / 2^256 = lo / 2^256 + hi, where hi < p.
+                let lo = $field([r[0], r[1], r[2], r[3]]).montgomery_reduce_256();
+                lo.add(&$field([r[4], r[5], r[6], r[7]]))
             }
 
             #[inline(always)]
@@ -202,6 +216,13 @@ macro_rules! field_arithmetic_asm {
             /// Multiplies `rhs` by `self`, returning the result.
             #[inline]
             pub fn mul(&self, rhs: &Self) -> $field {
+                #[cfg(feature = "op-count")]
+                $crate::stats::record_mul();
+                self.mul_inner(rhs)
+            }
+
+            #[inline(always)]
+            fn mul_inner(&self, rhs: &Self) -> $field {
                 let mut r0: u64;
                 let mut r1: u64;
                 let mut r2: u64;
@@ -8,9 +8,14 @@ use crate::bn256::Fr;
 use crate::endo;
 use crate::ff::WithSmallOrderMulGroup;
 use crate::ff::{Field, PrimeField};
+use crate::generic_curve::GenericCurve;
 use crate::group::Curve;
-use crate::group::{cofactor::CofactorGroup, prime::PrimeCurveAffine, Group, GroupEncoding};
+use crate::group::{
+    cofactor::CofactorGroup, prime::PrimeCurveAffine, Group, GroupEncoding, UncompressedEncoding,
+};
 use crate::hash_to_curve::svdw_hash_to_curve;
+use crate::normalize::Normalization;
+use crate::torsion::{div_limbs, mul_by_limbs, SmallOrder};
 use crate::{
     impl_add_binop_specify_output, impl_binops_additive, impl_binops_additive_specify_output,
     impl_binops_multiplicative, impl_binops_multiplicative_mixed, impl_sub_binop_specify_output,
@@ -18,7 +23,7 @@ use crate::{
 };
 use crate::{Coordinates, CurveAffine, CurveExt};
 use core::cmp;
-use core::fmt::Debug;
+use core::fmt::{self, Debug};
 use core::iter::Sum;
 use core::ops::{Add, Mul, Neg, Sub};
 use rand::RngCore;
@@ -127,6 +132,17 @@ const ENDO_PARAMS_BN: EndoParameters = EndoParameters {
 
 endo!(G1, Fr, ENDO_PARAMS_BN);
 
+/// Returns the constants of the BN254 G1 curve and of its fields.
+pub fn params() -> crate::params::CurveParams {
+    crate::params::CurveParams::of::<G1>(Some(&ENDO_PARAMS_BN))
+}
+
+impl G1 {
+    /// The cofactor of the prime-order subgroup, as little-endian 64-bit
+    /// limbs.
+    pub const COFACTOR: [u64; 4] = [1, 0, 0, 0];
+}
+
 impl group::cofactor::CofactorGroup for G1 {
     type Subgroup = G1;
 
@@ -143,6 +159,47 @@ impl group::cofactor::CofactorGroup for G1 {
     }
 }
 
+impl G2 {
+    /// The cofactor of the prime-order subgroup, as little-endian 64-bit
+    /// limbs.
+    pub const COFACTOR: [u64; 4] = [
+        0x345f2299c0f9fa8d,
+        0x06ceecda572a2489,
+        0xb85045b68181585e,
+        0x30644e72e131a029,
+    ];
+
+    /// The prime factors of `COFACTOR` that fit in a `u64`. The remaining
+    /// factor is a 177-bit prime.
+    pub const SMALL_COFACTOR_PRIMES: [u64; 3] = [10069, 5864401, 1875725156269];
+}
+
+impl SmallOrder for G2 {
+    fn torsion_generators() -> Vec<(u64, Self)> {
+        // The twist has a cyclic group of order r * COFACTOR, so `[r] P` has
+        // order dividing the cofactor and `[COFACTOR / q][r] P` has order `q`
+        // unless it is the identity.
+        (1u64..)
+            .find_map(|k| {
+                let x = Fq2 {
+                    c0: Fq::from(k),
+                    c1: Fq::ZERO,
+                };
+                let y = Option::<Fq2>::from((x.square() * x + G2::b()).sqrt())?;
+                let p = mul_by_limbs(&G2Affine { x, y }.to_curve(), &G2::ORDER_LIMBS);
+                let generators: Vec<_> = G2::SMALL_COFACTOR_PRIMES
+                    .iter()
+                    .map(|q| (*q, mul_by_limbs(&p, &div_limbs(&G2::COFACTOR, *q))))
+                    .collect();
+                generators
+                    .iter()
+                    .all(|(_, t)| !bool::from(t.is_identity()))
+                    .then_some(generators)
+            })
+            .unwrap()
+    }
+}
+
 impl CofactorGroup for G2 {
     type Subgroup = G2;
 
@@ -198,11 +255,265 @@ impl G1 {
     const SVDW_Z: Fq = Fq::ONE;
 }
 
+// ξ = 9 + u, the non-residue used to build the tower over `Fq2`.
+const XI: Fq2 = Fq2 {
+    c0: Fq::from_raw([9, 0, 0, 0]),
+    c1: Fq::one(),
+};
+
+/// The curves `y^2 = x^3 + b` over `Fq2` that G2 points coming from other
+/// libraries usually live on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum G2Twist {
+    /// `b = 3 / ξ`, the D-type sextic twist used by `G2`.
+    D,
+    /// `b = 3 ξ`, the M-type sextic twist.
+    M,
+    /// `b = 3 ξ^2`, the quadratic twist of `G2` by `ξ`.
+    Quadratic,
+    /// `b = 3`, the curve of `G1` taken over `Fq2`.
+    Base,
+}
+
+impl G2Twist {
+    /// Returns the `b` coefficient of the curve.
+    pub fn b(&self) -> Fq2 {
+        match self {
+            G2Twist::D => G2_B,
+            G2Twist::M => XI * G1_B_FQ2,
+            G2Twist::Quadratic => G2_B * XI.square() * XI,
+            G2Twist::Base => G1_B_FQ2,
+        }
+    }
+
+    /// Returns the curve as a `GenericCurve`.
+    pub fn curve(&self) -> GenericCurve<Fq2> {
+        GenericCurve::new(G2_A, self.b()).unwrap()
+    }
+}
+
+const G1_B_FQ2: Fq2 = Fq2 {
+    c0: G1_B,
+    c1: Fq::zero(),
+};
+
+impl G2Affine {
+    /// Returns which of the curves in `G2Twist` the point `(x, y)` lies on, if
+    /// any. This is useful to diagnose G2 data that fails to deserialize
+    /// because it was produced for a different twist.
+    pub fn twist_of(x: &Fq2, y: &Fq2) -> Option<G2Twist> {
+        let b = y.square() - x.square() * x;
+        [G2Twist::D, G2Twist::M, G2Twist::Quadratic, G2Twist::Base]
+            .into_iter()
+            .find(|twist| twist.b() == b)
+    }
+}
+
+/// The point encodings recognized by `from_bytes_auto`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PointEncoding {
+    /// The `GroupEncoding` and `UncompressedEncoding` of this crate.
+    Native,
+    /// gnark-crypto: big-endian, with the flags in the top two bits of the
+    /// first byte.
+    Gnark,
+    /// arkworks: little-endian, with the flags in the top two bits of the
+    /// last byte. Uncompressed points also carry the sign of `y` there.
+    Arkworks,
+}
+
+/// Returns true if `y` is larger than `-y`, the root that gnark-crypto and
+/// arkworks flag, i.e. if `y` is not the smallest root.
+fn is_largest_fq(y: &Fq) -> bool {
+    bool::from(Normalization::SmallestRoot.needs_negation(y))
+}
+
+/// Returns true if `y` is larger than `-y` comparing `c1` first, as both
+/// libraries order `Fq2` lexicographically from its top coefficient.
+fn is_largest_fq2(y: &Fq2) -> bool {
+    let top = if bool::from(y.c1.is_zero()) {
+        &y.c0
+    } else {
+        &y.c1
+    };
+    is_largest_fq(top)
+}
+
+/// The error returned by `from_bytes_auto`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromBytesAutoError<C> {
+    /// The bytes are not a point in any of the encodings.
+    Unrecognized,
+    /// The bytes are a point in several encodings, listed with the point each
+    /// of them decodes to. These may differ, so the caller has to know which
+    /// encoding produced the bytes.
+    Ambiguous(Vec<(C, PointEncoding)>),
+}
+
+impl<C> fmt::Display for FromBytesAutoError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromBytesAutoError::Unrecognized => write!(f, "not a point in any known encoding"),
+            FromBytesAutoError::Ambiguous(candidates) => {
+                write!(f, "a point in {} different encodings", candidates.len())
+            }
+        }
+    }
+}
+
+impl<C: Debug> std::error::Error for FromBytesAutoError<C> {}
+
+macro_rules! impl_from_bytes_auto {
+    ($affine:ident, $curve:ident, $base:ident, $is_largest:ident) => {
+        impl $affine {
+            /// Decodes a compressed or uncompressed point in whichever of the
+            /// encodings of `PointEncoding` parses it, and returns that
+            /// encoding along with the point. Bytes that parse in more than one
+            /// encoding are reported as ambiguous rather than guessed, which
+            /// is common for compressed points: the flags of one library are
+            /// often valid coordinate bits of another. zcash's encoding needs
+            /// three flag bits, which BN254 does not have room for, so it is
+            /// not recognized.
+            pub fn from_bytes_auto(
+                bytes: &[u8],
+            ) -> Result<($affine, PointEncoding), FromBytesAutoError<$affine>> {
+                let mut candidates: Vec<_> = [
+                    PointEncoding::Native,
+                    PointEncoding::Gnark,
+                    PointEncoding::Arkworks,
+                ]
+                .into_iter()
+                .filter_map(|encoding| {
+                    Self::from_bytes_as(bytes, encoding).map(|point| (point, encoding))
+                })
+                .collect();
+                match candidates.len() {
+                    0 => Err(FromBytesAutoError::Unrecognized),
+                    1 => Ok(candidates.remove(0)),
+                    _ => Err(FromBytesAutoError::Ambiguous(candidates)),
+                }
+            }
+
+            /// Decodes a compressed or uncompressed point in `encoding`, and
+            /// checks that it is in the prime-order subgroup, as gnark-crypto
+            /// and arkworks do.
+            pub fn from_bytes_as(bytes: &[u8], encoding: PointEncoding) -> Option<$affine> {
+                Self::from_bytes_as_unchecked(bytes, encoding)
+                    .filter(|p| bool::from($curve::from(*p).is_torsion_free()))
+            }
+
+            /// Decodes a compressed or uncompressed point in `encoding`
+            /// without checking that it is in the prime-order subgroup.
+            #[cfg_attr(
+                all(feature = "panic-lints", not(test)),
+                deny(
+                    clippy::unwrap_used,
+                    clippy::expect_used,
+                    clippy::panic,
+                    clippy::unreachable,
+                    clippy::indexing_slicing
+                )
+            )]
+            pub fn from_bytes_as_unchecked(
+                bytes: &[u8],
+                encoding: PointEncoding,
+            ) -> Option<$affine> {
+                let size = $base::size();
+                let compressed = if bytes.len() == size {
+                    true
+                } else if bytes.len() == 2 * size {
+                    false
+                } else {
+                    return None;
+                };
+
+                let parse_le = |bytes: &[u8]| -> Option<$base> {
+                    let mut repr = [0u8; $base::size()];
+                    repr.copy_from_slice(bytes);
+                    Option::from($base::from_bytes(&repr))
+                };
+                let parse_be = |bytes: &[u8]| {
+                    let mut bytes = bytes.to_vec();
+                    bytes.reverse();
+                    parse_le(&bytes)
+                };
+                let from_xy = |x, y| Option::<$affine>::from($affine::from_xy(x, y));
+                let decompress = |x: $base, largest: bool| {
+                    let y = Option::<$base>::from(
+                        (x.square() * x + $affine::a() * x + $affine::b()).sqrt(),
+                    )?;
+                    Some($affine {
+                        x,
+                        y: if $is_largest(&y) == largest { y } else { -y },
+                    })
+                };
+
+                match encoding {
+                    PointEncoding::Native => {
+                        if compressed {
+                            let mut repr = <$affine as GroupEncoding>::Repr::default();
+                            repr.as_mut().copy_from_slice(bytes);
+                            Option::from($affine::from_bytes(&repr))
+                        } else {
+                            let mut repr =
+                                <$affine as UncompressedEncoding>::Uncompressed::default();
+                            repr.as_mut().copy_from_slice(bytes);
+                            Option::from($affine::from_uncompressed(&repr))
+                        }
+                    }
+                    PointEncoding::Gnark => {
+                        let mut bytes = bytes.to_vec();
+                        let first = bytes.first_mut()?;
+                        let flags = *first & 0xc0;
+                        *first &= 0x3f;
+                        let zero = bytes.iter().all(|byte| *byte == 0);
+                        match (compressed, flags) {
+                            (_, 0x40) => zero.then($affine::identity),
+                            (true, 0x80) | (true, 0xc0) => {
+                                decompress(parse_be(&bytes)?, flags == 0xc0)
+                            }
+                            (false, 0x00) if !zero => {
+                                let (x, y) = bytes.split_at(size);
+                                from_xy(parse_be(x)?, parse_be(y)?)
+                            }
+                            _ => None,
+                        }
+                    }
+                    PointEncoding::Arkworks => {
+                        let mut bytes = bytes.to_vec();
+                        let last = bytes.last_mut()?;
+                        let flags = *last & 0xc0;
+                        *last &= 0x3f;
+                        let zero = bytes.iter().all(|byte| *byte == 0);
+                        match (compressed, flags) {
+                            (_, 0x40) => zero.then($affine::identity),
+                            (true, 0x00) | (true, 0x80) => {
+                                decompress(parse_le(&bytes)?, flags == 0x80)
+                            }
+                            (false, 0x00) | (false, 0x80) if !zero => {
+                                let (x, y) = bytes.split_at(size);
+                                let p = from_xy(parse_le(x)?, parse_le(y)?)?;
+                                ($is_largest(&p.y) == (flags == 0x80)).then(|| p)
+                            }
+                            _ => None,
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_from_bytes_auto!(G1Affine, G1, Fq, is_largest_fq);
+impl_from_bytes_auto!(G2Affine, G2, Fq2, is_largest_fq2);
+
 #[cfg(test)]
 mod tests {
+    use super::{is_largest_fq, is_largest_fq2, FromBytesAutoError, PointEncoding};
     use crate::arithmetic::CurveEndo;
     use crate::bn256::{Fr, G1, G2};
     use crate::CurveExt;
+    use core::fmt::Debug;
     use ff::Field;
     use ff::{PrimeField, WithSmallOrderMulGroup};
     use rand_core::OsRng;
@@ -212,6 +523,50 @@ mod tests {
         crate::tests::curve::hash_to_curve_test::<G1>();
     }
 
+    #[test]
+    fn test_derive_generators() {
+        crate::tests::curve::derive_generators_test::<G1>();
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_derive_generators_index_overflow() {
+        use crate::hash_to_curve::{derive_generators, generator_domain, GeneratorIndexError};
+
+        let index = u32::MAX as usize + 1;
+        assert_eq!(
+            generator_domain("test", index),
+            Err(GeneratorIndexError(index))
+        );
+        assert_eq!(
+            derive_generators::<G1>("test", index + 1),
+            Err(GeneratorIndexError(index))
+        );
+    }
+
+    #[test]
+    fn test_hash_to_scalar() {
+        crate::tests::curve::hash_to_scalar_test::<G1>();
+
+        // Computed independently from the BLAKE2b expand_message_xmd with
+        // len_in_bytes = 64 and DST "test-bn256_g1_XMD:BLAKE2b_H2S_RO_".
+        for (message, expected) in [
+            (
+                &b"message"[..],
+                "17894484815283901465600973184481196864222927785810448676947350524481070242851",
+            ),
+            (
+                &b""[..],
+                "9004550753328960612066026466203855158778550507539385724987144311774669158940",
+            ),
+        ] {
+            assert_eq!(
+                crate::hash_to_curve::hash_to_scalar::<G1>(message, "test"),
+                Fr::from_str_vartime(expected).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn test_map_to_curve() {
         crate::tests::curve::svdw_map_to_curve_test::<G1>(
@@ -264,10 +619,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_twist_of() {
+        use super::{G2Twist, XI};
+        use crate::bn256::{Fq2, G2Affine};
+        use crate::group::Curve;
+
+        let g = G2::generator().to_affine();
+        assert_eq!(G2Affine::twist_of(&g.x, &g.y), Some(G2Twist::D));
+        assert_eq!(
+            G2Twist::D.curve().quadratic_twist(&XI),
+            G2Twist::Quadratic.curve()
+        );
+
+        for twist in [G2Twist::D, G2Twist::M, G2Twist::Quadratic, G2Twist::Base] {
+            let curve = twist.curve();
+            let point = std::iter::repeat_with(|| curve.lift_x(Fq2::random(OsRng)))
+                .flatten()
+                .next()
+                .unwrap();
+            let (x, y) = point.coordinates().unwrap();
+            assert_eq!(G2Affine::twist_of(&x, &y), Some(twist));
+            assert_eq!(G2Affine::twist_of(&x, &(y + Fq2::one())), None);
+        }
+    }
+
     #[test]
     fn test_curve() {
         crate::tests::curve::curve_tests::<G1>();
         crate::tests::curve::curve_tests::<G2>();
+        crate::tests::curve::batch_add_test::<G1>();
+        crate::tests::curve::group_traits_test::<G1>();
+        crate::tests::curve::batch_add_test::<G2>();
+        crate::tests::curve::group_traits_test::<G2>();
     }
 
     #[test]
@@ -311,4 +695,383 @@ mod tests {
             crate::tests::curve::random_serde_test::<G2>();
         }
     }
+
+    #[test]
+    fn test_decompression_hint() {
+        use crate::bn256::{G1Affine, G2Affine};
+        use group::prime::PrimeCurveAffine;
+        use group::{Curve, Group, GroupEncoding};
+
+        macro_rules! check {
+            ($affine:ident, $curve:ident) => {
+                for p in [
+                    $affine::identity(),
+                    $affine::generator(),
+                    $curve::random(OsRng).to_affine(),
+                ] {
+                    let (bytes, hint) = p.to_bytes_with_hint();
+                    assert_eq!(bytes, p.to_bytes());
+                    assert_eq!($affine::from_bytes_with_hint(&bytes, &hint).unwrap(), p);
+                    if !bool::from(p.is_identity()) {
+                        assert!(bool::from(
+                            $affine::from_bytes_with_hint(&bytes, &-hint).is_none()
+                        ));
+                        assert!(bool::from(
+                            $affine::from_bytes_with_hint(&bytes, &(hint + hint + hint)).is_none()
+                        ));
+                    }
+                }
+            };
+        }
+
+        check!(G1Affine, G1);
+        check!(G2Affine, G2);
+    }
+
+    #[test]
+    fn test_in_place() {
+        use crate::bn256::{Fq, G1Affine};
+        use group::{Curve, Group};
+
+        let mut p = G1::random(OsRng);
+        let q = G1::random(OsRng).to_affine();
+        let expected = p.double() + q - q - q;
+        p.double_in_place();
+        p.add_assign_mixed(&q);
+        p.sub_assign_mixed(&q);
+        p.sub_assign_mixed(&q);
+        assert_eq!(p, expected);
+
+        let mut p = G1::identity();
+        p.add_assign_mixed(&G1Affine::identity());
+        assert_eq!(p, G1::identity());
+
+        let a = Fq::random(OsRng);
+        let b = Fq::random(OsRng);
+        let mut c = a;
+        c *= b;
+        c.square_assign();
+        c.double_assign();
+        assert_eq!(c, (a * b).square().double());
+    }
+
+    #[test]
+    fn test_is_largest() {
+        use crate::bn256::{Fq, Fq2};
+
+        // The roots are compared as big-endian byte strings, `c1` first.
+        fn is_largest_be(bytes: &[u8], neg_bytes: &[u8]) -> bool {
+            bytes.iter().rev().gt(neg_bytes.iter().rev())
+        }
+
+        for _ in 0..100 {
+            let y = Fq::random(OsRng);
+            assert_eq!(
+                is_largest_fq(&y),
+                is_largest_be(&y.to_bytes(), &(-y).to_bytes())
+            );
+            for y in [
+                Fq2::random(OsRng),
+                Fq2 {
+                    c0: y,
+                    c1: Fq::ZERO,
+                },
+            ] {
+                assert_eq!(
+                    is_largest_fq2(&y),
+                    is_largest_be(&y.to_bytes(), &(-y).to_bytes())
+                );
+                assert_ne!(is_largest_fq2(&y), is_largest_fq2(&-y));
+            }
+        }
+        assert!(!is_largest_fq(&Fq::ZERO));
+        assert!(!is_largest_fq2(&Fq2::ZERO));
+    }
+
+    /// Checks that `from_bytes_auto` finds `p` in `encoding`, alone or among
+    /// the candidates of an ambiguous input.
+    fn assert_auto<C: Debug + PartialEq>(
+        result: Result<(C, PointEncoding), FromBytesAutoError<C>>,
+        p: C,
+        encoding: PointEncoding,
+    ) {
+        match result {
+            Ok(decoded) => assert_eq!(decoded, (p, encoding)),
+            Err(FromBytesAutoError::Ambiguous(candidates)) => {
+                assert!(candidates.contains(&(p, encoding)))
+            }
+            Err(err) => panic!("{:?} not decoded: {}", p, err),
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_auto() {
+        use crate::bn256::{Fq, Fq2, G1Affine, G1Compressed, G2Affine};
+        use crate::CurveAffine;
+        use group::cofactor::CofactorGroup;
+        use group::prime::PrimeCurveAffine;
+        use group::{Curve, Group, GroupEncoding, UncompressedEncoding};
+
+        macro_rules! check {
+            ($affine:ident, $curve:ident, $is_largest:ident) => {
+                for p in [$affine::generator(), $curve::random(OsRng).to_affine()] {
+                    for bytes in [
+                        p.to_bytes().as_ref().to_vec(),
+                        p.to_uncompressed().as_ref().to_vec(),
+                    ] {
+                        assert_auto($affine::from_bytes_auto(&bytes), p, PointEncoding::Native);
+                    }
+
+                    let largest = $is_largest(&p.y);
+                    let mut x = p.x.to_bytes();
+                    let mut y = p.y.to_bytes();
+                    x.reverse();
+                    y.reverse();
+
+                    let mut gnark = x.to_vec();
+                    gnark[0] |= if largest { 0xc0 } else { 0x80 };
+                    assert_eq!(
+                        $affine::from_bytes_as(&gnark, PointEncoding::Gnark),
+                        Some(p)
+                    );
+                    assert_auto($affine::from_bytes_auto(&gnark), p, PointEncoding::Gnark);
+                    let gnark = [x.to_vec(), y.to_vec()].concat();
+                    assert_eq!(
+                        $affine::from_bytes_as(&gnark, PointEncoding::Gnark),
+                        Some(p)
+                    );
+                    assert_auto($affine::from_bytes_auto(&gnark), p, PointEncoding::Gnark);
+
+                    let mut arkworks = p.x.to_bytes().to_vec();
+                    *arkworks.last_mut().unwrap() |= if largest { 0x80 } else { 0 };
+                    assert_eq!(
+                        $affine::from_bytes_as(&arkworks, PointEncoding::Arkworks),
+                        Some(p)
+                    );
+                    assert_auto(
+                        $affine::from_bytes_auto(&arkworks),
+                        p,
+                        PointEncoding::Arkworks,
+                    );
+                }
+
+                let size = compressed_size::<$affine>();
+                let mut infinity = vec![0u8; size];
+                infinity[0] = 0x40;
+                assert_eq!(
+                    $affine::from_bytes_as(&infinity, PointEncoding::Gnark),
+                    Some($affine::identity())
+                );
+                infinity.reverse();
+                assert_eq!(
+                    $affine::from_bytes_as(&infinity, PointEncoding::Arkworks),
+                    Some($affine::identity())
+                );
+                assert_eq!(
+                    $affine::from_bytes_auto(&[0u8; 3]),
+                    Err(FromBytesAutoError::Unrecognized)
+                );
+            };
+        }
+
+        fn compressed_size<C: GroupEncoding>() -> usize {
+            C::Repr::default().as_ref().len()
+        }
+
+        check!(G1Affine, G1, is_largest_fq);
+        check!(G2Affine, G2, is_largest_fq2);
+
+        // The native encoding rejects the infinity flag with a nonzero x, so
+        // the arkworks encoding of -g1 is not read as a native point.
+        let g1 = G1Affine::generator();
+        let mut negative = [0u8; 32];
+        negative[0] = 1;
+        negative[31] = 0x80;
+        let repr = G1Compressed::try_from(&negative[..]).unwrap();
+        assert!(bool::from(G1Affine::from_bytes(&repr).is_none()));
+        assert_eq!(
+            G1Affine::from_bytes_auto(&negative),
+            Ok((-g1, PointEncoding::Arkworks))
+        );
+
+        // Points of G2 outside the prime-order subgroup are rejected.
+        let p = (1..)
+            .find_map(|k| {
+                let x = Fq2 {
+                    c0: Fq::from(k),
+                    c1: Fq::ZERO,
+                };
+                Option::<Fq2>::from((x.square() * x + G2::b()).sqrt()).map(|y| G2Affine { x, y })
+            })
+            .unwrap();
+        assert!(bool::from(p.is_on_curve()));
+        assert!(!bool::from(G2::from(p).is_torsion_free()));
+        let mut gnark = p.x.to_bytes();
+        gnark.reverse();
+        gnark[0] |= if is_largest_fq2(&p.y) { 0xc0 } else { 0x80 };
+        assert_eq!(G2Affine::from_bytes_as(&gnark, PointEncoding::Gnark), None);
+        assert_eq!(
+            G2Affine::from_bytes_as_unchecked(&gnark, PointEncoding::Gnark),
+            Some(p)
+        );
+        let mut arkworks = p.to_uncompressed().as_ref().to_vec();
+        arkworks[127] |= if is_largest_fq2(&p.y) { 0x80 } else { 0 };
+        assert_eq!(
+            G2Affine::from_bytes_as(&arkworks, PointEncoding::Arkworks),
+            None
+        );
+    }
+
+    #[test]
+    fn test_arkworks_vectors() {
+        use crate::bn256::{G1Affine, G2Affine};
+        use group::prime::PrimeCurveAffine;
+        use group::Group;
+
+        fn hex(s: &str) -> Vec<u8> {
+            (0..s.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+                .collect()
+        }
+
+        // `serialize_compressed` and `serialize_uncompressed` of ark-bn254
+        // points. The negations have `y > -y`, so arkworks sets the
+        // `YIsNegative` flag (0x80) in both encodings.
+        let g1 = G1Affine::generator();
+        let g1_double = (G1::generator().double()).to_affine();
+        for (point, compressed, uncompressed) in [
+            (
+                g1,
+                "0100000000000000000000000000000000000000000000000000000000000000",
+                "0100000000000000000000000000000000000000000000000000000000000000\
+                 0200000000000000000000000000000000000000000000000000000000000000",
+            ),
+            (
+                -g1,
+                "0100000000000000000000000000000000000000000000000000000000000080",
+                "0100000000000000000000000000000000000000000000000000000000000000\
+                 45fd7cd8168c203c8dca7168916a81975d588181b64550b829a031e1724e64b0",
+            ),
+            (
+                g1_double,
+                "d3cf876dc108c2d3a81c8716a91678d9851518685b04859b021a132ee7440603",
+                "d3cf876dc108c2d3a81c8716a91678d9851518685b04859b021a132ee7440603\
+                 c4a2185a7abf3effc78f53e349a4a6680a9caeb2965f84e7927c0a0e8c73ed15",
+            ),
+            (
+                -g1_double,
+                "d3cf876dc108c2d3a81c8716a91678d9851518685b04859b021a132ee7440683",
+                "d3cf876dc108c2d3a81c8716a91678d9851518685b04859b021a132ee7440603\
+                 835a647e9ccce13cc53a1e8547c6da2e53bcd2ce1fe6cbd0962327d3e6da769a",
+            ),
+        ] {
+            assert_eq!(
+                G1Affine::from_bytes_as(&hex(compressed), PointEncoding::Arkworks),
+                Some(point)
+            );
+            assert_eq!(
+                G1Affine::from_bytes_as(&hex(uncompressed), PointEncoding::Arkworks),
+                Some(point)
+            );
+        }
+
+        let g2 = G2Affine::generator();
+        for (point, compressed, uncompressed) in [
+            (
+                g2,
+                "edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018\
+                 c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19",
+                "edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018\
+                 c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19\
+                 aa7dfa6601cce64c7bd3430c69e7d1e38f40cb8d8071ab4aeb6d8cdba55ec812\
+                 5b9722d1dcdaac55f38eb37033314bbc95330c69ad999eec75f05f58d0890609",
+            ),
+            (
+                -g2,
+                "edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018\
+                 c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e99",
+                "edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018\
+                 c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19\
+                 9d7f827115c039ef11f72d5c2883afb3cd17b6f335d4a46d3e32a505cdef9b1d\
+                 ec655a073ab173e6993bbef75d3936dbc724751809acb1cbb3afd188a2c45da7",
+            ),
+        ] {
+            assert_eq!(
+                G2Affine::from_bytes_as(&hex(compressed), PointEncoding::Arkworks),
+                Some(point)
+            );
+            assert_eq!(
+                G2Affine::from_bytes_as(&hex(uncompressed), PointEncoding::Arkworks),
+                Some(point)
+            );
+        }
+
+        // A flagged point is not a native one, and is detected as arkworks.
+        let mut negative = hex(
+            "0100000000000000000000000000000000000000000000000000000000000000\
+             45fd7cd8168c203c8dca7168916a81975d588181b64550b829a031e1724e64b0",
+        );
+        assert_eq!(
+            G1Affine::from_bytes_auto(&negative),
+            Ok((-g1, PointEncoding::Arkworks))
+        );
+        // The flag has to match the sign of y.
+        negative[63] &= 0x7f;
+        assert_eq!(
+            G1Affine::from_bytes_as(&negative, PointEncoding::Arkworks),
+            None
+        );
+        let mut positive = hex(
+            "0100000000000000000000000000000000000000000000000000000000000000\
+             0200000000000000000000000000000000000000000000000000000000000000",
+        );
+        positive[63] |= 0x80;
+        assert_eq!(
+            G1Affine::from_bytes_as(&positive, PointEncoding::Arkworks),
+            None
+        );
+    }
+
+    #[test]
+    fn test_mul_blinded() {
+        use group::Group;
+
+        for _ in 0..10 {
+            let p = G1::random(OsRng);
+            let k = Fr::random(OsRng);
+            assert_eq!(p.mul_blinded(&k, OsRng), p * k);
+            let q = G2::random(OsRng);
+            assert_eq!(q.mul_blinded(&k, OsRng), q * k);
+        }
+        assert_eq!(G1::identity().mul_blinded(&Fr::ONE, OsRng), G1::identity());
+        assert_eq!(
+            G1::generator().mul_blinded(&Fr::ZERO, OsRng),
+            G1::identity()
+        );
+    }
+
+    #[test]
+    fn test_byte_conversions() {
+        use crate::bn256::{Fq2, G1Affine, G1Compressed, G1Uncompressed, G2Affine};
+        use group::{Curve, Group, GroupEncoding, UncompressedEncoding};
+
+        let p = G1::random(OsRng).to_affine();
+        let bytes: [u8; 32] = p.to_bytes().into();
+        assert_eq!(G1Affine::try_from(&bytes[..]), Ok(p));
+        assert_eq!(G1::try_from(&bytes[..]), Ok(G1::from(p)));
+        assert!(G1Affine::try_from(&bytes[1..]).is_err());
+        assert_eq!(G1Compressed::try_from(&bytes[..]), Ok(p.to_bytes()));
+
+        let bytes: [u8; 64] = p.to_uncompressed().into();
+        assert_eq!(
+            G1Uncompressed::try_from(&bytes[..]).unwrap(),
+            p.to_uncompressed()
+        );
+
+        let q = G2::random(OsRng).to_affine();
+        assert_eq!(G2Affine::try_from(q.to_bytes().as_ref()), Ok(q));
+        assert_eq!(Fq2::try_from(&q.x.to_bytes()[..]), Ok(q.x));
+        assert!(Fq2::try_from(&[0xff; 64][..]).is_err());
+    }
 }
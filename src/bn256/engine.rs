@@ -1,18 +1,22 @@
 #![allow(clippy::suspicious_arithmetic_impl)]
+use crate::arithmetic::parallelize_with;
 use crate::bn256::curve::*;
 use crate::bn256::fq::*;
 use crate::bn256::fq12::*;
 use crate::bn256::fq2::*;
 use crate::bn256::fq6::FROBENIUS_COEFF_FQ6_C1;
 use crate::bn256::fr::*;
-use crate::ff::{Field, PrimeField};
+use crate::ff::{BatchInvert, Field, PrimeField};
 use crate::group::cofactor::CofactorCurveAffine;
-use crate::group::Group;
+use crate::group::{Curve, Group};
+use crate::serde::{read_table_header, write_table_header, SerdeObject};
+use crate::CurveExt;
 use core::borrow::Borrow;
 use core::iter::Sum;
 use core::ops::{Add, Mul, MulAssign, Neg, Sub};
 use pairing::{Engine, MillerLoopResult, MultiMillerLoop, PairingCurveAffine};
 use rand_core::RngCore;
+use std::io::{self, Read, Write};
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 
 pub const BN_X: u64 = 4965661367192848881;
@@ -202,7 +206,7 @@ impl Group for Gt {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct G2Prepared {
     pub(crate) coeffs: Vec<(Fq2, Fq2, Fq2)>,
     pub(crate) infinity: bool,
@@ -446,6 +450,237 @@ impl G2Prepared {
             infinity: false,
         }
     }
+
+    /// Prepares a batch of points, computing the line coefficients in affine
+    /// coordinates. Every step of the Miller loop shares a single inversion
+    /// across all points, so this is cheaper than `from_affine` whenever many
+    /// points are prepared together. Points are expected to be in the prime
+    /// order subgroup.
+    pub fn batch_from_affine(qs: &[G2Affine]) -> Vec<G2Prepared> {
+        // Line through `t` with slope `lambda`, evaluated by `ell` as
+        // `y_P + (-lambda * x_P) w + (lambda * x_T - y_T) vw`
+        fn line(lambda: &Fq2, t: &G2Affine) -> (Fq2, Fq2, Fq2) {
+            (Fq2::one(), -lambda, lambda * t.x - t.y)
+        }
+
+        fn doubling_step(
+            ts: &mut [G2Affine],
+            coeffs: &mut [Vec<(Fq2, Fq2, Fq2)>],
+            denominators: &mut [Fq2],
+        ) {
+            for (t, d) in ts.iter().zip(denominators.iter_mut()) {
+                *d = t.y.double();
+            }
+            denominators.iter_mut().batch_invert();
+
+            for ((t, coeffs), d) in ts
+                .iter_mut()
+                .zip(coeffs.iter_mut())
+                .zip(denominators.iter())
+            {
+                // lambda = 3x^2 / 2y
+                let mut lambda = t.x.square();
+                lambda = lambda.double() + lambda;
+                lambda.mul_assign(d);
+
+                coeffs.push(line(&lambda, t));
+
+                let x3 = lambda.square() - t.x.double();
+                t.y = lambda * (t.x - x3) - t.y;
+                t.x = x3;
+            }
+        }
+
+        fn addition_step(
+            ts: &mut [G2Affine],
+            qs: &[G2Affine],
+            coeffs: &mut [Vec<(Fq2, Fq2, Fq2)>],
+            denominators: &mut [Fq2],
+        ) {
+            for ((t, q), d) in ts.iter().zip(qs.iter()).zip(denominators.iter_mut()) {
+                *d = q.x - t.x;
+            }
+            denominators.iter_mut().batch_invert();
+
+            for (((t, q), coeffs), d) in ts
+                .iter_mut()
+                .zip(qs.iter())
+                .zip(coeffs.iter_mut())
+                .zip(denominators.iter())
+            {
+                // lambda = (y_Q - y_T) / (x_Q - x_T)
+                let lambda = (q.y - t.y) * d;
+
+                coeffs.push(line(&lambda, t));
+
+                let x3 = lambda.square() - t.x - q.x;
+                t.y = lambda * (t.x - x3) - t.y;
+                t.x = x3;
+            }
+        }
+
+        let mut prepared: Vec<G2Prepared> = qs
+            .iter()
+            .map(|q| G2Prepared {
+                coeffs: vec![],
+                infinity: bool::from(q.is_identity()),
+            })
+            .collect();
+
+        let indices: Vec<usize> = (0..qs.len()).filter(|&i| !prepared[i].infinity).collect();
+        let qs: Vec<G2Affine> = indices.iter().map(|&i| qs[i]).collect();
+        let negqs: Vec<G2Affine> = qs.iter().map(|q| -q).collect();
+
+        let mut ts = qs.clone();
+        let mut coeffs = vec![vec![]; qs.len()];
+        let mut denominators = vec![Fq2::zero(); qs.len()];
+
+        for i in (1..SIX_U_PLUS_2_NAF.len()).rev() {
+            doubling_step(&mut ts, &mut coeffs, &mut denominators);
+            let x = SIX_U_PLUS_2_NAF[i - 1];
+            match x {
+                1 => {
+                    addition_step(&mut ts, &qs, &mut coeffs, &mut denominators);
+                }
+                -1 => {
+                    addition_step(&mut ts, &negqs, &mut coeffs, &mut denominators);
+                }
+                _ => continue,
+            }
+        }
+
+        let q1s: Vec<G2Affine> = qs
+            .iter()
+            .map(|q| {
+                let mut q1 = *q;
+
+                q1.x.c1 = q1.x.c1.neg();
+                q1.x.mul_assign(&FROBENIUS_COEFF_FQ6_C1[1]);
+
+                q1.y.c1 = q1.y.c1.neg();
+                q1.y.mul_assign(&XI_TO_Q_MINUS_1_OVER_2);
+
+                q1
+            })
+            .collect();
+
+        addition_step(&mut ts, &q1s, &mut coeffs, &mut denominators);
+
+        let minusq2s: Vec<G2Affine> = qs
+            .iter()
+            .map(|q| {
+                let mut minusq2 = *q;
+                minusq2.x.mul_assign(&FROBENIUS_COEFF_FQ6_C1[2]);
+
+                minusq2
+            })
+            .collect();
+
+        addition_step(&mut ts, &minusq2s, &mut coeffs, &mut denominators);
+
+        for (i, coeffs) in indices.into_iter().zip(coeffs) {
+            prepared[i].coeffs = coeffs;
+        }
+
+        prepared
+    }
+
+    /// Same as `batch_from_affine`, but the points are split across at most
+    /// `num_threads` threads, or one thread per core if `None`, and every
+    /// thread prepares its share as one batch.
+    pub fn batch_from_affine_parallel(
+        qs: &[G2Affine],
+        num_threads: Option<usize>,
+    ) -> Vec<G2Prepared> {
+        let mut prepared = vec![
+            G2Prepared {
+                coeffs: vec![],
+                infinity: true,
+            };
+            qs.len()
+        ];
+        parallelize_with(num_threads, &mut prepared, |prepared, start| {
+            let batch = G2Prepared::batch_from_affine(&qs[start..start + prepared.len()]);
+            for (prepared, q) in prepared.iter_mut().zip(batch) {
+                *prepared = q;
+            }
+        });
+        prepared
+    }
+}
+
+impl G2Prepared {
+    /// The version of the format written by `write_raw`, increased whenever
+    /// the format or the line coefficients change.
+    pub const FORMAT_VERSION: u16 = 1;
+
+    const TAG: [u8; 4] = *b"G2P\0";
+
+    /// The number of line coefficients of a point other than the identity:
+    /// one doubling per digit of `6u + 2` after the first, one addition per
+    /// nonzero digit, and the two Frobenius additions.
+    fn num_coeffs() -> usize {
+        let digits = &SIX_U_PLUS_2_NAF[..SIX_U_PLUS_2_NAF.len() - 1];
+        digits.len() + digits.iter().filter(|digit| **digit != 0).count() + 2
+    }
+
+    /// Writes the line coefficients in the raw format of `SerdeObject`,
+    /// preceded by a versioned header, the identity flag, and the number of
+    /// coefficients as a little-endian `u32`.
+    pub fn write_raw<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_table_header(writer, &Self::TAG, Self::FORMAT_VERSION, G2::CURVE_ID)?;
+        writer.write_all(&[self.infinity as u8])?;
+        writer.write_all(&(self.coeffs.len() as u32).to_le_bytes())?;
+        for (c0, c1, c2) in self.coeffs.iter() {
+            c0.write_raw(writer)?;
+            c1.write_raw(writer)?;
+            c2.write_raw(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Reads coefficients written by `write_raw`, checking the header, the
+    /// number of coefficients and that every coordinate is canonical. The
+    /// coefficients are not checked to come from a point, so the data must
+    /// come from a trusted source.
+    #[cfg_attr(
+        all(feature = "panic-lints", not(test)),
+        deny(
+            clippy::unwrap_used,
+            clippy::expect_used,
+            clippy::panic,
+            clippy::unreachable,
+            clippy::indexing_slicing
+        )
+    )]
+    pub fn read_raw<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let invalid = |msg| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        read_table_header(reader, &Self::TAG, Self::FORMAT_VERSION, G2::CURVE_ID)?;
+        let mut header = [0u8; 5];
+        reader.read_exact(&mut header)?;
+        let [flag, len @ ..] = header;
+        let infinity = match flag {
+            0 => false,
+            1 => true,
+            _ => return Err(invalid("invalid identity flag")),
+        };
+        let len = u32::from_le_bytes(len) as usize;
+        if len != if infinity { 0 } else { Self::num_coeffs() } {
+            return Err(invalid("invalid number of coefficients"));
+        }
+
+        let coeffs = (0..len)
+            .map(|_| {
+                Ok((
+                    Fq2::read_raw(reader)?,
+                    Fq2::read_raw(reader)?,
+                    Fq2::read_raw(reader)?,
+                ))
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(G2Prepared { coeffs, infinity })
+    }
 }
 
 impl From<G2Affine> for G2Prepared {
@@ -622,6 +857,55 @@ pub fn multi_miller_loop(terms: &[(&G1Affine, &G2Prepared)]) -> Gt {
     Gt(f)
 }
 
+/// Same as `multi_miller_loop`, but the G2 points are prepared together using
+/// affine line computations with batched inversions. See
+/// `G2Prepared::batch_from_affine`.
+pub fn multi_miller_loop_affine(terms: &[(&G1Affine, &G2Affine)]) -> Gt {
+    let qs: Vec<G2Affine> = terms.iter().map(|&(_, q)| *q).collect();
+    let prepared = G2Prepared::batch_from_affine(&qs);
+    let terms: Vec<(&G1Affine, &G2Prepared)> = terms
+        .iter()
+        .zip(prepared.iter())
+        .map(|(&(p, _), q)| (p, q))
+        .collect();
+    multi_miller_loop(&terms)
+}
+
+/// The number of terms from which the `MultiMillerLoop` implementation of
+/// `Bn256` splits the loop across threads, with the `parallel-miller-loop`
+/// feature. A single Miller loop is much longer than spawning a thread, but
+/// the verifiers of most proof systems only pair two to four terms and gain
+/// little from it.
+pub const PARALLEL_MILLER_LOOP_TERMS: usize = 8;
+
+/// Same as `multi_miller_loop`, but the terms are split across threads and
+/// the partial Miller loops are multiplied together at the end. The result
+/// still needs a single final exponentiation. With the `parallel-miller-loop`
+/// feature, `Bn256::multi_miller_loop` calls it from
+/// `PARALLEL_MILLER_LOOP_TERMS` terms on. Otherwise it is only used when
+/// called directly, since callers that are already parallel, such as provers,
+/// would be oversubscribed by the threads it spawns.
+pub fn multi_miller_loop_parallel(terms: &[(&G1Affine, &G2Prepared)]) -> Gt {
+    let num_threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    if num_threads == 1 || terms.len() < 2 {
+        return multi_miller_loop(terms);
+    }
+    let chunk_size = (terms.len() + num_threads - 1) / num_threads;
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = terms
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || multi_miller_loop(chunk)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .fold(Gt::identity(), |acc, f| acc + f)
+    })
+}
+
 pub fn pairing(g1: &G1Affine, g2: &G2Affine) -> Gt {
     let g2 = G2Prepared::from_affine(*g2);
     let terms: &[(&G1Affine, &G2Prepared)] = &[(g1, &g2)];
@@ -629,6 +913,30 @@ pub fn pairing(g1: &G1Affine, g2: &G2Affine) -> Gt {
     u.final_exponentiation()
 }
 
+/// Checks a KZG opening proof: that `commitment` opens to `value` at `point`
+/// given the witness `proof` and the `[tau]_2` element of the setup, i.e.
+/// `e(commitment - [value]_1 + point * proof, [1]_2) = e(proof, [tau]_2)`.
+/// Both pairings are computed with a single Miller loop and final
+/// exponentiation.
+pub fn kzg_pairing_check(
+    commitment: &G1Affine,
+    proof: &G1Affine,
+    point: &Fr,
+    value: &Fr,
+    tau_g2: &G2Affine,
+) -> bool {
+    let lhs = (commitment - G1Affine::generator() * value + proof * point).to_affine();
+    let neg_proof = -proof;
+    let g2 = G2Prepared::from_affine(G2Affine::generator());
+    let tau_g2 = G2Prepared::from_affine(*tau_g2);
+    let terms: &[(&G1Affine, &G2Prepared)] = &[(&lhs, &g2), (&neg_proof, &tau_g2)];
+    bool::from(
+        multi_miller_loop(terms)
+            .final_exponentiation()
+            .is_identity(),
+    )
+}
+
 #[derive(Clone, Debug)]
 pub struct Bn256;
 
@@ -650,6 +958,10 @@ impl MultiMillerLoop for Bn256 {
     type Result = Gt;
 
     fn multi_miller_loop(terms: &[(&Self::G1Affine, &Self::G2Prepared)]) -> Self::Result {
+        #[cfg(feature = "parallel-miller-loop")]
+        if terms.len() >= PARALLEL_MILLER_LOOP_TERMS {
+            return multi_miller_loop_parallel(terms);
+        }
         multi_miller_loop(terms)
     }
 }
@@ -836,3 +1148,137 @@ fn random_miller_loop_tests() {
         assert_eq!(abcd, abcd_with_double_loop);
     }
 }
+
+#[test]
+fn test_kzg_pairing_check() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    // Commit to p(X) = a + b X and open at z, so that the witness
+    // is [(p(tau) - p(z)) / (tau - z)]_1 = [b]_1.
+    let tau = Fr::random(&mut rng);
+    let tau_g2 = (G2Affine::generator() * tau).to_affine();
+    for _ in 0..10 {
+        let (a, b, z) = (
+            Fr::random(&mut rng),
+            Fr::random(&mut rng),
+            Fr::random(&mut rng),
+        );
+        let commitment = (G1Affine::generator() * (a + b * tau)).to_affine();
+        let proof = (G1Affine::generator() * b).to_affine();
+        let value = a + b * z;
+
+        assert!(kzg_pairing_check(&commitment, &proof, &z, &value, &tau_g2));
+        assert!(!kzg_pairing_check(
+            &commitment,
+            &proof,
+            &z,
+            &(value + Fr::one()),
+            &tau_g2
+        ));
+        assert!(!kzg_pairing_check(
+            &commitment,
+            &-proof,
+            &z,
+            &value,
+            &tau_g2
+        ));
+    }
+}
+
+#[test]
+fn random_parallel_miller_loop_tests() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let ps: Vec<G1Affine> = (0..17).map(|_| G1::random(&mut rng).into()).collect();
+    let qs: Vec<G2Prepared> = (0..17)
+        .map(|_| G2Affine::from(G2::random(&mut rng)).into())
+        .collect();
+    for n in [0, 1, 2, 17] {
+        let terms: Vec<_> = ps.iter().zip(qs.iter()).take(n).collect();
+        assert_eq!(
+            multi_miller_loop_parallel(&terms).final_exponentiation(),
+            multi_miller_loop(&terms).final_exponentiation()
+        );
+        assert_eq!(
+            <Bn256 as MultiMillerLoop>::multi_miller_loop(&terms).final_exponentiation(),
+            multi_miller_loop(&terms).final_exponentiation()
+        );
+    }
+}
+
+#[test]
+fn random_affine_miller_loop_tests() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let a = G1Affine::from(G1::random(&mut rng));
+        let b = G2Affine::from(G2::random(&mut rng));
+        let c = G1Affine::from(G1::random(&mut rng));
+        let d = G2Affine::from(G2::random(&mut rng));
+        let z2 = G2Affine::identity();
+
+        let expected = multi_miller_loop(&[(&a, &G2Prepared::from(b)), (&c, &G2Prepared::from(d))])
+            .final_exponentiation();
+        let result =
+            multi_miller_loop_affine(&[(&a, &b), (&c, &d), (&c, &z2)]).final_exponentiation();
+
+        assert_eq!(expected, result);
+        assert_eq!(
+            pairing(&a, &b),
+            multi_miller_loop_affine(&[(&a, &b)]).final_exponentiation()
+        );
+    }
+}
+
+#[test]
+fn test_batch_from_affine_parallel() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let mut qs: Vec<G2Affine> = (0..9).map(|_| G2::random(&mut rng).into()).collect();
+    qs[4] = G2Affine::identity();
+    let expected = G2Prepared::batch_from_affine(&qs);
+    for num_threads in [None, Some(1), Some(4), Some(16)] {
+        assert_eq!(
+            G2Prepared::batch_from_affine_parallel(&qs, num_threads),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_g2_prepared_serialization() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for q in [G2Affine::identity(), G2Affine::from(G2::random(&mut rng))] {
+        let prepared = G2Prepared::from_affine(q);
+        let mut buf = vec![];
+        prepared.write_raw(&mut buf).unwrap();
+        assert_eq!(G2Prepared::read_raw(&mut &buf[..]).unwrap(), prepared);
+        assert!(G2Prepared::read_raw(&mut &buf[..buf.len() - 1]).is_err());
+    }
+
+    let prepared = G2Prepared::batch_from_affine(&[G2Affine::generator()]);
+    let mut buf = vec![];
+    prepared[0].write_raw(&mut buf).unwrap();
+    assert_eq!(G2Prepared::read_raw(&mut &buf[..]).unwrap(), prepared[0]);
+
+    // Flipping the identity flag changes the expected number of coefficients.
+    let flag = 4 + 2 + 1 + G2::CURVE_ID.len();
+    buf[flag] = 1;
+    assert!(G2Prepared::read_raw(&mut &buf[..]).is_err());
+}
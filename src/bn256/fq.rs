@@ -4,7 +4,6 @@ use crate::bn256::assembly::field_arithmetic_asm;
 use crate::{field_arithmetic, field_specific};
 
 use crate::arithmetic::{adc, mac, sbb};
-use crate::bn256::LegendreSymbol;
 use crate::ff::{Field, FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
 use crate::{
     field_bits, field_common, impl_add_binop_specify_output, impl_binops_additive,
@@ -101,7 +100,9 @@ const TWO_INV: Fq = Fq::from_raw([
     0x183227397098d014,
 ]);
 
-/// `0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd46`
+/// `p - 1 = 2 * t` with `t` odd, so the 2-adic root of unity is
+/// `MULTIPLICATIVE_GENERATOR^t = -1 =
+/// 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd46`
 const ROOT_OF_UNITY: Fq = Fq::from_raw([
     0x3c208c16d87cfd46,
     0x97816a916871ca8d,
@@ -117,7 +118,7 @@ const ROOT_OF_UNITY_INV: Fq = Fq::from_raw([
     0x30644e72e131a029,
 ]);
 
-// `0x9`
+/// `MULTIPLICATIVE_GENERATOR^(2^S) = 0x9`
 const DELTA: Fq = Fq::from_raw([0x9, 0, 0, 0]);
 
 /// `ZETA^3 = 1 mod r` where `ZETA^2 != 1 mod r`
@@ -160,25 +161,6 @@ impl Fq {
     pub const fn size() -> usize {
         32
     }
-
-    pub fn legendre(&self) -> LegendreSymbol {
-        // s = self^((modulus - 1) // 2)
-        // 0x183227397098d014dc2822db40c0ac2ecbc0b548b438e5469e10460b6c3e7ea3
-        let s = &[
-            0x9e10460b6c3e7ea3u64,
-            0xcbc0b548b438e546u64,
-            0xdc2822db40c0ac2eu64,
-            0x183227397098d014u64,
-        ];
-        let s = self.pow(s);
-        if s == Self::zero() {
-            LegendreSymbol::Zero
-        } else if s == Self::one() {
-            LegendreSymbol::QuadraticResidue
-        } else {
-            LegendreSymbol::QuadraticNonResidue
-        }
-    }
 }
 
 impl ff::Field for Fq {
@@ -220,6 +202,9 @@ impl ff::Field for Fq {
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
     fn invert(&self) -> CtOption<Self> {
+        #[cfg(feature = "op-count")]
+        crate::stats::record_invert();
+
         let tmp = self.pow([
             0x3c208c16d87cfd45,
             0x97816a916871ca8d,
@@ -242,7 +227,7 @@ impl ff::PrimeField for Fq {
     const ROOT_OF_UNITY_INV: Self = ROOT_OF_UNITY_INV;
     const TWO_INV: Self = TWO_INV;
     const DELTA: Self = DELTA;
-    const S: u32 = 0;
+    const S: u32 = 1;
 
     fn from_repr(repr: Self::Repr) -> CtOption<Self> {
         let mut tmp = Fq([0, 0, 0, 0]);
@@ -318,9 +303,38 @@ impl WithSmallOrderMulGroup<3> for Fq {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::bn256::LegendreSymbol;
     use ff::Field;
     use rand_core::OsRng;
 
+    #[test]
+    fn test_legendre() {
+        assert_eq!(Fq::zero().legendre(), LegendreSymbol::Zero);
+        assert!(bool::from(Fq::zero().is_quadratic_residue()));
+        assert_eq!(Fq::one().legendre(), LegendreSymbol::QuadraticResidue);
+        assert_eq!((-Fq::one()).legendre(), LegendreSymbol::QuadraticNonResidue);
+
+        // Small values and values close to the modulus or its half take the
+        // most steps, or make the approximations of the batches inexact.
+        let small = (1..200).map(Fq::from);
+        let large = (1..200).map(|k| -Fq::from(k));
+        let half = (1..200).map(|k| TWO_INV + Fq::from(k << 40));
+        let random = (0..1000).map(|_| Fq::random(OsRng));
+        for a in small.chain(large).chain(half).chain(random) {
+            let euler = a.pow(&[
+                0x9e10460b6c3e7ea3,
+                0xcbc0b548b438e546,
+                0xdc2822db40c0ac2e,
+                0x183227397098d014,
+            ]);
+            assert_eq!(bool::from(a.is_quadratic_residue()), euler == Fq::one());
+            assert_eq!(
+                bool::from(a.is_quadratic_residue()),
+                bool::from(a.sqrt().is_some())
+            );
+        }
+    }
+
     #[test]
     fn test_sqrt_fq() {
         let v = (Fq::TWO_INV).square().sqrt().unwrap();
@@ -357,6 +371,31 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_two_adicity() {
+        // (p - 1) / 2^S
+        let t = [
+            0x9e10460b6c3e7ea3,
+            0xcbc0b548b438e546,
+            0xdc2822db40c0ac2e,
+            0x183227397098d014,
+        ];
+        assert!(t[0] & 1 == 1);
+        assert_eq!(Fq::ROOT_OF_UNITY, Fq::MULTIPLICATIVE_GENERATOR.pow(t));
+        assert_eq!(Fq::ROOT_OF_UNITY.pow([1u64 << Fq::S]), Fq::one());
+        assert_ne!(Fq::ROOT_OF_UNITY.pow([1u64 << (Fq::S - 1)]), Fq::one());
+        assert_eq!(Fq::ROOT_OF_UNITY * Fq::ROOT_OF_UNITY_INV, Fq::one());
+
+        assert_eq!(Fq::DELTA, Fq::MULTIPLICATIVE_GENERATOR.pow([1u64 << Fq::S]));
+        assert_eq!(Fq::DELTA.pow(t), Fq::one());
+    }
+
+    #[test]
+    fn test_zeta() {
+        assert_ne!(Fq::ZETA, Fq::one());
+        assert_eq!(Fq::ZETA.square() * Fq::ZETA, Fq::one());
+    }
+
     #[test]
     fn test_from_u512() {
         assert_eq!(
@@ -98,6 +98,29 @@ impl Fq12 {
         }
     }
 
+    pub const fn new(c0: Fq6, c1: Fq6) -> Self {
+        Fq12 { c0, c1 }
+    }
+
+    /// Returns the coefficient of $1$.
+    pub const fn c0(&self) -> Fq6 {
+        self.c0
+    }
+
+    /// Returns the coefficient of $w$.
+    pub const fn c1(&self) -> Fq6 {
+        self.c1
+    }
+
+    /// Multiply by w, where w^2 = v.
+    pub fn mul_by_nonresidue(&mut self) {
+        // (c0 + c1 w) w = c1 v + c0 w
+        let mut c1 = self.c1;
+        c1.mul_by_nonresidue();
+        self.c1 = self.c0;
+        self.c0 = c1;
+    }
+
     pub fn mul_assign(&mut self, other: &Self) {
         let t0 = self.c0 * other.c0;
         let mut t1 = self.c1 * other.c1;
@@ -196,6 +219,15 @@ impl Fq12 {
         self.c1.c2.mul_assign(&FROBENIUS_COEFF_FQ12_C1[power % 12]);
     }
 
+    /// Multiply every coefficient by the `Fq2` element `c`.
+    pub fn mul_by_fq2(&mut self, c: &Fq2) {
+        self.c0.mul_by_fq2(c);
+        self.c1.mul_by_fq2(c);
+    }
+
+    /// Multiply by the sparse element `c0 + c1 v + c4 v w`, i.e. the element
+    /// whose only non-zero `Fq2` coefficients are at indices 0, 1 and 4 when
+    /// `Fq12` is viewed as `(c0 + c1 v + c2 v^2) + (c3 + c4 v + c5 v^2) w`.
     pub fn mul_by_014(&mut self, c0: &Fq2, c1: &Fq2, c4: &Fq2) {
         let mut aa = self.c0;
         aa.mul_by_01(c0, c1);
@@ -211,12 +243,12 @@ impl Fq12 {
         self.c0 += &aa;
     }
 
+    /// Multiply by the sparse element `c0 + c3 w + c4 v w`, which is the shape
+    /// of the line evaluations in the Miller loop. See `mul_by_014` for the
+    /// coefficient indexing.
     pub fn mul_by_034(&mut self, c0: &Fq2, c3: &Fq2, c4: &Fq2) {
-        let t0 = Fq6 {
-            c0: self.c0.c0 * c0,
-            c1: self.c0.c1 * c0,
-            c2: self.c0.c2 * c0,
-        };
+        let mut t0 = self.c0;
+        t0.mul_by_fq2(c0);
         let mut t1 = self.c1;
         t1.mul_by_01(c3, c4);
         let o = c0 + c3;
@@ -489,6 +521,49 @@ use rand::SeedableRng;
 #[cfg(test)]
 use rand_xorshift::XorShiftRng;
 
+#[test]
+fn test_fq12_mul_nonresidue() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let w = Fq12::new(Fq6::zero(), Fq6::one());
+
+    for _ in 0..1000 {
+        let mut a = Fq12::random(&mut rng);
+        assert_eq!(Fq12::new(a.c0(), a.c1()), a);
+
+        let mut b = a;
+        a.mul_by_nonresidue();
+        b.mul_assign(&w);
+
+        assert_eq!(a, b);
+    }
+}
+
+#[test]
+fn test_fq12_mul_by_fq2() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..1000 {
+        let c = Fq2::random(&mut rng);
+        let mut a = Fq12::random(&mut rng);
+        let mut b = a;
+
+        a.mul_by_fq2(&c);
+        b.mul_assign(&Fq12::new(
+            Fq6::new(c, Fq2::zero(), Fq2::zero()),
+            Fq6::zero(),
+        ));
+
+        assert_eq!(a, b);
+    }
+}
+
 #[test]
 fn test_fq12_mul_by_014() {
     let mut rng = XorShiftRng::from_seed([
@@ -71,6 +71,17 @@ impl<'a> From<&'a Fq2> for [u8; 64] {
     }
 }
 
+impl<'a> TryFrom<&'a [u8]> for Fq2 {
+    type Error = crate::serde::InvalidEncoding;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let bytes: &[u8; 64] = bytes
+            .try_into()
+            .map_err(|_| crate::serde::InvalidEncoding)?;
+        Option::from(Fq2::from_bytes(bytes)).ok_or(crate::serde::InvalidEncoding)
+    }
+}
+
 impl Neg for Fq2 {
     type Output = Fq2;
 
@@ -142,10 +153,34 @@ impl Fq2 {
         }
     }
 
+    /// Returns minus three, embedded in `Fq2`.
+    pub const fn minus_three() -> Fq2 {
+        Fq2 {
+            c0: Fq::minus_three(),
+            c1: Fq::zero(),
+        }
+    }
+
+    /// Returns true if `self` and `other` have the same limbs, as a
+    /// `const fn` in variable time.
+    pub const fn eq_vartime(&self, other: &Self) -> bool {
+        self.c0.eq_vartime(&other.c0) && self.c1.eq_vartime(&other.c1)
+    }
+
     pub const fn new(c0: Fq, c1: Fq) -> Self {
         Fq2 { c0, c1 }
     }
 
+    /// Returns the coefficient of $1$.
+    pub const fn c0(&self) -> Fq {
+        self.c0
+    }
+
+    /// Returns the coefficient of $u$.
+    pub const fn c1(&self) -> Fq {
+        self.c1
+    }
+
     pub const fn size() -> usize {
         64
     }
@@ -156,8 +191,8 @@ impl Fq2 {
         let c1 = Fq::from_bytes(bytes[32..64].try_into().unwrap());
         CtOption::new(
             Fq2 {
-                c0: c0.unwrap(),
-                c1: c1.unwrap(),
+                c0: c0.unwrap_or(Fq::zero()),
+                c1: c1.unwrap_or(Fq::zero()),
             },
             c0.is_some() & c1.is_some(),
         )
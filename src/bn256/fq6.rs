@@ -101,6 +101,25 @@ impl Fq6 {
         }
     }
 
+    pub const fn new(c0: Fq2, c1: Fq2, c2: Fq2) -> Self {
+        Fq6 { c0, c1, c2 }
+    }
+
+    /// Returns the coefficient of $1$.
+    pub const fn c0(&self) -> Fq2 {
+        self.c0
+    }
+
+    /// Returns the coefficient of $v$.
+    pub const fn c1(&self) -> Fq2 {
+        self.c1
+    }
+
+    /// Returns the coefficient of $v^2$.
+    pub const fn c2(&self) -> Fq2 {
+        self.c2
+    }
+
     pub fn mul_assign(&mut self, other: &Self) {
         let mut a_a = self.c0;
         let mut b_b = self.c1;
@@ -273,6 +292,13 @@ impl Fq6 {
         self.c0.mul_by_xi();
     }
 
+    /// Multiply every coefficient by the `Fq2` element `c`.
+    pub fn mul_by_fq2(&mut self, c: &Fq2) {
+        self.c0 *= c;
+        self.c1 *= c;
+        self.c2 *= c;
+    }
+
     pub fn mul_by_1(&mut self, c1: &Fq2) {
         let mut b_b = self.c1;
         b_b *= c1;
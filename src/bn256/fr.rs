@@ -20,6 +20,7 @@ use crate::impl_from_u64;
 
 use crate::arithmetic::{adc, mac, sbb};
 use crate::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use crate::sqrt::SqrtPrecomputation;
 use crate::{
     field_bits, field_common, impl_add_binop_specify_output, impl_binops_additive,
     impl_binops_additive_specify_output, impl_binops_multiplicative,
@@ -234,6 +235,9 @@ impl ff::Field for Fr {
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
     fn invert(&self) -> CtOption<Self> {
+        #[cfg(feature = "op-count")]
+        crate::stats::record_invert();
+
         let tmp = self.pow([
             0x43e1f593efffffff,
             0x2833e84879b97091,
@@ -245,14 +249,10 @@ impl ff::Field for Fr {
     }
 
     fn sqrt(&self) -> CtOption<Self> {
-        /// `(t - 1) // 2` where t * 2^s + 1 = p with t odd.
-        const T_MINUS1_OVER2: [u64; 4] = [
-            0xcdcb848a1f0fac9f,
-            0x0c0ac2e9419f4243,
-            0x098d014dc2822db4,
-            0x0000000183227397,
-        ];
-        ff::helpers::sqrt_tonelli_shanks(self, T_MINUS1_OVER2)
+        lazy_static::lazy_static! {
+            static ref SQRT: SqrtPrecomputation<Fr> = SqrtPrecomputation::new();
+        }
+        SQRT.sqrt(self)
     }
 
     fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
@@ -355,6 +355,23 @@ mod test {
     use rand_core::OsRng;
     use rand_xorshift::XorShiftRng;
 
+    #[test]
+    fn test_canonical_limbs_batch() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let elements: Vec<Fr> = (0..1000).map(|_| Fr::random(&mut rng)).collect();
+        let limbs = Fr::to_canonical_limbs_batch(&elements);
+        for (element, limb) in elements.iter().zip(limbs.iter()) {
+            assert_eq!(*limb, element.to_canonical_limbs());
+            assert_eq!(*element, Fr::from_raw(*limb));
+        }
+        assert_eq!(Fr::from_canonical_limbs_batch(&limbs), elements);
+        assert_eq!(Fr::one().to_canonical_limbs(), [1, 0, 0, 0]);
+    }
+
     #[test]
     fn test_sqrt() {
         let v = (Fr::TWO_INV).square().sqrt().unwrap();
@@ -406,6 +423,77 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_inv_2_pow() {
+        let two = Fr::from(2);
+        for k in 0..=(Fr::S as usize + 2) {
+            assert_eq!(Fr::inv_2_pow(k) * two.pow_vartime([k as u64]), Fr::ONE);
+        }
+    }
+
+    #[test]
+    fn test_wide_constructors() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for _ in 0..1000 {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            assert_eq!(Fr::from_bytes_wide(&bytes), Fr::from_uniform_bytes(&bytes));
+
+            let lo = Fr::from_bytes_wide(&{
+                let mut lo = [0u8; 64];
+                lo[..32].copy_from_slice(&bytes[..32]);
+                lo
+            });
+            let limbs =
+                [0, 8, 16, 24].map(|i| u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap()));
+            assert_eq!(Fr::from_u256_reduce(limbs), lo);
+        }
+
+        // The modulus itself reduces to zero.
+        assert_eq!(Fr::from_u256_reduce(MODULUS.0), Fr::ZERO);
+    }
+
+    #[test]
+    fn test_roots_of_unity() {
+        let roots = Fr::ROOTS_OF_UNITY;
+        assert_eq!(roots.len(), Fr::S as usize + 1);
+        assert_eq!(roots[0], Fr::ROOT_OF_UNITY);
+        assert_eq!(roots[Fr::S as usize], Fr::ONE);
+        assert_eq!(roots[Fr::S as usize - 1], -Fr::ONE);
+        for (i, root) in roots.iter().enumerate() {
+            assert_eq!(*root, Fr::ROOT_OF_UNITY.pow_vartime([1u64 << i]));
+        }
+    }
+
+    #[test]
+    fn test_byte_conversions() {
+        let a = Fr::random(OsRng);
+        let bytes: [u8; 32] = a.into();
+        assert_eq!(Fr::try_from(&bytes[..]), Ok(a));
+        assert!(Fr::try_from(&bytes[..31]).is_err());
+        assert!(Fr::try_from(&[0xff; 32][..]).is_err());
+    }
+
+    #[test]
+    fn test_montgomery_reduce() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for _ in 0..1000 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            let wide = crate::arithmetic::mul_512(a.0, b.0);
+            assert_eq!(Fr::montgomery_reduce(&wide), a * b);
+        }
+        assert_eq!(Fr::montgomery_reduce(&[0; 8]), Fr::ZERO);
+    }
+
     #[test]
     #[cfg(feature = "bits")]
     fn test_bits() {
@@ -5,6 +5,8 @@ mod fq12;
 mod fq2;
 mod fq6;
 mod fr;
+mod residue;
+pub mod threshold;
 
 #[cfg(feature = "asm")]
 mod assembly;
@@ -16,10 +18,6 @@ pub use fq12::*;
 pub use fq2::*;
 pub use fq6::*;
 pub use fr::*;
+pub use residue::*;
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum LegendreSymbol {
-    Zero = 0,
-    QuadraticResidue = 1,
-    QuadraticNonResidue = -1,
-}
+pub use crate::arithmetic::LegendreSymbol;
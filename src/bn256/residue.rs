@@ -0,0 +1,184 @@
+//! Residue witnesses for the final exponentiation, following "On Proving
+//! Pairings" by Novakovic and Eagen (<https://eprint.iacr.org/2024/640>).
+//!
+//! A Miller loop output `f` passes the pairing check when `f^h = 1`, with
+//! `h = (p^12 - 1) / r`. Instead of raising `f` to `h` in a circuit, the prover
+//! supplies `c` and a 27th root of unity `s` such that `f * s = c^λ`, where
+//! `λ = 6x + 2 + p - p^2 + p^3` is a multiple of `r`. Checking this relation
+//! only costs an exponentiation by `λ`, which shares most of its work with the
+//! Miller loop.
+
+use crate::bn256::{Fq, Fq12, Fq2, Fq6, Fr, BN_X};
+use crate::ff::{Field, PrimeField};
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Num, One, Zero};
+
+struct ResidueParams {
+    /// `h = (p^12 - 1) / r`.
+    h: Vec<u64>,
+    /// `λ = 6x + 2 + p - p^2 + p^3`.
+    lambda: Vec<u64>,
+    /// `(p^12 - 1) / 3`, to test for cubic residuosity.
+    cubic: Vec<u64>,
+    /// `r^-1 mod h`, to take `r`-th roots of elements of order dividing `h`.
+    r_inv: Vec<u64>,
+    /// `m'^-1 mod h`, where `λ = 3 m' r`.
+    m_inv: Vec<u64>,
+    /// `3^-1 mod t`, where `p^12 - 1 = 27 t`.
+    three_inv: Vec<u64>,
+    /// A primitive 27th root of unity, which is not a cube.
+    w: Fq12,
+}
+
+fn mod_inverse(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    let m = BigInt::from(m.clone());
+    let (mut old_r, mut r) = (BigInt::from(a.clone()), m.clone());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+    while !r.is_zero() {
+        let q = &old_r / &r;
+        let tmp = &old_r - &q * &r;
+        old_r = std::mem::replace(&mut r, tmp);
+        let tmp = &old_s - &q * &s;
+        old_s = std::mem::replace(&mut s, tmp);
+    }
+    if !old_r.is_one() {
+        return None;
+    }
+    ((old_s % &m + &m) % &m).to_biguint()
+}
+
+lazy_static::lazy_static! {
+    static ref RESIDUE_PARAMS: ResidueParams = {
+        let p = BigUint::from_str_radix(&Fq::MODULUS[2..], 16).unwrap();
+        let r = BigUint::from_str_radix(&Fr::MODULUS[2..], 16).unwrap();
+        let x = BigUint::from(BN_X);
+
+        let order = p.pow(12) - 1u32;
+        let h = &order / &r;
+        let lambda = x * 6u32 + 2u32 + &p + p.pow(3) - p.pow(2);
+        assert!((&lambda % &r).is_zero());
+        let m = &lambda / &r;
+        assert!((&m % 3u32).is_zero());
+        let t = &order / 27u32;
+        assert!(!(&t % 3u32).is_zero());
+
+        let r_inv = mod_inverse(&r, &h).unwrap();
+        let m_inv = mod_inverse(&(m / 3u32), &h).unwrap();
+        let three_inv = mod_inverse(&BigUint::from(3u32), &t).unwrap();
+
+        // g^t lies in the 3-Sylow subgroup, which has order 27, and generates
+        // it unless it is a cube.
+        let t = t.to_u64_digits();
+        let w = (1u64..)
+            .map(|k| {
+                let g = Fq12::new(
+                    Fq6::new(Fq2::new(Fq::from(k), Fq::ONE), Fq2::ZERO, Fq2::ZERO),
+                    Fq6::ONE,
+                );
+                g.pow_vartime(&t)
+            })
+            .find(|w| w.pow_vartime([9]) != Fq12::ONE)
+            .unwrap();
+
+        ResidueParams {
+            h: h.to_u64_digits(),
+            lambda: lambda.to_u64_digits(),
+            cubic: (order / 3u32).to_u64_digits(),
+            r_inv: r_inv.to_u64_digits(),
+            m_inv: m_inv.to_u64_digits(),
+            three_inv: three_inv.to_u64_digits(),
+            w,
+        }
+    };
+}
+
+/// Returns a cube root of the cubic residue `a`.
+fn cube_root(a: &Fq12) -> Option<Fq12> {
+    let params = &*RESIDUE_PARAMS;
+
+    // x^3 = a^(3 * 3^-1 mod t) = a * z, with z in the 3-Sylow subgroup. As a
+    // is a cube, z is the cube of some w^i, which we search for.
+    let x = a.pow_vartime(&params.three_inv);
+    let z = x.square() * x * Option::<Fq12>::from(a.invert())?;
+    let w3 = params.w.square() * params.w;
+
+    let mut y = Fq12::ONE;
+    let mut acc = z;
+    for _ in 0..9 {
+        if acc == Fq12::ONE {
+            return Some(x * y);
+        }
+        y *= params.w;
+        acc *= w3;
+    }
+    None
+}
+
+/// Returns the residue witness `(c, s)` of the Miller loop output `f`, such
+/// that `f * s = c^λ` and `s` is a 27th root of unity, or `None` if `f` does
+/// not pass the pairing check.
+pub fn residue_witness(f: &Fq12) -> Option<(Fq12, Fq12)> {
+    let params = &*RESIDUE_PARAMS;
+    if f.pow_vartime(&params.h) != Fq12::ONE {
+        return None;
+    }
+
+    // w is not a cube, so exactly one of f, f w and f w^2 is.
+    let mut s = Fq12::ONE;
+    let mut scaled = *f;
+    while scaled.pow_vartime(&params.cubic) != Fq12::ONE {
+        s *= params.w;
+        scaled *= params.w;
+    }
+
+    // Take the r-th, m'-th and 3rd roots in turn. Since scaled^h = 1 and
+    // gcd(r m', h) = 1, the first two are plain exponentiations.
+    let c = scaled.pow_vartime(&params.r_inv).pow_vartime(&params.m_inv);
+    let c = cube_root(&c)?;
+    Some((c, s))
+}
+
+/// Checks a residue witness returned by `residue_witness`. If it holds, `f`
+/// passes the pairing check: `f^h = c^(λ h) s^-h = 1`, as `r h` is the order
+/// of the multiplicative group and `27` divides `h`.
+pub fn verify_residue_witness(f: &Fq12, c: &Fq12, s: &Fq12) -> bool {
+    let params = &*RESIDUE_PARAMS;
+    s.pow_vartime([27]) == Fq12::ONE && c.pow_vartime(&params.lambda) == f * s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{residue_witness, verify_residue_witness};
+    use crate::bn256::{multi_miller_loop, Fq12, Fr, G1Affine, G2Affine, G2Prepared};
+    use crate::ff::Field;
+    use crate::group::Curve;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_residue_witness() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for _ in 0..2 {
+            // e(aP, Q) e(-P, aQ) = 1
+            let a = Fr::random(&mut rng);
+            let p = G1Affine::generator();
+            let q = G2Affine::generator();
+            let ap = (p * a).to_affine();
+            let neg_p = -p;
+            let aq = G2Prepared::from_affine((q * a).to_affine());
+            let q = G2Prepared::from_affine(q);
+            let f = multi_miller_loop(&[(&ap, &q), (&neg_p, &aq)]).0;
+
+            let (c, s) = residue_witness(&f).unwrap();
+            assert!(verify_residue_witness(&f, &c, &s));
+            assert!(!verify_residue_witness(&(f + Fq12::ONE), &c, &s));
+
+            let f = multi_miller_loop(&[(&ap, &q), (&p, &aq)]).0;
+            assert_eq!(residue_witness(&f), None);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+//! Threshold BLS signatures over BN254.
+//!
+//! Signatures are in G1, as the hash to curve of the message under `DOMAIN`
+//! multiplied by the secret key, and public keys are in G2. A secret shared
+//! with a degree `t - 1` polynomial, by a dealer with `share_secret` or by a
+//! distributed key generation, gives key shares at the nonzero indices `i`.
+//! Each share signs on its own, and any `t` partial signatures combine with
+//! the Lagrange coefficients at zero into the signature of the shared key.
+//!
+//! Public keys are validated as in the `KeyValidate` of the BLS signature
+//! draft: the identity and the points of the twist outside of the subgroup
+//! are rejected, since the identity key verifies the identity signature of
+//! every message.
+
+use crate::bn256::{multi_miller_loop, Fr, G1Affine, G2Affine, G2Prepared, G1, G2};
+use crate::group::cofactor::CofactorGroup;
+use crate::group::{Curve, Group};
+use crate::secret::Secret;
+use crate::CurveExt;
+use ff::{BatchInvert, Field};
+use pairing::MillerLoopResult;
+use rand_core::RngCore;
+use std::fmt;
+
+/// The domain under which messages are hashed to G1.
+pub const DOMAIN: &str = "halo2curves-bls";
+
+/// A share of a secret key, the evaluation of the sharing polynomial at the
+/// nonzero `index`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct KeyShare {
+    pub index: u64,
+    pub secret: Fr,
+}
+
+/// The public key of a `KeyShare`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PublicKeyShare {
+    pub index: u64,
+    pub key: G2Affine,
+}
+
+/// A signature by a single `KeyShare`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PartialSignature {
+    pub index: u64,
+    pub signature: G1Affine,
+}
+
+impl fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyShare")
+            .field("index", &self.index)
+            .field("secret", &Secret::new(self.secret))
+            .finish()
+    }
+}
+
+/// Hashes `message` to G1.
+pub fn hash_to_g1(message: &[u8]) -> G1Affine {
+    G1::hash_to_curve(DOMAIN)(message).to_affine()
+}
+
+/// Returns true if `key` is a valid public key: not the identity, and in the
+/// subgroup of order r.
+pub fn validate_public_key(key: &G2Affine) -> bool {
+    let key = G2::from(*key);
+    !bool::from(key.is_identity()) && bool::from(key.is_torsion_free())
+}
+
+/// Returns true if `public_key` is valid and
+/// `e(signature, [1]_2) = e(H(message), public_key)`.
+pub fn verify(public_key: &G2Affine, message: &[u8], signature: &G1Affine) -> bool {
+    if !validate_public_key(public_key) {
+        return false;
+    }
+    let hash = hash_to_g1(message);
+    let neg_signature = -signature;
+    let g2 = G2Prepared::from_affine(G2Affine::generator());
+    let public_key = G2Prepared::from_affine(*public_key);
+    let terms: &[(&G1Affine, &G2Prepared)] = &[(&neg_signature, &g2), (&hash, &public_key)];
+    bool::from(
+        multi_miller_loop(terms)
+            .final_exponentiation()
+            .is_identity(),
+    )
+}
+
+/// Splits `secret` into `n` shares at the indices `1..=n`, any `threshold` of
+/// which recover it. Panics unless `0 < threshold <= n`.
+pub fn share_secret(secret: &Fr, threshold: usize, n: u64, mut rng: impl RngCore) -> Vec<KeyShare> {
+    assert!(threshold > 0 && threshold as u64 <= n);
+    let mut coefficients = vec![*secret];
+    coefficients.extend((1..threshold).map(|_| Fr::random(&mut rng)));
+
+    (1..=n)
+        .map(|index| {
+            let x = Fr::from(index);
+            let secret = coefficients
+                .iter()
+                .rev()
+                .fold(Fr::ZERO, |acc, coefficient| acc * x + coefficient);
+            KeyShare { index, secret }
+        })
+        .collect()
+}
+
+/// Returns the Lagrange coefficients at zero of the polynomials interpolated
+/// over `indices`, or `None` if an index is zero or repeated.
+pub fn lagrange_coefficients(indices: &[u64]) -> Option<Vec<Fr>> {
+    let xs: Vec<Fr> = indices.iter().map(|index| Fr::from(*index)).collect();
+    let mut denominators: Vec<Fr> = xs
+        .iter()
+        .enumerate()
+        .map(|(i, xi)| {
+            xs.iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(*xi, |acc, (_, xj)| acc * (*xj - xi))
+        })
+        .collect();
+    if denominators.iter().any(|d| bool::from(d.is_zero())) {
+        return None;
+    }
+    denominators.iter_mut().batch_invert();
+
+    // lambda_i = prod_{j != i} x_j / (x_j - x_i), where the numerator is the
+    // product of all the x_j divided by x_i.
+    let product = xs.iter().fold(Fr::ONE, |acc, x| acc * x);
+    Some(denominators.iter().map(|d| product * d).collect())
+}
+
+/// Combines the partial signatures, at least as many as the threshold, into
+/// the signature of the shared key. Returns `None` if an index is zero or
+/// repeated. The partial signatures should be verified beforehand, since a
+/// single invalid one invalidates the result.
+pub fn aggregate(partials: &[PartialSignature]) -> Option<G1Affine> {
+    let indices: Vec<u64> = partials.iter().map(|partial| partial.index).collect();
+    let coefficients = lagrange_coefficients(&indices)?;
+    let signature = partials
+        .iter()
+        .zip(coefficients.iter())
+        .fold(G1::identity(), |acc, (partial, coefficient)| {
+            acc + partial.signature * coefficient
+        });
+    Some(signature.to_affine())
+}
+
+/// Combines the public key shares, at least as many as the threshold, into
+/// the public key of the shared secret. Returns `None` if an index is zero or
+/// repeated, or if a share or the result is not a valid public key.
+pub fn aggregate_public_keys(shares: &[PublicKeyShare]) -> Option<G2Affine> {
+    if !shares.iter().all(|share| validate_public_key(&share.key)) {
+        return None;
+    }
+    let indices: Vec<u64> = shares.iter().map(|share| share.index).collect();
+    let coefficients = lagrange_coefficients(&indices)?;
+    let key = shares
+        .iter()
+        .zip(coefficients.iter())
+        .fold(G2::identity(), |acc, (share, coefficient)| {
+            acc + share.key * coefficient
+        });
+    let key = key.to_affine();
+    validate_public_key(&key).then_some(key)
+}
+
+impl KeyShare {
+    pub fn public_share(&self) -> PublicKeyShare {
+        PublicKeyShare {
+            index: self.index,
+            key: (G2Affine::generator() * self.secret).to_affine(),
+        }
+    }
+
+    /// Signs `message` with this share.
+    pub fn sign(&self, message: &[u8]) -> PartialSignature {
+        PartialSignature {
+            index: self.index,
+            signature: (hash_to_g1(message) * self.secret).to_affine(),
+        }
+    }
+}
+
+impl PublicKeyShare {
+    /// Returns true if `partial` is the signature of `message` by the share
+    /// with this public key, which must be valid.
+    pub fn verify(&self, message: &[u8], partial: &PartialSignature) -> bool {
+        partial.index == self.index && verify(&self.key, message, &partial.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::prime::PrimeCurveAffine;
+    use crate::CurveAffine;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_threshold_signature() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let secret = Fr::random(&mut rng);
+        let public_key = (G2Affine::generator() * secret).to_affine();
+        let shares = share_secret(&secret, 3, 5, &mut rng);
+        let public_shares: Vec<_> = shares.iter().map(KeyShare::public_share).collect();
+
+        let message = b"threshold";
+        let partials: Vec<_> = shares.iter().map(|share| share.sign(message)).collect();
+        for (public_share, partial) in public_shares.iter().zip(partials.iter()) {
+            assert!(public_share.verify(message, partial));
+            assert!(!public_share.verify(b"other", partial));
+        }
+        assert!(!public_shares[0].verify(message, &partials[1]));
+
+        let signature = aggregate(&partials[..3]).unwrap();
+        assert_eq!(signature, (hash_to_g1(message) * secret).to_affine());
+        assert!(verify(&public_key, message, &signature));
+        assert_eq!(aggregate(&partials[2..]), Some(signature));
+        assert_eq!(
+            aggregate(&[partials[4], partials[0], partials[2]]),
+            Some(signature)
+        );
+        assert_eq!(
+            aggregate_public_keys(&public_shares[1..4]),
+            Some(public_key)
+        );
+
+        // Fewer partial signatures than the threshold give another signature.
+        assert!(!verify(
+            &public_key,
+            message,
+            &aggregate(&partials[..2]).unwrap()
+        ));
+
+        assert_eq!(aggregate(&[partials[0], partials[0], partials[1]]), None);
+        assert_eq!(lagrange_coefficients(&[0, 1]), None);
+        assert_eq!(lagrange_coefficients(&[7]), Some(vec![Fr::ONE]));
+    }
+
+    #[test]
+    fn test_public_key_validation() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let message = b"threshold";
+
+        // The identity key verifies the identity signature of any message.
+        let identity = G2Affine::identity();
+        assert!(!validate_public_key(&identity));
+        assert!(!verify(&identity, message, &G1Affine::identity()));
+        let share = PublicKeyShare {
+            index: 1,
+            key: identity,
+        };
+        let partial = PartialSignature {
+            index: 1,
+            signature: G1Affine::identity(),
+        };
+        assert!(!share.verify(message, &partial));
+
+        // A point of the twist outside of the subgroup.
+        let outside = loop {
+            let x = crate::bn256::Fq2::random(&mut rng);
+            let y = (x.square() * x + G2Affine::b()).sqrt();
+            if bool::from(y.is_some()) {
+                break G2Affine { x, y: y.unwrap() };
+            }
+        };
+        assert!(bool::from(outside.is_on_curve()));
+        assert!(!validate_public_key(&outside));
+        assert!(!verify(&outside, message, &hash_to_g1(message)));
+
+        let shares = share_secret(&Fr::random(&mut rng), 2, 3, &mut rng);
+        let mut public_shares: Vec<_> = shares.iter().map(KeyShare::public_share).collect();
+        assert!(public_shares
+            .iter()
+            .all(|share| validate_public_key(&share.key)));
+        assert!(aggregate_public_keys(&public_shares).is_some());
+        public_shares[1].key = outside;
+        assert_eq!(aggregate_public_keys(&public_shares), None);
+        public_shares[1].key = identity;
+        assert_eq!(aggregate_public_keys(&public_shares), None);
+
+        // Shares of the zero secret aggregate to the identity.
+        let zero_shares: Vec<_> = share_secret(&Fr::ZERO, 2, 2, &mut rng)
+            .iter()
+            .map(KeyShare::public_share)
+            .collect();
+        assert_eq!(aggregate_public_keys(&zero_shares), None);
+    }
+}
@@ -0,0 +1,126 @@
+//! Conversion of scalars between the fields of different curves.
+//!
+//! A scalar of one field is read as its canonical integer and reduced modulo
+//! the order of the other. Whether the reduction changed the integer is part
+//! of the result, so that callers aggregating values across curves choose
+//! between failing on overflow (`strict`) and reducing (`wrapping`) instead of
+//! copying representation bytes and silently getting a different integer.
+
+use crate::repr::FieldRepr;
+use ff::{FromUniformBytes, PrimeField};
+use std::fmt;
+
+/// The error returned by `ConvertedScalar::strict` when the integer of the
+/// source scalar is not smaller than the destination modulus.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ScalarOverflow;
+
+impl fmt::Display for ScalarOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "scalar does not fit in the destination field")
+    }
+}
+
+impl std::error::Error for ScalarOverflow {}
+
+/// A scalar converted by `convert_scalar`, together with whether it was
+/// reduced modulo the destination order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ConvertedScalar<F> {
+    value: F,
+    reduced: bool,
+}
+
+impl<F> ConvertedScalar<F> {
+    /// Returns true if the integer of the source scalar was not smaller than
+    /// the destination modulus, so that the value differs from it.
+    pub fn was_reduced(&self) -> bool {
+        self.reduced
+    }
+
+    /// Returns the converted scalar, or an error if it was reduced.
+    pub fn strict(self) -> Result<F, ScalarOverflow> {
+        if self.reduced {
+            Err(ScalarOverflow)
+        } else {
+            Ok(self.value)
+        }
+    }
+
+    /// Returns the converted scalar, reduced modulo the destination order.
+    pub fn wrapping(self) -> F {
+        self.value
+    }
+}
+
+/// Converts `scalar` to the field `Dst`, as the canonical integer of `scalar`
+/// modulo the order of `Dst`.
+pub fn convert_scalar<Src: PrimeField, Dst: PrimeField + FromUniformBytes<64>>(
+    scalar: &Src,
+) -> ConvertedScalar<Dst> {
+    let repr = FieldRepr::from_field(scalar);
+    match repr.into_field::<Dst>() {
+        Some(value) => ConvertedScalar {
+            value,
+            reduced: false,
+        },
+        None => {
+            let mut bytes = [0u8; 64];
+            for (chunk, limb) in bytes.chunks_mut(8).zip(repr.limbs()) {
+                chunk.copy_from_slice(&limb.to_le_bytes());
+            }
+            ConvertedScalar {
+                value: Dst::from_uniform_bytes(&bytes),
+                reduced: true,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convert_scalar, ScalarOverflow};
+    use crate::bn256::{Fq, Fr};
+    use crate::{pasta, secp256k1};
+    use ff::{Field, PrimeField};
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_convert_scalar() {
+        // r < q on BN254, so every scalar of Fr fits in Fq.
+        for _ in 0..100 {
+            let scalar = Fr::random(OsRng);
+            let converted = convert_scalar::<Fr, Fq>(&scalar);
+            assert!(!converted.was_reduced());
+            let value = converted.strict().unwrap();
+            assert_eq!(value.to_repr(), scalar.to_repr());
+            assert_eq!(convert_scalar::<Fq, Fr>(&value).strict(), Ok(scalar));
+        }
+
+        let small = convert_scalar::<Fq, Fr>(&Fq::from(1234));
+        assert!(!small.was_reduced());
+        assert_eq!(small.wrapping(), Fr::from(1234));
+
+        // q - 1 = (q - r - 1) + r.
+        let converted = convert_scalar::<Fq, Fr>(&-Fq::ONE);
+        assert!(converted.was_reduced());
+        assert_eq!(converted.strict(), Err(ScalarOverflow));
+        assert_eq!(
+            converted.wrapping(),
+            Fr::from_u128(147946756881789318990833708069417712965)
+        );
+
+        // Pallas and Vesta swap their fields, with q > p.
+        let converted = convert_scalar::<pasta::Fq, pasta::Fp>(&-pasta::Fq::ONE);
+        assert!(converted.was_reduced());
+        assert!(!convert_scalar::<pasta::Fp, pasta::Fq>(&-pasta::Fp::ONE).was_reduced());
+
+        // The secp256k1 order is smaller than its base field.
+        let converted = convert_scalar::<secp256k1::Fp, secp256k1::Fq>(&-secp256k1::Fp::ONE);
+        assert!(converted.was_reduced());
+        assert_eq!(
+            convert_scalar::<secp256k1::Fp, secp256k1::Fq>(&secp256k1::Fp::ZERO).strict(),
+            Ok(secp256k1::Fq::ZERO)
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Cosets of the power-of-two evaluation domains of a prime field.
+//!
+//! The domain of size `2^log_n` is the subgroup generated by the primitive
+//! `2^log_n`-th root of unity derived from `ROOT_OF_UNITY`. Its cosets are
+//! shifted by powers of `MULTIPLICATIVE_GENERATOR`, which generates the whole
+//! multiplicative group: the `k`-th coset is `g^k * H`, and the cosets for
+//! `k < (p - 1) / 2^log_n` are pairwise disjoint. Provers that agree on these
+//! conventions pick the same quotient domains.
+
+use crate::arithmetic::parallelize_with;
+use ff::PrimeField;
+
+/// Returns `g^k`, the shift of the `k`-th coset, for the multiplicative
+/// generator `g` of `F`.
+pub fn coset_shift<F: PrimeField>(k: u64) -> F {
+    F::MULTIPLICATIVE_GENERATOR.pow_vartime([k])
+}
+
+/// Returns the primitive `2^log_n`-th root of unity generating the domain of
+/// size `2^log_n`, or `None` if `F` has no such domain.
+pub fn domain_generator<F: PrimeField>(log_n: u32) -> Option<F> {
+    if log_n > F::S {
+        return None;
+    }
+    Some(F::ROOT_OF_UNITY.pow_vartime([1u64 << (F::S - log_n)]))
+}
+
+/// Returns `g^k * omega^i`, the `i`-th element of the `k`-th coset of the
+/// domain of size `2^log_n` with generator `omega`, or `None` if `F` has no
+/// such domain.
+pub fn coset_element<F: PrimeField>(log_n: u32, k: u64, i: u64) -> Option<F> {
+    domain_generator::<F>(log_n).map(|omega| coset_shift::<F>(k) * omega.pow_vartime([i]))
+}
+
+/// Returns the twiddle factors `omega^i` for `i` in `0..2^(log_n - 1)` of the
+/// domain of size `2^log_n`, or `None` if `F` has no such domain. The powers
+/// are computed in parallel with at most `num_threads` threads, or one thread
+/// per core if `None`.
+pub fn domain_twiddles<F: PrimeField>(log_n: u32, num_threads: Option<usize>) -> Option<Vec<F>> {
+    let omega = domain_generator::<F>(log_n)?;
+    let mut twiddles = vec![F::ZERO; (1usize << log_n) / 2];
+    parallelize_with(num_threads, &mut twiddles, |twiddles, start| {
+        let mut power = omega.pow_vartime([start as u64]);
+        for twiddle in twiddles.iter_mut() {
+            *twiddle = power;
+            power *= omega;
+        }
+    });
+    Some(twiddles)
+}
+
+/// Returns true if the coset `shift * H'` of the domain `H'` of size
+/// `2^log_coset` is disjoint from the domain `H` of size `2^log_domain`.
+///
+/// One of the domains contains the other, so they intersect exactly when
+/// `shift` is in the larger one, that is when `shift^(2^max) = 1`.
+pub fn coset_avoids_domain<F: PrimeField>(shift: &F, log_coset: u32, log_domain: u32) -> bool {
+    let mut x = *shift;
+    for _ in 0..log_coset.max(log_domain) {
+        x = x.square();
+    }
+    x != F::ONE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bn256::Fr, pasta::Fp, secp256k1::Fq};
+    use std::collections::HashSet;
+
+    fn coset_test<F: PrimeField>() {
+        assert_eq!(coset_shift::<F>(0), F::ONE);
+        assert_eq!(coset_shift::<F>(1), F::MULTIPLICATIVE_GENERATOR);
+        assert_eq!(domain_generator::<F>(F::S), Some(F::ROOT_OF_UNITY));
+        assert_eq!(domain_generator::<F>(0), Some(F::ONE));
+        assert_eq!(domain_generator::<F>(F::S + 1), None);
+
+        let log_n = F::S.min(3);
+        let omega = domain_generator::<F>(log_n).unwrap();
+        assert_eq!(omega.pow_vartime([1u64 << log_n]), F::ONE);
+
+        // The first few cosets are disjoint from each other.
+        let n = 1u64 << log_n;
+        let elements: HashSet<_> = (0..4)
+            .flat_map(|k| (0..n).map(move |i| coset_element::<F>(log_n, k, i).unwrap()))
+            .map(|x| x.to_repr().as_ref().to_vec())
+            .collect();
+        assert_eq!(elements.len() as u64, 4 * n);
+
+        for k in 1..4 {
+            assert!(coset_avoids_domain(&coset_shift::<F>(k), log_n, log_n));
+        }
+        assert!(!coset_avoids_domain(&F::ONE, log_n, log_n));
+        assert!(!coset_avoids_domain(&omega, log_n, log_n));
+        // omega is in the larger domain and outside of the smaller one.
+        if log_n > 0 {
+            assert!(!coset_avoids_domain(&omega, log_n, log_n - 1));
+            assert!(omega.pow_vartime([n / 2]) != F::ONE);
+        }
+
+        let twiddles = domain_twiddles::<F>(log_n, Some(3)).unwrap();
+        assert_eq!(twiddles.len() as u64, n / 2);
+        for (i, twiddle) in twiddles.iter().enumerate() {
+            assert_eq!(*twiddle, omega.pow_vartime([i as u64]));
+        }
+        assert_eq!(domain_twiddles::<F>(log_n, None), Some(twiddles));
+        assert_eq!(domain_twiddles::<F>(F::S + 1, None), None);
+    }
+
+    #[test]
+    fn test_cosets() {
+        coset_test::<Fr>();
+        coset_test::<Fp>();
+        coset_test::<Fq>();
+    }
+}
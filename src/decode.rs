@@ -0,0 +1,845 @@
+//! Decoding of untrusted bytes into points and scalars of any curve.
+//!
+//! `decode_any` takes the curve and the serialization format at runtime and
+//! never panics: every malformed input is reported as a `DecodeError` saying
+//! what is wrong with it. This is the entry point meant for fuzz harnesses and
+//! for services parsing adversarial data.
+//!
+//! `read_points` and `read_points_batched` decode large vectors of points of
+//! one curve, such as a structured reference string, on all cores. Their
+//! `_report` variants list every rejected point instead of the first one.
+//!
+//! With the `panic-lints` feature, clippy rejects the panicking constructs of
+//! this module, and of the other code parsing untrusted data: the `order`,
+//! `normalize` and `serde` modules, `G2Prepared::read_raw` and the BN254
+//! `from_bytes_as` decoders. The feature only turns on these lints and does
+//! not change the code that is compiled.
+
+#![cfg_attr(
+    all(feature = "panic-lints", not(test)),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unreachable,
+        clippy::indexing_slicing
+    )
+)]
+
+use crate::arithmetic::parallelize;
+use crate::dynamic::{DynPoint, DynScalar};
+use crate::group::cofactor::CofactorGroup;
+use crate::group::{GroupEncoding, UncompressedEncoding};
+use crate::id::CurveId;
+use crate::msm::msm;
+use crate::serde::SerdeObject;
+use crate::{babyjubjub, bn256, grumpkin, secp256k1, secp256r1, secq256k1, CurveAffine, CurveExt};
+use ff::PrimeField;
+use group::prime::PrimeCurveAffine;
+use rand_core::RngCore;
+use std::fmt;
+
+/// The serialization formats supported by `decode_any`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// The compressed point encoding of `GroupEncoding`.
+    CompressedPoint,
+    /// The uncompressed point encoding of `UncompressedEncoding`.
+    UncompressedPoint,
+    /// The affine coordinates in Montgomery form, as written by
+    /// `SerdeObject::write_raw`.
+    RawPoint,
+    /// The canonical little-endian encoding of a scalar.
+    Scalar,
+    /// The compressed or uncompressed encoding of gnark-crypto, told apart by
+    /// their length. Only implemented for BN254.
+    Gnark,
+    /// The compressed or uncompressed encoding of arkworks, told apart by
+    /// their length. Only implemented for BN254.
+    Arkworks,
+}
+
+/// An object decoded by `decode_any`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Decoded {
+    Point(DynPoint),
+    Scalar(DynScalar),
+}
+
+/// Why `decode_any` rejected its input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input does not have the length of the format on this curve.
+    WrongLength { expected: usize, actual: usize },
+    /// The input has the right length but does not encode a point of the
+    /// curve: the flags are invalid, a coordinate is not canonical, or the
+    /// coordinates are not on the curve.
+    InvalidPoint,
+    /// The input encodes a point of the curve outside of the prime-order
+    /// subgroup.
+    NotInSubgroup,
+    /// The input is not smaller than the order of the curve.
+    NonCanonicalScalar,
+    /// The curve does not implement this format.
+    UnsupportedFormat,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::WrongLength { expected, actual } => {
+                write!(f, "expected {expected} bytes, got {actual}")
+            }
+            DecodeError::InvalidPoint => write!(f, "invalid point encoding"),
+            DecodeError::NotInSubgroup => write!(f, "point is not in the prime-order subgroup"),
+            DecodeError::NonCanonicalScalar => write!(f, "scalar is not canonical"),
+            DecodeError::UnsupportedFormat => write!(f, "format not supported by this curve"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+fn check_length(expected: usize, actual: usize) -> Result<(), DecodeError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(DecodeError::WrongLength { expected, actual })
+    }
+}
+
+fn decode_uncompressed<C: CurveExt>(bytes: &[u8]) -> Result<C, DecodeError>
+where
+    C::AffineExt: UncompressedEncoding,
+{
+    let mut repr = <C::AffineExt as UncompressedEncoding>::Uncompressed::default();
+    check_length(repr.as_ref().len(), bytes.len())?;
+    repr.as_mut().copy_from_slice(bytes);
+    Option::from(C::AffineExt::from_uncompressed(&repr))
+        .map(C::from)
+        .ok_or(DecodeError::InvalidPoint)
+}
+
+fn decode_raw<C: CurveExt>(bytes: &[u8]) -> Result<C, DecodeError>
+where
+    C::AffineExt: SerdeObject,
+{
+    let expected = C::AffineExt::identity().to_raw_bytes().len();
+    check_length(expected, bytes.len())?;
+    C::AffineExt::from_raw_bytes(bytes)
+        .map(C::from)
+        .ok_or(DecodeError::InvalidPoint)
+}
+
+/// Decodes a BN254 point in a foreign encoding of `bn256::PointEncoding`,
+/// whose compressed form has the length of the native one. A length matching
+/// neither form is reported against the compressed one.
+fn decode_foreign<C>(
+    bytes: &[u8],
+    encoding: bn256::PointEncoding,
+    decode: impl Fn(&[u8], bn256::PointEncoding) -> Option<C>,
+) -> Result<C, DecodeError>
+where
+    C: CurveAffine,
+{
+    let size = C::Repr::default().as_ref().len();
+    if bytes.len() != size && bytes.len() != 2 * size {
+        check_length(size, bytes.len())?;
+    }
+    decode(bytes, encoding).ok_or(DecodeError::InvalidPoint)
+}
+
+/// Rejects the points outside of the prime-order subgroup. Among the
+/// `CurveExt` curves of `decode_any`, only BN254 G2 has a cofactor, and none of
+/// its encodings checks subgroup membership.
+fn check_subgroup(point: DynPoint) -> Result<DynPoint, DecodeError> {
+    match point {
+        DynPoint::Bn256G2(p) if !bool::from(p.is_torsion_free()) => Err(DecodeError::NotInSubgroup),
+        point => Ok(point),
+    }
+}
+
+/// Decodes a point of Baby Jubjub, which is not a `CurveExt` and has no raw
+/// format. Only the points of its prime-order subgroup are accepted.
+fn decode_babyjubjub(bytes: &[u8], format: Format) -> Result<DynPoint, DecodeError> {
+    let point = match format {
+        Format::CompressedPoint => {
+            let mut repr = <babyjubjub::BabyJubjubAffine as GroupEncoding>::Repr::default();
+            check_length(repr.as_ref().len(), bytes.len())?;
+            repr.as_mut().copy_from_slice(bytes);
+            Option::from(babyjubjub::BabyJubjubAffine::from_bytes(&repr))
+        }
+        Format::UncompressedPoint => {
+            let mut repr =
+                <babyjubjub::BabyJubjubAffine as UncompressedEncoding>::Uncompressed::default();
+            check_length(repr.as_ref().len(), bytes.len())?;
+            repr.as_mut().copy_from_slice(bytes);
+            Option::from(babyjubjub::BabyJubjubAffine::from_uncompressed(&repr))
+        }
+        Format::RawPoint | Format::Scalar | Format::Gnark | Format::Arkworks => {
+            return Err(DecodeError::UnsupportedFormat)
+        }
+    }
+    .ok_or(DecodeError::InvalidPoint)?;
+
+    Option::from(babyjubjub::BabyJubjub::from(point).into_subgroup())
+        .map(DynPoint::BabyJubjub)
+        .ok_or(DecodeError::NotInSubgroup)
+}
+
+/// Decodes `bytes` as an object of the curve `curve` in the given format.
+/// Points are checked to be on the curve and in the prime-order subgroup.
+pub fn decode_any(curve: CurveId, bytes: &[u8], format: Format) -> Result<Decoded, DecodeError> {
+    macro_rules! decode_point {
+        ($decode:ident) => {
+            match curve {
+                CurveId::Bn256G1 => $decode::<bn256::G1>(bytes).map(DynPoint::Bn256G1),
+                CurveId::Bn256G2 => $decode::<bn256::G2>(bytes).map(DynPoint::Bn256G2),
+                CurveId::Grumpkin => $decode::<grumpkin::G1>(bytes).map(DynPoint::Grumpkin),
+                CurveId::Secp256k1 => {
+                    $decode::<secp256k1::Secp256k1>(bytes).map(DynPoint::Secp256k1)
+                }
+                CurveId::Secp256r1 => {
+                    $decode::<secp256r1::Secp256r1>(bytes).map(DynPoint::Secp256r1)
+                }
+                CurveId::Secq256k1 => {
+                    $decode::<secq256k1::Secq256k1>(bytes).map(DynPoint::Secq256k1)
+                }
+                // The pasta points come from `pasta_curves`, which only
+                // implements the compressed encoding.
+                CurveId::Pallas | CurveId::Vesta => Err(DecodeError::UnsupportedFormat),
+                CurveId::BabyJubjub => decode_babyjubjub(bytes, format),
+            }
+        };
+    }
+
+    match format {
+        Format::CompressedPoint if curve == CurveId::BabyJubjub => {
+            decode_babyjubjub(bytes, format).map(Decoded::Point)
+        }
+        Format::CompressedPoint => {
+            let expected = DynPoint::identity(curve).to_bytes().len();
+            check_length(expected, bytes.len())?;
+            DynPoint::from_bytes(curve, bytes)
+                .ok_or(DecodeError::InvalidPoint)
+                .and_then(check_subgroup)
+                .map(Decoded::Point)
+        }
+        Format::UncompressedPoint => decode_point!(decode_uncompressed)
+            .and_then(check_subgroup)
+            .map(Decoded::Point),
+        Format::RawPoint => decode_point!(decode_raw)
+            .and_then(check_subgroup)
+            .map(Decoded::Point),
+        Format::Gnark | Format::Arkworks => {
+            let encoding = if format == Format::Gnark {
+                bn256::PointEncoding::Gnark
+            } else {
+                bn256::PointEncoding::Arkworks
+            };
+            match curve {
+                CurveId::Bn256G1 => {
+                    decode_foreign(bytes, encoding, bn256::G1Affine::from_bytes_as_unchecked)
+                        .map(|p| DynPoint::Bn256G1(p.into()))
+                }
+                CurveId::Bn256G2 => {
+                    decode_foreign(bytes, encoding, bn256::G2Affine::from_bytes_as_unchecked)
+                        .map(|p| DynPoint::Bn256G2(p.into()))
+                }
+                _ => Err(DecodeError::UnsupportedFormat),
+            }
+            .and_then(check_subgroup)
+            .map(Decoded::Point)
+        }
+        Format::Scalar => {
+            let expected = DynScalar::zero(curve).to_bytes().len();
+            check_length(expected, bytes.len())?;
+            DynScalar::from_bytes(curve, bytes)
+                .map(Decoded::Scalar)
+                .ok_or(DecodeError::NonCanonicalScalar)
+        }
+    }
+}
+
+/// The failures of a batch decoding, as reported by `read_points_report` and
+/// `read_points_batched_report`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchDecodeError {
+    /// The index of every rejected point with the reason, by increasing
+    /// index. Trailing bytes too short for a point are reported at the index
+    /// they would have had.
+    pub failures: Vec<(usize, DecodeError)>,
+}
+
+impl fmt::Display for BatchDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} invalid points", self.failures.len())?;
+        if let Some((index, error)) = self.failures.first() {
+            write!(f, ", the first at index {index}: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BatchDecodeError {}
+
+/// Returns the size of the encoding of a point of `C` in `format`.
+fn point_size<C>(format: Format) -> Result<usize, DecodeError>
+where
+    C: CurveAffine + UncompressedEncoding + SerdeObject,
+{
+    match format {
+        Format::CompressedPoint => Ok(C::Repr::default().as_ref().len()),
+        Format::UncompressedPoint => Ok(C::Uncompressed::default().as_ref().len()),
+        Format::RawPoint => Ok(C::identity().to_raw_bytes().len()),
+        Format::Scalar | Format::Gnark | Format::Arkworks => Err(DecodeError::UnsupportedFormat),
+    }
+}
+
+/// Decodes the concatenated encodings of `size` bytes of points of `C` in
+/// `bytes`, on all cores, checking every point for subgroup membership if
+/// `check_subgroup`. Trailing bytes shorter than `size` are ignored.
+fn decode_points<C>(
+    bytes: &[u8],
+    size: usize,
+    format: Format,
+    check_subgroup: bool,
+) -> Vec<Result<C, DecodeError>>
+where
+    C: CurveAffine + UncompressedEncoding + SerdeObject,
+    C::CurveExt: CofactorGroup,
+{
+    let decode = |bytes: &[u8]| -> Result<C, DecodeError> {
+        let point: Option<C> = match format {
+            Format::CompressedPoint => {
+                let mut repr = C::Repr::default();
+                repr.as_mut().copy_from_slice(bytes);
+                C::from_bytes(&repr).into()
+            }
+            Format::UncompressedPoint => {
+                let mut repr = C::Uncompressed::default();
+                repr.as_mut().copy_from_slice(bytes);
+                C::from_uncompressed(&repr).into()
+            }
+            Format::RawPoint => C::from_raw_bytes(bytes),
+            Format::Scalar | Format::Gnark | Format::Arkworks => {
+                return Err(DecodeError::UnsupportedFormat)
+            }
+        };
+        let point = point.ok_or(DecodeError::InvalidPoint)?;
+        if check_subgroup && !bool::from(point.to_curve().is_torsion_free()) {
+            return Err(DecodeError::NotInSubgroup);
+        }
+        Ok(point)
+    };
+
+    let mut points = vec![Err(DecodeError::InvalidPoint); bytes.len() / size];
+    parallelize(&mut points, |points, start| {
+        for (point, bytes) in points.iter_mut().zip(bytes.chunks_exact(size).skip(start)) {
+            *point = decode(bytes);
+        }
+    });
+    points
+}
+
+/// Returns `rounds` subgroup checks of random linear combinations of
+/// `points`.
+fn batch_torsion_free<C>(points: &[C], rounds: usize, mut rng: impl RngCore) -> bool
+where
+    C: CurveAffine,
+    C::CurveExt: CofactorGroup,
+{
+    (0..rounds).all(|_| {
+        let coeffs: Vec<C::ScalarExt> = points
+            .iter()
+            .map(|_| {
+                C::ScalarExt::from_u128(((rng.next_u64() as u128) << 64) | rng.next_u64() as u128)
+            })
+            .collect();
+        bool::from(msm(&coeffs, points).is_torsion_free())
+    })
+}
+
+/// Like `decode_points`, but decodes all of `bytes` and reports every
+/// failure.
+fn decode_points_report<C>(
+    bytes: &[u8],
+    format: Format,
+    check_subgroup: bool,
+) -> (Vec<(usize, C)>, Vec<(usize, DecodeError)>)
+where
+    C: CurveAffine + UncompressedEncoding + SerdeObject,
+    C::CurveExt: CofactorGroup,
+{
+    let size = match point_size::<C>(format) {
+        Ok(size) => size,
+        Err(error) => return (vec![], vec![(0, error)]),
+    };
+
+    let mut points = vec![];
+    let mut failures = vec![];
+    for (i, point) in decode_points::<C>(bytes, size, format, check_subgroup)
+        .into_iter()
+        .enumerate()
+    {
+        match point {
+            Ok(point) => points.push((i, point)),
+            Err(error) => failures.push((i, error)),
+        }
+    }
+    if bytes.len() % size != 0 {
+        failures.push((
+            bytes.len() / size,
+            DecodeError::WrongLength {
+                expected: size,
+                actual: bytes.len() % size,
+            },
+        ));
+    }
+    (points, failures)
+}
+
+/// Decodes the concatenated encodings of points of `C` in `bytes`, on all
+/// cores. Every point is checked to be on the curve and in the prime-order
+/// subgroup.
+pub fn read_points<C>(bytes: &[u8], format: Format) -> Result<Vec<C>, DecodeError>
+where
+    C: CurveAffine + UncompressedEncoding + SerdeObject,
+    C::CurveExt: CofactorGroup,
+{
+    let size = point_size::<C>(format)?;
+    check_length(bytes.len() - bytes.len() % size, bytes.len())?;
+    decode_points(bytes, size, format, true)
+        .into_iter()
+        .collect()
+}
+
+/// Like `read_points`, but replaces the subgroup check of every point by
+/// `rounds` checks of random linear combinations of all the points, which
+/// costs a few multi-scalar multiplications instead of a scalar
+/// multiplication per point.
+///
+/// Each round misses points outside of the subgroup with probability at most
+/// `1/q`, for `q` the smallest prime factor of the cofactor: for BN254 G2,
+/// `q = 10069`, so about 10 rounds are needed for a 128-bit guarantee. Curves
+/// of prime order need no rounds at all.
+pub fn read_points_batched<C>(
+    bytes: &[u8],
+    format: Format,
+    rounds: usize,
+    rng: impl RngCore,
+) -> Result<Vec<C>, DecodeError>
+where
+    C: CurveAffine + UncompressedEncoding + SerdeObject,
+    C::CurveExt: CofactorGroup,
+{
+    let size = point_size::<C>(format)?;
+    check_length(bytes.len() - bytes.len() % size, bytes.len())?;
+    let points = decode_points::<C>(bytes, size, format, false)
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+    if !batch_torsion_free(&points, rounds, rng) {
+        return Err(DecodeError::NotInSubgroup);
+    }
+    Ok(points)
+}
+
+/// Like `read_points`, but decodes every point even after a failure, and
+/// reports the index and the reason of all of them, to triage a corrupt
+/// file in one pass.
+pub fn read_points_report<C>(bytes: &[u8], format: Format) -> Result<Vec<C>, BatchDecodeError>
+where
+    C: CurveAffine + UncompressedEncoding + SerdeObject,
+    C::CurveExt: CofactorGroup,
+{
+    let (points, failures) = decode_points_report::<C>(bytes, format, true);
+    if !failures.is_empty() {
+        return Err(BatchDecodeError { failures });
+    }
+    Ok(points.into_iter().map(|(_, point)| point).collect())
+}
+
+/// Like `read_points_batched`, but reports the index and the reason of
+/// every failure. When a random linear combination fails, the points that
+/// decoded are checked one by one to find the ones outside of the subgroup.
+pub fn read_points_batched_report<C>(
+    bytes: &[u8],
+    format: Format,
+    rounds: usize,
+    rng: impl RngCore,
+) -> Result<Vec<C>, BatchDecodeError>
+where
+    C: CurveAffine + UncompressedEncoding + SerdeObject,
+    C::CurveExt: CofactorGroup,
+{
+    let (points, mut failures) = decode_points_report::<C>(bytes, format, false);
+    let decoded: Vec<C> = points.iter().map(|(_, point)| *point).collect();
+    if !batch_torsion_free(&decoded, rounds, rng) {
+        failures.extend(
+            points
+                .iter()
+                .filter(|(_, point)| !bool::from(point.to_curve().is_torsion_free()))
+                .map(|(i, _)| (*i, DecodeError::NotInSubgroup)),
+        );
+        failures.sort_by_key(|(i, _)| *i);
+    }
+    if !failures.is_empty() {
+        return Err(BatchDecodeError { failures });
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_any, read_points, read_points_batched, read_points_batched_report,
+        read_points_report, BatchDecodeError, DecodeError, Decoded, Format,
+    };
+    use crate::dynamic::{DynPoint, DynScalar};
+    use crate::group::{Curve, Group, GroupEncoding, UncompressedEncoding};
+    use crate::id::CurveId;
+    use crate::serde::SerdeObject;
+    use crate::{babyjubjub, bn256, secp256k1, CurveAffine};
+    use ff::Field;
+    use rand_core::{RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    const FORMATS: [Format; 6] = [
+        Format::CompressedPoint,
+        Format::UncompressedPoint,
+        Format::RawPoint,
+        Format::Scalar,
+        Format::Gnark,
+        Format::Arkworks,
+    ];
+
+    #[test]
+    fn test_decode_any() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for id in CurveId::ALL {
+            let point = DynPoint::random(id, &mut rng);
+            let bytes = point.to_bytes();
+            assert_eq!(
+                decode_any(id, &bytes, Format::CompressedPoint),
+                Ok(Decoded::Point(point))
+            );
+            assert_eq!(
+                decode_any(id, &bytes[1..], Format::CompressedPoint),
+                Err(DecodeError::WrongLength {
+                    expected: bytes.len(),
+                    actual: bytes.len() - 1
+                })
+            );
+
+            let scalar = DynScalar::random(id, &mut rng);
+            assert_eq!(
+                decode_any(id, &scalar.to_bytes(), Format::Scalar),
+                Ok(Decoded::Scalar(scalar))
+            );
+            assert_eq!(
+                decode_any(id, &[0xff; 32], Format::Scalar),
+                Err(DecodeError::NonCanonicalScalar)
+            );
+        }
+
+        let point = bn256::G1::random(&mut rng).to_affine();
+        let uncompressed = point.to_uncompressed();
+        assert_eq!(
+            decode_any(
+                CurveId::Bn256G1,
+                uncompressed.as_ref(),
+                Format::UncompressedPoint
+            ),
+            Ok(Decoded::Point(DynPoint::Bn256G1(point.into())))
+        );
+        let raw = point.to_raw_bytes();
+        assert_eq!(
+            decode_any(CurveId::Bn256G1, &raw, Format::RawPoint),
+            Ok(Decoded::Point(DynPoint::Bn256G1(point.into())))
+        );
+        let mut off_curve = raw.clone();
+        off_curve[0] ^= 1;
+        assert_eq!(
+            decode_any(CurveId::Bn256G1, &off_curve, Format::RawPoint),
+            Err(DecodeError::InvalidPoint)
+        );
+        let mut too_long = secp256k1::Secp256k1::random(&mut rng)
+            .to_affine()
+            .to_uncompressed()
+            .as_ref()
+            .to_vec();
+        too_long.push(0);
+        assert_eq!(
+            decode_any(CurveId::Secp256k1, &too_long, Format::UncompressedPoint),
+            Err(DecodeError::WrongLength {
+                expected: 64,
+                actual: 65
+            })
+        );
+        assert_eq!(
+            decode_any(CurveId::Pallas, &raw, Format::RawPoint),
+            Err(DecodeError::UnsupportedFormat)
+        );
+
+        // A point of the twist outside of the subgroup is rejected in every
+        // format.
+        let outside = loop {
+            let x = bn256::Fq2::random(&mut rng);
+            let y = (x.square() * x + bn256::G2Affine::b()).sqrt();
+            if bool::from(y.is_some()) {
+                break bn256::G2Affine { x, y: y.unwrap() };
+            }
+        };
+        assert!(!bool::from(bn256::G2::from(outside).is_torsion_free()));
+        for (bytes, format) in [
+            (
+                outside.to_bytes().as_ref().to_vec(),
+                Format::CompressedPoint,
+            ),
+            (
+                outside.to_uncompressed().as_ref().to_vec(),
+                Format::UncompressedPoint,
+            ),
+            (outside.to_raw_bytes(), Format::RawPoint),
+            (
+                [outside.x.c1, outside.x.c0, outside.y.c1, outside.y.c0]
+                    .iter()
+                    .flat_map(|c| c.to_bytes().into_iter().rev())
+                    .collect(),
+                Format::Gnark,
+            ),
+        ] {
+            assert_eq!(
+                decode_any(CurveId::Bn256G2, &bytes, format),
+                Err(DecodeError::NotInSubgroup)
+            );
+        }
+
+        // BN254 points are also decoded from the encodings of gnark-crypto and
+        // arkworks, which other curves do not support.
+        let point = bn256::G1::random(&mut rng).to_affine();
+        let mut x = point.x.to_bytes();
+        let mut y = point.y.to_bytes();
+        x.reverse();
+        y.reverse();
+        let gnark = [x, y].concat();
+        assert_eq!(
+            decode_any(CurveId::Bn256G1, &gnark, Format::Gnark),
+            Ok(Decoded::Point(DynPoint::Bn256G1(point.into())))
+        );
+        assert_eq!(
+            decode_any(CurveId::Bn256G1, &gnark[1..], Format::Gnark),
+            Err(DecodeError::WrongLength {
+                expected: 32,
+                actual: 63
+            })
+        );
+        let mut arkworks = point.x.to_bytes();
+        let mut minus_y = (-point.y).to_bytes();
+        minus_y.reverse();
+        if y > minus_y {
+            arkworks[31] |= 0x80;
+        }
+        assert_eq!(
+            decode_any(CurveId::Bn256G1, &arkworks, Format::Arkworks),
+            Ok(Decoded::Point(DynPoint::Bn256G1(point.into())))
+        );
+        assert_eq!(
+            decode_any(CurveId::Bn256G1, &[0xff; 32], Format::Arkworks),
+            Err(DecodeError::InvalidPoint)
+        );
+        assert_eq!(
+            decode_any(CurveId::Secp256k1, &gnark, Format::Gnark),
+            Err(DecodeError::UnsupportedFormat)
+        );
+
+        // Baby Jubjub has no raw format, and its points of small order are
+        // rejected.
+        let point = babyjubjub::BabyJubjubSubgroup::random(&mut rng);
+        let affine = babyjubjub::BabyJubjub::from(point).to_affine();
+        assert_eq!(
+            decode_any(
+                CurveId::BabyJubjub,
+                affine.to_uncompressed().as_ref(),
+                Format::UncompressedPoint
+            ),
+            Ok(Decoded::Point(DynPoint::BabyJubjub(point)))
+        );
+        assert_eq!(
+            decode_any(CurveId::BabyJubjub, &[0; 64], Format::RawPoint),
+            Err(DecodeError::UnsupportedFormat)
+        );
+        let torsion = babyjubjub::BabyJubjubAffine {
+            x: bn256::Fr::ZERO,
+            y: -bn256::Fr::ONE,
+        };
+        for (bytes, format) in [
+            (
+                torsion.to_bytes().as_ref().to_vec(),
+                Format::CompressedPoint,
+            ),
+            (
+                torsion.to_uncompressed().as_ref().to_vec(),
+                Format::UncompressedPoint,
+            ),
+        ] {
+            assert_eq!(
+                decode_any(CurveId::BabyJubjub, &bytes, format),
+                Err(DecodeError::NotInSubgroup)
+            );
+        }
+        let mut off_curve = affine.to_uncompressed();
+        off_curve.as_mut()[0] ^= 1;
+        assert_eq!(
+            decode_any(
+                CurveId::BabyJubjub,
+                off_curve.as_ref(),
+                Format::UncompressedPoint
+            ),
+            Err(DecodeError::InvalidPoint)
+        );
+
+        // Random inputs are rejected without panicking.
+        for _ in 0..1000 {
+            let id = CurveId::ALL[(rng.next_u32() % CurveId::ALL.len() as u32) as usize];
+            let format = FORMATS[(rng.next_u32() % FORMATS.len() as u32) as usize];
+            let mut bytes = vec![0u8; (rng.next_u32() % 200) as usize];
+            rng.fill_bytes(&mut bytes);
+            let _ = decode_any(id, &bytes, format);
+        }
+    }
+
+    #[test]
+    fn test_read_points() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let points: Vec<bn256::G2Affine> = (0..20)
+            .map(|_| bn256::G2::random(&mut rng).to_affine())
+            .collect();
+        let compressed: Vec<u8> = points
+            .iter()
+            .flat_map(|p| p.to_bytes().as_ref().to_vec())
+            .collect();
+        let uncompressed: Vec<u8> = points
+            .iter()
+            .flat_map(|p| p.to_uncompressed().as_ref().to_vec())
+            .collect();
+        let raw: Vec<u8> = points.iter().flat_map(|p| p.to_raw_bytes()).collect();
+        for (bytes, format) in [
+            (&compressed, Format::CompressedPoint),
+            (&uncompressed, Format::UncompressedPoint),
+            (&raw, Format::RawPoint),
+        ] {
+            assert_eq!(read_points(bytes, format), Ok(points.clone()));
+            assert_eq!(
+                read_points_batched(bytes, format, 10, &mut rng),
+                Ok(points.clone())
+            );
+            assert!(matches!(
+                read_points::<bn256::G2Affine>(&bytes[1..], format),
+                Err(DecodeError::WrongLength { .. })
+            ));
+        }
+        assert_eq!(
+            read_points::<bn256::G2Affine>(&raw, Format::Scalar),
+            Err(DecodeError::UnsupportedFormat)
+        );
+
+        // A point of the twist outside of the subgroup.
+        let outside = loop {
+            let x = bn256::Fq2::random(&mut rng);
+            let y = (x.square() * x + bn256::G2Affine::b()).sqrt();
+            if bool::from(y.is_some()) {
+                break bn256::G2Affine { x, y: y.unwrap() };
+            }
+        };
+        let mut raw = raw;
+        raw.extend(outside.to_raw_bytes());
+        assert_eq!(
+            read_points::<bn256::G2Affine>(&raw, Format::RawPoint),
+            Err(DecodeError::NotInSubgroup)
+        );
+        assert_eq!(
+            read_points_batched::<bn256::G2Affine>(&raw, Format::RawPoint, 10, &mut rng),
+            Err(DecodeError::NotInSubgroup)
+        );
+    }
+
+    #[test]
+    fn test_read_points_report() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let points: Vec<bn256::G2Affine> = (0..10)
+            .map(|_| bn256::G2::random(&mut rng).to_affine())
+            .collect();
+        let raw: Vec<u8> = points.iter().flat_map(|p| p.to_raw_bytes()).collect();
+        assert_eq!(
+            read_points_report(&raw, Format::RawPoint),
+            Ok(points.clone())
+        );
+        assert_eq!(
+            read_points_batched_report(&raw, Format::RawPoint, 10, &mut rng),
+            Ok(points.clone())
+        );
+
+        // Corrupt points 2 and 7, move point 5 out of the subgroup and leave
+        // half a point at the end.
+        let size = raw.len() / points.len();
+        let outside = loop {
+            let x = bn256::Fq2::random(&mut rng);
+            let y = (x.square() * x + bn256::G2Affine::b()).sqrt();
+            if bool::from(y.is_some()) {
+                break bn256::G2Affine { x, y: y.unwrap() };
+            }
+        };
+        let mut corrupt = raw.clone();
+        corrupt[2 * size] ^= 1;
+        corrupt[7 * size + 1] ^= 1;
+        corrupt[5 * size..6 * size].copy_from_slice(&outside.to_raw_bytes());
+        corrupt.extend_from_slice(&raw[..size / 2]);
+
+        let expected = Err(BatchDecodeError {
+            failures: vec![
+                (2, DecodeError::InvalidPoint),
+                (5, DecodeError::NotInSubgroup),
+                (7, DecodeError::InvalidPoint),
+                (
+                    10,
+                    DecodeError::WrongLength {
+                        expected: size,
+                        actual: size / 2,
+                    },
+                ),
+            ],
+        });
+        assert_eq!(
+            read_points_report::<bn256::G2Affine>(&corrupt, Format::RawPoint),
+            expected
+        );
+        assert_eq!(
+            read_points_batched_report::<bn256::G2Affine>(&corrupt, Format::RawPoint, 10, &mut rng),
+            expected
+        );
+        // The first failure is the one of `read_points`.
+        assert_eq!(
+            read_points::<bn256::G2Affine>(&corrupt[..10 * size], Format::RawPoint),
+            Err(DecodeError::InvalidPoint)
+        );
+        assert_eq!(
+            read_points_report::<bn256::G2Affine>(&raw, Format::Scalar),
+            Err(BatchDecodeError {
+                failures: vec![(0, DecodeError::UnsupportedFormat)]
+            })
+        );
+    }
+}
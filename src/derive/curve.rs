@@ -99,6 +99,39 @@ macro_rules! new_curve_impl {
                     }
                 }
 
+                impl<'a> TryFrom<&'a [u8]> for [< $name Compressed >] {
+                    type Error = $crate::serde::InvalidEncoding;
+
+                    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                        bytes.try_into().map(Self).map_err(|_| $crate::serde::InvalidEncoding)
+                    }
+                }
+
+                impl From<[< $name Compressed >]> for [u8; [< $name _COMPRESSED_SIZE >]] {
+                    fn from(value: [< $name Compressed >]) -> Self {
+                        value.0
+                    }
+                }
+
+                impl<'a> TryFrom<&'a [u8]> for $name_affine {
+                    type Error = $crate::serde::InvalidEncoding;
+
+                    /// Decodes the compressed `GroupEncoding` of a point.
+                    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                        let repr = [< $name Compressed >]::try_from(bytes)?;
+                        Option::from(<Self as GroupEncoding>::from_bytes(&repr)).ok_or($crate::serde::InvalidEncoding)
+                    }
+                }
+
+                impl<'a> TryFrom<&'a [u8]> for $name {
+                    type Error = $crate::serde::InvalidEncoding;
+
+                    /// Decodes the compressed `GroupEncoding` of a point.
+                    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                        $name_affine::try_from(bytes).map(Self::from)
+                    }
+                }
+
                 impl group::GroupEncoding for $name_affine {
                     type Repr = [< $name Compressed >];
 
@@ -120,12 +153,13 @@ macro_rules! new_curve_impl {
 
                                     let y = $base::conditional_select(&y, &-y, ysign ^ sign);
 
+                                    // The infinity flag is only valid with a zero x.
                                     CtOption::new(
                                         $name_affine {
                                             x,
                                             y,
                                         },
-                                        Choice::from(1u8),
+                                        !is_inf,
                                     )
                                 })
                             })
@@ -168,6 +202,40 @@ macro_rules! new_curve_impl {
                     }
                 }
 
+                impl $name_affine {
+                    /// Returns the compressed encoding of this point together with
+                    /// a decompression hint: the `y` coordinate, whose parity is the
+                    /// sign bit of the encoding. The hint of the identity is zero.
+                    pub fn to_bytes_with_hint(&self) -> ([< $name Compressed >], $base) {
+                        (self.to_bytes(), self.y)
+                    }
+
+                    /// Decompresses `bytes` with a hint returned by
+                    /// `to_bytes_with_hint`. Instead of computing a square root, this
+                    /// checks that `hint^2 = x^3 + a*x + b` and that the parity of
+                    /// `hint` matches the sign bit, which is what a circuit verifying
+                    /// the decompression constrains.
+                    pub fn from_bytes_with_hint(bytes: &[< $name Compressed >], hint: &$base) -> CtOption<Self> {
+                        let mut tmp = bytes.0;
+                        let is_inf = Choice::from(tmp[[< $name _COMPRESSED_SIZE >] - 1] >> 7);
+                        let ysign = Choice::from((tmp[[< $name _COMPRESSED_SIZE >] - 1] >> 6) & 1);
+                        tmp[[< $name _COMPRESSED_SIZE >] - 1] &= 0b0011_1111;
+                        let mut xbytes = [0u8; $base::size()];
+                        xbytes.copy_from_slice(&tmp[ ..$base::size()]);
+
+                        $base::from_bytes(&xbytes).and_then(|x| {
+                            CtOption::new(Self::identity(), x.is_zero() & hint.is_zero() & is_inf).or_else(|| {
+                                let y = *hint;
+                                let x3 = x.square() * x;
+                                let on_curve = y.square().ct_eq(&(x3 + $name::curve_constant_a() * x + $name::curve_constant_b()));
+                                let sign = Choice::from(y.to_bytes()[0] & 1);
+
+                                CtOption::new($name_affine { x, y }, on_curve & !is_inf & !(ysign ^ sign))
+                            })
+                        })
+                    }
+                }
+
                 }
             };
         }
@@ -209,6 +277,20 @@ macro_rules! new_curve_impl {
                         }
                     }
 
+                    impl<'a> TryFrom<&'a [u8]> for [< $name Uncompressed >] {
+                        type Error = $crate::serde::InvalidEncoding;
+
+                        fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                            bytes.try_into().map(Self).map_err(|_| $crate::serde::InvalidEncoding)
+                        }
+                    }
+
+                    impl From<[< $name Uncompressed >]> for [u8; [< $name _UNCOMPRESSED_SIZE >]] {
+                        fn from(value: [< $name Uncompressed >]) -> Self {
+                            value.0
+                        }
+                    }
+
                     impl ConstantTimeEq for [< $name Uncompressed >] {
                         fn ct_eq(&self, other: &Self) -> Choice {
                             self.0.ct_eq(&other.0)
@@ -312,6 +394,10 @@ macro_rules! new_curve_impl {
 
 
         impl $name {
+            /// The order of the prime-order subgroup, as little-endian 64-bit
+            /// limbs.
+            pub const ORDER_LIMBS: [u64; 4] = $scalar::MODULUS_LIMBS;
+
             pub fn generator() -> Self {
                 let generator = $name_affine::generator();
                 Self {
@@ -337,6 +423,23 @@ macro_rules! new_curve_impl {
                 *CONST_3B
             }
 
+            // Multiplies by the curve constant `a`, skipping the product for
+            // the common `a = 0` and `a = -3` curves.
+            #[inline]
+            fn mul_by_a(input: &$base) -> $base {
+                // Compared limb by limb at compile time, so that the branches
+                // fold away.
+                const A_IS_ZERO: bool = $constant_a.eq_vartime(&$base::zero());
+                const A_IS_MINUS_3: bool = $constant_a.eq_vartime(&$base::minus_three());
+                if A_IS_ZERO {
+                    $base::zero()
+                } else if A_IS_MINUS_3 {
+                    -(input.double() + input)
+                } else {
+                    input * $name::curve_constant_a()
+                }
+            }
+
             fn mul_by_3b(input: &$base) -> $base {
                 if $name::CURVE_ID == "bn256"{
                     input.double().double().double() + input
@@ -344,6 +447,92 @@ macro_rules! new_curve_impl {
                     input * $name::curve_constant_3b()
                 }
             }
+
+            // The doubling formulas, which map the identity to some other
+            // representation of the identity.
+            #[inline]
+            fn double_unselected(&self) -> $name {
+                // Algorithm 3, https://eprint.iacr.org/2015/1060.pdf
+                let t0 = self.x.square();
+                let t1 = self.y.square();
+                let t2 = self.z.square();
+                let t3 = self.x * self.y;
+                let t3 = t3 + t3;
+                let z3 = self.x * self.z;
+                let z3 = z3 + z3;
+                let x3 = $name::mul_by_a(&z3);
+                let y3 = $name::mul_by_3b(&t2);
+                let y3 = x3 + y3;
+                let x3 = t1 - y3;
+                let y3 = t1 + y3;
+                let y3 = x3 * y3;
+                let x3 = t3 * x3;
+                let z3 = $name::mul_by_3b(&z3);
+                let t2 = $name::mul_by_a(&t2);
+                let t3 = t0 - t2;
+                let t3 = $name::mul_by_a(&t3);
+                let t3 = t3 + z3;
+                let z3 = t0 + t0;
+                let t0 = z3 + t0;
+                let t0 = t0 + t2;
+                let t0 = t0 * t3;
+                let y3 = y3 + t0;
+                let t2 = self.y * self.z;
+                let t2 = t2 + t2;
+                let t0 = t2 * t3;
+                let x3 = x3 - t0;
+                let z3 = t2 * t1;
+                let z3 = z3 + z3;
+                let z3 = z3 + z3;
+
+                $name {
+                    x: x3,
+                    y: y3,
+                    z: z3,
+                }
+            }
+
+            /// Doubles this point in place.
+            #[inline]
+            pub fn double_in_place(&mut self) {
+                *self = group::Group::double(self);
+            }
+
+            /// Adds the affine point `rhs` to this point in place, with the
+            /// mixed addition formulas.
+            #[inline]
+            pub fn add_assign_mixed(&mut self, rhs: &$name_affine) {
+                *self += rhs;
+            }
+
+            /// Subtracts the affine point `rhs` from this point in place, with
+            /// the mixed addition formulas.
+            #[inline]
+            pub fn sub_assign_mixed(&mut self, rhs: &$name_affine) {
+                *self -= rhs;
+            }
+
+            /// Returns `self * scalar`, blinded against side channels. The
+            /// scalar is split into two random additive shares, and each share
+            /// multiplies a copy of the point whose projective coordinates were
+            /// scaled by a random factor, so no two calls process the same
+            /// intermediate values.
+            pub fn mul_blinded(&self, scalar: &$scalar, mut rng: impl RngCore) -> $name {
+                let share = <$scalar as ff::Field>::random(&mut rng);
+                let mut randomize = |p: &$name| {
+                    let lambda = <$base as ff::Field>::random(&mut rng);
+                    let lambda = $base::conditional_select(&lambda, &$base::one(), lambda.is_zero());
+                    $name {
+                        x: p.x * lambda,
+                        y: p.y * lambda,
+                        z: p.z * lambda,
+                    }
+                };
+
+                let p1 = randomize(self);
+                let p2 = randomize(self);
+                p1 * share + p2 * (*scalar - share)
+            }
         }
 
         impl $name_affine {
@@ -440,6 +629,12 @@ macro_rules! new_curve_impl {
                     z: $base::conditional_select(&a.z, &b.z, choice),
                 }
             }
+
+            fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+                $base::conditional_swap(&mut a.x, &mut b.x, choice);
+                $base::conditional_swap(&mut a.y, &mut b.y, choice);
+                $base::conditional_swap(&mut a.z, &mut b.z, choice);
+            }
         }
 
         impl PartialEq for $name {
@@ -566,45 +761,7 @@ macro_rules! new_curve_impl {
             }
 
             fn double(&self) -> Self {
-                // Algorithm 3, https://eprint.iacr.org/2015/1060.pdf
-                let t0 = self.x.square();
-                let t1 = self.y.square();
-                let t2 = self.z.square();
-                let t3 = self.x * self.y;
-                let t3 = t3 + t3;
-                let z3 = self.x * self.z;
-                let z3 = z3 + z3;
-                let x3 = $name::curve_constant_a() * z3;
-                let y3 = $name::mul_by_3b(&t2);
-                let y3 = x3 + y3;
-                let x3 = t1 - y3;
-                let y3 = t1 + y3;
-                let y3 = x3 * y3;
-                let x3 = t3 * x3;
-                let z3 = $name::mul_by_3b(&z3);
-                let t2 = $name::curve_constant_a() * t2;
-                let t3 = t0 - t2;
-                let t3 = $name::curve_constant_a() * t3;
-                let t3 = t3 + z3;
-                let z3 = t0 + t0;
-                let t0 = z3 + t0;
-                let t0 = t0 + t2;
-                let t0 = t0 * t3;
-                let y3 = y3 + t0;
-                let t2 = self.y * self.z;
-                let t2 = t2 + t2;
-                let t0 = t2 * t3;
-                let x3 = x3 - t0;
-                let z3 = t2 * t1;
-                let z3 = z3 + z3;
-                let z3 = z3 + z3;
-
-                let tmp = $name {
-                    x: x3,
-                    y: y3,
-                    z: z3,
-                };
-
+                let tmp = self.double_unselected();
                 $name::conditional_select(&tmp, &$name::identity(), self.is_identity())
             }
 
@@ -722,6 +879,11 @@ macro_rules! new_curve_impl {
                     y: $base::conditional_select(&a.y, &b.y, choice),
                 }
             }
+
+            fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+                $base::conditional_swap(&mut a.x, &mut b.x, choice);
+                $base::conditional_swap(&mut a.y, &mut b.y, choice);
+            }
         }
 
         impl cmp::Eq for $name_affine {}
@@ -849,6 +1011,148 @@ macro_rules! new_curve_impl {
         }
 
 
+        impl $crate::arithmetic::CurveDoubling for $name {
+            fn double_n(&self, k: usize) -> Self {
+                let mut acc = *self;
+                for _ in 0..k {
+                    acc = acc.double_unselected();
+                }
+                $name::conditional_select(&acc, &$name::identity(), acc.is_identity())
+            }
+        }
+
+        impl $crate::arithmetic::CurveAffineExt for $name_affine {
+            fn batch_add<const COMPLETE: bool, const LOAD_POINTS: bool>(
+                points: &mut [Self],
+                output_indices: &[u32],
+                num_points: usize,
+                offset: usize,
+                bases: &[Self],
+                base_positions: &[u32],
+            ) {
+                let get_point = |point_data: u32| -> Self {
+                    let negate = point_data & 0x80000000 != 0;
+                    let base_idx = (point_data & 0x7FFFFFFF) as usize;
+                    if negate {
+                        -bases[base_idx]
+                    } else {
+                        bases[base_idx]
+                    }
+                };
+                let is_identity = |point: &Self| -> bool {
+                    <Self as group::prime::PrimeCurveAffine>::is_identity(point).into()
+                };
+
+                // Affine addition formula (P != Q):
+                // - lambda = (y_2 - y_1) / (x_2 - x_1)
+                // - x_3 = lambda^2 - (x_2 + x_1)
+                // - y_3 = lambda * (x_1 - x_3) - y_1
+
+                // Batch invert accumulator
+                let mut acc = $base::one();
+
+                for i in (0..num_points).step_by(2) {
+                    // Where that result of the point addition will be stored
+                    let out_idx = output_indices[i >> 1] as usize - offset;
+
+                    #[cfg(all(feature = "prefetch", target_arch = "x86_64"))]
+                    if i < num_points - 2 {
+                        if LOAD_POINTS {
+                            $crate::prefetch::<Self>(bases, base_positions[i + 2] as usize);
+                            $crate::prefetch::<Self>(bases, base_positions[i + 3] as usize);
+                        }
+                        $crate::prefetch::<Self>(points, output_indices[(i >> 1) + 1] as usize - offset);
+                    }
+                    if LOAD_POINTS {
+                        points[i] = get_point(base_positions[i]);
+                        points[i + 1] = get_point(base_positions[i + 1]);
+                    }
+
+                    if COMPLETE {
+                        // Nothing to do here if one of the points is zero
+                        if is_identity(&points[i]) || is_identity(&points[i + 1]) {
+                            continue;
+                        }
+
+                        if points[i].x == points[i + 1].x {
+                            if points[i].y != points[i + 1].y {
+                                // P == -Q, so the sum is zero
+                                points[i] = <Self as group::prime::PrimeCurveAffine>::identity();
+                                points[i + 1] = points[i];
+                                continue;
+                            }
+
+                            // Point doubling (P == Q)
+                            // - s = (3 * x^2 + a) / (2 * y)
+                            // - x_2 = s^2 - (2 * x)
+                            // - y_2 = s * (x - x_2) - y
+
+                            // (2 * x)
+                            points[out_idx].x = points[i].x + points[i].x;
+                            // x^2
+                            let xx = points[i].x.square();
+                            // (2 * y)
+                            points[i + 1].x = points[i].y + points[i].y;
+                            // (3 * x^2 + a) * acc
+                            points[i + 1].y = (xx + xx + xx + $name::curve_constant_a()) * acc;
+                            // acc *= (2 * y)
+                            acc *= points[i + 1].x;
+                            continue;
+                        }
+                    }
+
+                    // (x_2 + x_1)
+                    points[out_idx].x = points[i].x + points[i + 1].x;
+                    // (x_2 - x_1)
+                    points[i + 1].x -= points[i].x;
+                    // (y2 - y1) * acc
+                    points[i + 1].y = (points[i + 1].y - points[i].y) * acc;
+                    // acc *= (x_2 - x_1)
+                    acc *= points[i + 1].x;
+                }
+
+                // Batch invert
+                acc = acc.invert().unwrap_or(acc);
+
+                for i in (0..num_points).step_by(2).rev() {
+                    // Where that result of the point addition will be stored
+                    let out_idx = output_indices[i >> 1] as usize - offset;
+
+                    #[cfg(all(feature = "prefetch", target_arch = "x86_64"))]
+                    if i > 0 {
+                        $crate::prefetch::<Self>(points, output_indices[(i >> 1) - 1] as usize - offset);
+                    }
+
+                    if COMPLETE {
+                        // points[i] is zero so the sum is points[i + 1]
+                        if is_identity(&points[i]) {
+                            points[out_idx] = points[i + 1];
+                            continue;
+                        }
+                        // points[i + 1] is zero so the sum is points[i]
+                        if is_identity(&points[i + 1]) {
+                            points[out_idx] = points[i];
+                            continue;
+                        }
+                    }
+
+                    // lambda
+                    points[i + 1].y *= acc;
+                    // acc *= (x_2 - x_1)
+                    acc *= points[i + 1].x;
+                    // x_3 = lambda^2 - (x_2 + x_1)
+                    points[out_idx].x = points[i + 1].y.square() - points[out_idx].x;
+                    // y_3 = lambda * (x_1 - x_3) - y_1
+                    points[out_idx].y =
+                        points[i + 1].y * (points[i].x - points[out_idx].x) - points[i].y;
+                }
+            }
+
+            fn into_coordinates(self) -> (Self::Base, Self::Base) {
+                (self.x, self.y)
+            }
+        }
+
         impl_binops_additive!($name, $name);
         impl_binops_additive!($name, $name_affine);
         impl_binops_additive_specify_output!($name_affine, $name_affine, $name);
@@ -911,7 +1215,7 @@ macro_rules! new_curve_impl {
                 let t5 = t5 * x3;
                 let x3 = t1 + t2;
                 let t5 = t5 - x3;
-                let z3 = $name::curve_constant_a() * t4;
+                let z3 = $name::mul_by_a(&t4);
                 let x3 = $name::mul_by_3b(&t2);
                 let z3 = x3 + z3;
                 let x3 = t1 - z3;
@@ -919,11 +1223,11 @@ macro_rules! new_curve_impl {
                 let y3 = x3 * z3;
                 let t1 = t0 + t0;
                 let t1 = t1 + t0;
-                let t2 = $name::curve_constant_a() * t2;
+                let t2 = $name::mul_by_a(&t2);
                 let t4 = $name::mul_by_3b(&t4);
                 let t1 = t1 + t2;
                 let t2 = t0 - t2;
-                let t2 = $name::curve_constant_a() * t2;
+                let t2 = $name::mul_by_a(&t2);
                 let t4 = t4 + t2;
                 let t0 = t1 * t4;
                 let y3 = y3 + t0;
@@ -959,7 +1263,7 @@ macro_rules! new_curve_impl {
                 let t4 = t4 + self.x;
                 let t5 = rhs.y * self.z;
                 let t5 = t5 + self.y;
-                let z3 = $name::curve_constant_a() * t4;
+                let z3 = $name::mul_by_a(&t4);
                 let x3 = $name::mul_by_3b(&self.z);
                 let z3 = x3 + z3;
                 let x3 = t1 - z3;
@@ -967,11 +1271,11 @@ macro_rules! new_curve_impl {
                 let y3 = x3 * z3;
                 let t1 = t0 + t0;
                 let t1 = t1 + t0;
-                let t2 = $name::curve_constant_a() * self.z;
+                let t2 = $name::mul_by_a(&self.z);
                 let t4 = $name::mul_by_3b(&t4);
                 let t1 = t1 + t2;
                 let t2 = t0 - t2;
-                let t2 = $name::curve_constant_a() * t2;
+                let t2 = $name::mul_by_a(&t2);
                 let t4 = t4 + t2;
                 let t0 = t1 * t4;
                 let y3 = y3 + t0;
@@ -1109,5 +1413,21 @@ macro_rules! new_curve_impl {
                 acc
             }
         }
+
+        impl Mul<$crate::signed::SignedScalar<$scalar>> for $name {
+            type Output = $name;
+
+            fn mul(self, other: $crate::signed::SignedScalar<$scalar>) -> Self::Output {
+                other.mul_point(&self)
+            }
+        }
+
+        impl Mul<$crate::signed::SignedScalar<$scalar>> for $name_affine {
+            type Output = $name;
+
+            fn mul(self, other: $crate::signed::SignedScalar<$scalar>) -> Self::Output {
+                other.mul_point(&$name::from(self))
+            }
+        }
     };
 }
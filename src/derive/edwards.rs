@@ -0,0 +1,953 @@
+/// Implements a twisted Edwards curve `a x^2 + y^2 = 1 + d x^2 y^2`, with
+/// `a` a square and `d` a non-square so that the addition law is complete.
+///
+/// Points are kept in extended coordinates `(X : Y : Z : T)` with `x = X/Z`,
+/// `y = Y/Z` and `x y = T/Z`. `$name` is the whole curve, of order
+/// `cofactor * r` where `r` is the modulus of `$scalar`, and `$name_subgroup`
+/// wraps the points of its subgroup of order `r`, which is the `PrimeGroup`
+/// of `CofactorGroup`. `$name` decodes and samples any point of the curve,
+/// while `$name_subgroup` only accepts points of the subgroup.
+///
+/// `$torsion_generator` is a point of order `cofactor`. The points of small
+/// order form a cyclic group, since `(0, -1)` is the only point of order two
+/// on a complete curve, and it generates them.
+///
+/// The compressed encoding is `y` in little endian, with the sign of `x` in
+/// the top bit of the last byte, so the base field must leave that bit free.
+/// As in circomlib's `packPoint`, `x` is negative if it is larger than
+/// `(p - 1) / 2`.
+#[macro_export]
+macro_rules! new_edwards_curve_impl {
+    (($($privacy:tt)*),
+    $name:ident,
+    $name_affine:ident,
+    $name_subgroup:ident,
+    $base:ident,
+    $scalar:ident,
+    $generator:expr,
+    $constant_a:expr,
+    $constant_d:expr,
+    $cofactor:expr,
+    $torsion_generator:expr,
+    $curve_id:literal,
+    ) => {
+        paste::paste! {
+
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        #[cfg_attr(feature = "derive_serde", derive(Serialize, Deserialize))]
+        pub struct [< $name Compressed >](#[cfg_attr(feature = "derive_serde", serde(with = "serde_arrays"))] [u8; $base::size()]);
+
+        impl std::fmt::Debug for [< $name Compressed >] {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0[..].fmt(f)
+            }
+        }
+
+        impl Default for [< $name Compressed >] {
+            fn default() -> Self {
+                [< $name Compressed >]([0; $base::size()])
+            }
+        }
+
+        impl AsRef<[u8]> for [< $name Compressed >] {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl AsMut<[u8]> for [< $name Compressed >] {
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+
+        impl<'a> TryFrom<&'a [u8]> for [< $name Compressed >] {
+            type Error = $crate::serde::InvalidEncoding;
+
+            fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                bytes.try_into().map(Self).map_err(|_| $crate::serde::InvalidEncoding)
+            }
+        }
+
+        impl From<[< $name Compressed >]> for [u8; $base::size()] {
+            fn from(value: [< $name Compressed >]) -> Self {
+                value.0
+            }
+        }
+
+        impl group::GroupEncoding for $name_affine {
+            type Repr = [< $name Compressed >];
+
+            // The decoded point is always on the curve, and points outside of
+            // the prime-order subgroup are left to the subgroup type to reject.
+            fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+                Self::from_bytes_unchecked(bytes)
+            }
+
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+                let mut tmp = bytes.0;
+                let xsign = Choice::from(tmp[$base::size() - 1] >> 7);
+                tmp[$base::size() - 1] &= 0b0111_1111;
+
+                $base::from_bytes(&tmp).and_then(|y| {
+                    // x^2 = (1 - y^2) / (a - d y^2), where the denominator is
+                    // nonzero since a / d is not a square.
+                    let y2 = y.square();
+                    let u = $base::one() - y2;
+                    let v = $name::curve_constant_a() - $name::curve_constant_d() * y2;
+                    v.invert().and_then(|v_inv| (u * v_inv).sqrt()).and_then(|x| {
+                        let sign = $name::is_negative(&x);
+                        let x = $base::conditional_select(&x, &-x, xsign ^ sign);
+
+                        // There is no negative zero.
+                        CtOption::new($name_affine { x, y }, !(x.is_zero() & xsign))
+                    })
+                })
+            }
+
+            fn to_bytes(&self) -> Self::Repr {
+                let mut bytes = self.y.to_bytes();
+                bytes[$base::size() - 1] |= $name::is_negative(&self.x).unwrap_u8() << 7;
+                [< $name Compressed >](bytes)
+            }
+        }
+
+        impl group::GroupEncoding for $name {
+            type Repr = [< $name Compressed >];
+
+            fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+                $name_affine::from_bytes(bytes).map(Self::from)
+            }
+
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+                $name_affine::from_bytes_unchecked(bytes).map(Self::from)
+            }
+
+            fn to_bytes(&self) -> Self::Repr {
+                $name_affine::from(self).to_bytes()
+            }
+        }
+
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        pub struct [< $name Uncompressed >]([u8; 2 * $base::size()]);
+
+        impl std::fmt::Debug for [< $name Uncompressed >] {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0[..].fmt(f)
+            }
+        }
+
+        impl Default for [< $name Uncompressed >] {
+            fn default() -> Self {
+                [< $name Uncompressed >]([0; 2 * $base::size()])
+            }
+        }
+
+        impl AsRef<[u8]> for [< $name Uncompressed >] {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl AsMut<[u8]> for [< $name Uncompressed >] {
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+
+        impl<'a> TryFrom<&'a [u8]> for [< $name Uncompressed >] {
+            type Error = $crate::serde::InvalidEncoding;
+
+            fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                bytes.try_into().map(Self).map_err(|_| $crate::serde::InvalidEncoding)
+            }
+        }
+
+        impl From<[< $name Uncompressed >]> for [u8; 2 * $base::size()] {
+            fn from(value: [< $name Uncompressed >]) -> Self {
+                value.0
+            }
+        }
+
+        // `x` then `y`, both in little endian. The identity is `(0, 1)` and
+        // needs no flag.
+        impl group::UncompressedEncoding for $name_affine {
+            type Uncompressed = [< $name Uncompressed >];
+
+            fn from_uncompressed(bytes: &Self::Uncompressed) -> CtOption<Self> {
+                Self::from_uncompressed_unchecked(bytes)
+                    .and_then(|p| CtOption::new(p, p.is_on_curve()))
+            }
+
+            fn from_uncompressed_unchecked(bytes: &Self::Uncompressed) -> CtOption<Self> {
+                let mut x = [0; $base::size()];
+                x.copy_from_slice(&bytes.0[..$base::size()]);
+                let mut y = [0; $base::size()];
+                y.copy_from_slice(&bytes.0[$base::size()..]);
+
+                $base::from_bytes(&x)
+                    .and_then(|x| $base::from_bytes(&y).map(|y| $name_affine { x, y }))
+            }
+
+            fn to_uncompressed(&self) -> Self::Uncompressed {
+                let mut res = [0; 2 * $base::size()];
+                res[..$base::size()].copy_from_slice(&self.x.to_bytes());
+                res[$base::size()..].copy_from_slice(&self.y.to_bytes());
+                [< $name Uncompressed >](res)
+            }
+        }
+
+        impl<'a> TryFrom<&'a [u8]> for $name_affine {
+            type Error = $crate::serde::InvalidEncoding;
+
+            /// Decodes the compressed `GroupEncoding` of a point of the
+            /// prime-order subgroup.
+            fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                let repr = [< $name Compressed >]::try_from(bytes)?;
+                Option::from(<Self as GroupEncoding>::from_bytes(&repr)).ok_or($crate::serde::InvalidEncoding)
+            }
+        }
+
+        impl<'a> TryFrom<&'a [u8]> for $name {
+            type Error = $crate::serde::InvalidEncoding;
+
+            /// Decodes the compressed `GroupEncoding` of a point of the
+            /// prime-order subgroup.
+            fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                $name_affine::try_from(bytes).map(Self::from)
+            }
+        }
+
+        }
+
+        #[derive(Copy, Clone, Debug)]
+        #[cfg_attr(feature = "derive_serde", derive(Serialize, Deserialize))]
+        $($privacy)* struct $name {
+            pub x: $base,
+            pub y: $base,
+            pub z: $base,
+            pub t: $base,
+        }
+
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        #[cfg_attr(feature = "derive_serde", derive(Serialize, Deserialize))]
+        $($privacy)* struct $name_affine {
+            pub x: $base,
+            pub y: $base,
+        }
+
+        impl $name {
+            /// The cofactor of the prime-order subgroup.
+            pub const COFACTOR: u64 = $cofactor;
+
+            /// The curve identifier.
+            pub const CURVE_ID: &'static str = $curve_id;
+
+            pub fn generator() -> Self {
+                $name_affine::generator().to_curve()
+            }
+
+            /// Returns a generator of the points of small order, whose order
+            /// is the cofactor.
+            pub fn torsion_generator() -> Self {
+                $name_affine {
+                    x: $torsion_generator.0,
+                    y: $torsion_generator.1,
+                }
+                .to_curve()
+            }
+
+            /// Returns the `COFACTOR` points of small order, as the multiples
+            /// of `torsion_generator` starting with the identity.
+            pub fn small_order_points() -> Vec<Self> {
+                let t = $name::torsion_generator();
+                let mut points = vec![$name::identity()];
+                for i in 1..Self::COFACTOR as usize {
+                    points.push(points[i - 1] + t);
+                }
+                points
+            }
+
+            const fn curve_constant_a() -> $base {
+                $name_affine::curve_constant_a()
+            }
+
+            const fn curve_constant_d() -> $base {
+                $name_affine::curve_constant_d()
+            }
+
+            // Multiplies by the curve constant `a`, skipping the product for
+            // the common `a = -1` curves.
+            #[inline]
+            fn mul_by_a(input: &$base) -> $base {
+                // Compared limb by limb at compile time, so that the branch
+                // folds away.
+                const A_IS_MINUS_1: bool = $constant_a.eq_vartime(&$base::minus_one());
+                if A_IS_MINUS_1 {
+                    -input
+                } else {
+                    input * $name::curve_constant_a()
+                }
+            }
+
+            /// Returns true if this point is on the curve, i.e. if
+            /// `(a X^2 + Y^2) Z^2 = Z^4 + d X^2 Y^2` and `X Y = T Z`.
+            pub fn is_on_curve(&self) -> Choice {
+                let x2 = self.x.square();
+                let y2 = self.y.square();
+                let z2 = self.z.square();
+                ((($name::mul_by_a(&x2) + y2) * z2)
+                    .ct_eq(&(z2.square() + $name::curve_constant_d() * x2 * y2)))
+                    & (self.x * self.y).ct_eq(&(self.t * self.z))
+                    & !self.z.is_zero()
+            }
+
+            // Returns true if `x > (p - 1) / 2`, i.e. if `2x` wraps around the
+            // odd modulus and is odd.
+            fn is_negative(x: &$base) -> Choice {
+                Choice::from(x.double().to_bytes()[0] & 1)
+            }
+
+            /// Doubles this point in place.
+            #[inline]
+            pub fn double_in_place(&mut self) {
+                *self = group::Group::double(self);
+            }
+
+            /// Returns this point multiplied by the cofactor.
+            fn mul_by_cofactor(&self) -> $name {
+                let mut acc = $name::identity();
+                for i in (0..64 - Self::COFACTOR.leading_zeros()).rev() {
+                    acc = acc.double();
+                    if (Self::COFACTOR >> i) & 1 == 1 {
+                        acc += self;
+                    }
+                }
+                acc
+            }
+        }
+
+        impl $name_affine {
+            pub fn generator() -> Self {
+                Self {
+                    x: $generator.0,
+                    y: $generator.1,
+                }
+            }
+
+            const fn curve_constant_a() -> $base {
+                $constant_a
+            }
+
+            const fn curve_constant_d() -> $base {
+                $constant_d
+            }
+
+            /// Returns the curve constant `a`.
+            pub fn a() -> $base {
+                $constant_a
+            }
+
+            /// Returns the curve constant `d`.
+            pub fn d() -> $base {
+                $constant_d
+            }
+
+            /// Returns true if `a x^2 + y^2 = 1 + d x^2 y^2`.
+            pub fn is_on_curve(&self) -> Choice {
+                let x2 = self.x.square();
+                let y2 = self.y.square();
+                ($name::mul_by_a(&x2) + y2).ct_eq(&($base::one() + $name::curve_constant_d() * x2 * y2))
+            }
+
+            /// Returns the point `(x, y)` if it is on the curve.
+            pub fn from_xy(x: $base, y: $base) -> CtOption<Self> {
+                let p = $name_affine { x, y };
+                CtOption::new(p, p.is_on_curve())
+            }
+
+            /// Returns a random point of the curve, which is in the
+            /// prime-order subgroup only with probability `1 / COFACTOR`.
+            pub fn random(mut rng: impl RngCore) -> Self {
+                loop {
+                    let y = $base::random(&mut rng);
+                    let mut repr = <Self as group::GroupEncoding>::Repr::default();
+                    repr.as_mut().copy_from_slice(&y.to_bytes());
+                    repr.as_mut()[$base::size() - 1] |= ((rng.next_u32() % 2) as u8) << 7;
+                    let p = $name_affine::from_bytes_unchecked(&repr);
+                    if let Some(p) = Option::<$name_affine>::from(p) {
+                        return p;
+                    }
+                }
+            }
+        }
+
+        // Extended implementations
+
+        impl<'a> From<&'a $name_affine> for $name {
+            fn from(p: &'a $name_affine) -> $name {
+                p.to_curve()
+            }
+        }
+
+        impl From<$name_affine> for $name {
+            fn from(p: $name_affine) -> $name {
+                p.to_curve()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name::identity()
+            }
+        }
+
+        impl subtle::ConstantTimeEq for $name {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                // Z is never zero, so (X/Z, Y/Z) = (X'/Z', Y'/Z') if and only
+                // if XZ' = X'Z and YZ' = Y'Z.
+                (self.x * other.z).ct_eq(&(other.x * self.z))
+                    & (self.y * other.z).ct_eq(&(other.y * self.z))
+            }
+        }
+
+        impl subtle::ConditionallySelectable for $name {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                $name {
+                    x: $base::conditional_select(&a.x, &b.x, choice),
+                    y: $base::conditional_select(&a.y, &b.y, choice),
+                    z: $base::conditional_select(&a.z, &b.z, choice),
+                    t: $base::conditional_select(&a.t, &b.t, choice),
+                }
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.ct_eq(other).into()
+            }
+        }
+
+        impl cmp::Eq for $name {}
+
+        impl group::Curve for $name {
+            type AffineRepr = $name_affine;
+
+            fn batch_normalize(p: &[Self], q: &mut [Self::AffineRepr]) {
+                assert_eq!(p.len(), q.len());
+
+                let mut acc = $base::one();
+                for (p, q) in p.iter().zip(q.iter_mut()) {
+                    // We use the `x` field of $name_affine to store the product
+                    // of previous z-coordinates seen.
+                    q.x = acc;
+                    acc *= p.z;
+                }
+
+                // All the z-coordinates are nonzero.
+                acc = acc.invert().unwrap();
+
+                for (p, q) in p.iter().rev().zip(q.iter_mut().rev()) {
+                    // Compute tmp = 1/z
+                    let tmp = q.x * acc;
+
+                    // Cancel out z-coordinate in denominator of `acc`
+                    acc *= p.z;
+
+                    q.x = p.x * tmp;
+                    q.y = p.y * tmp;
+                }
+            }
+
+            fn to_affine(&self) -> Self::AffineRepr {
+                let zinv = self.z.invert().unwrap();
+                $name_affine {
+                    x: self.x * zinv,
+                    y: self.y * zinv,
+                }
+            }
+        }
+
+        impl group::Group for $name {
+            type Scalar = $scalar;
+
+            fn random(mut rng: impl RngCore) -> Self {
+                $name_affine::random(&mut rng).to_curve()
+            }
+
+            fn double(&self) -> Self {
+                // dbl-2008-hwcd, https://eprint.iacr.org/2008/522.pdf
+                let a = self.x.square();
+                let b = self.y.square();
+                let c = self.z.square().double();
+                let d = $name::mul_by_a(&a);
+                let e = (self.x + self.y).square() - a - b;
+                let g = d + b;
+                let f = g - c;
+                let h = d - b;
+
+                $name {
+                    x: e * f,
+                    y: g * h,
+                    z: f * g,
+                    t: e * h,
+                }
+            }
+
+            fn generator() -> Self {
+                $name::generator()
+            }
+
+            fn identity() -> Self {
+                Self {
+                    x: $base::zero(),
+                    y: $base::one(),
+                    z: $base::one(),
+                    t: $base::zero(),
+                }
+            }
+
+            fn is_identity(&self) -> Choice {
+                self.x.is_zero() & self.y.ct_eq(&self.z)
+            }
+        }
+
+        impl group::cofactor::CofactorGroup for $name {
+            type Subgroup = $name_subgroup;
+
+            fn clear_cofactor(&self) -> $name_subgroup {
+                $name_subgroup(self.mul_by_cofactor())
+            }
+
+            fn into_subgroup(self) -> CtOption<$name_subgroup> {
+                CtOption::new($name_subgroup(self), self.is_torsion_free())
+            }
+
+            fn is_torsion_free(&self) -> Choice {
+                // [r - 1] P + P = [r] P, which is the identity if and only if
+                // P is in the subgroup of order r.
+                (self * -$scalar::one() + self).is_identity()
+            }
+        }
+
+        impl group::cofactor::CofactorCurve for $name {
+            type Affine = $name_affine;
+        }
+
+        impl $crate::torsion::SmallOrder for $name {
+            fn torsion_generators() -> Vec<(u64, Self)> {
+                vec![(Self::COFACTOR, $name::torsion_generator())]
+            }
+        }
+
+        // Subgroup implementations
+
+        /// A point of the prime-order subgroup.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        #[cfg_attr(feature = "derive_serde", derive(Serialize, Deserialize))]
+        $($privacy)* struct $name_subgroup($name);
+
+        impl From<$name_subgroup> for $name {
+            fn from(p: $name_subgroup) -> $name {
+                p.0
+            }
+        }
+
+        impl<'a> From<&'a $name_subgroup> for $name {
+            fn from(p: &'a $name_subgroup) -> $name {
+                p.0
+            }
+        }
+
+        impl Default for $name_subgroup {
+            fn default() -> $name_subgroup {
+                $name_subgroup::identity()
+            }
+        }
+
+        impl subtle::ConstantTimeEq for $name_subgroup {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0.ct_eq(&other.0)
+            }
+        }
+
+        impl subtle::ConditionallySelectable for $name_subgroup {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                $name_subgroup($name::conditional_select(&a.0, &b.0, choice))
+            }
+        }
+
+        impl group::Group for $name_subgroup {
+            type Scalar = $scalar;
+
+            fn random(mut rng: impl RngCore) -> Self {
+                loop {
+                    let p = $name::random(&mut rng).mul_by_cofactor();
+                    if !bool::from(p.is_identity()) {
+                        return $name_subgroup(p);
+                    }
+                }
+            }
+
+            fn double(&self) -> Self {
+                $name_subgroup(self.0.double())
+            }
+
+            fn generator() -> Self {
+                $name_subgroup($name::generator())
+            }
+
+            fn identity() -> Self {
+                $name_subgroup($name::identity())
+            }
+
+            fn is_identity(&self) -> Choice {
+                self.0.is_identity()
+            }
+        }
+
+        impl group::GroupEncoding for $name_subgroup {
+            type Repr = <$name as group::GroupEncoding>::Repr;
+
+            fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+                use group::cofactor::CofactorGroup;
+                $name::from_bytes(bytes).and_then(|p| p.into_subgroup())
+            }
+
+            // The subgroup check is what makes this type sound, so it is not
+            // skipped.
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+                Self::from_bytes(bytes)
+            }
+
+            fn to_bytes(&self) -> Self::Repr {
+                self.0.to_bytes()
+            }
+        }
+
+        impl group::prime::PrimeGroup for $name_subgroup {}
+
+        impl<T> Sum<T> for $name_subgroup
+        where
+            T: core::borrow::Borrow<$name_subgroup>,
+        {
+            fn sum<I>(iter: I) -> Self
+            where
+                I: Iterator<Item = T>,
+            {
+                iter.fold(Self::identity(), |acc, item| acc + item.borrow())
+            }
+        }
+
+        impl<'a> Neg for &'a $name_subgroup {
+            type Output = $name_subgroup;
+
+            fn neg(self) -> $name_subgroup {
+                $name_subgroup(-self.0)
+            }
+        }
+
+        impl Neg for $name_subgroup {
+            type Output = $name_subgroup;
+
+            fn neg(self) -> $name_subgroup {
+                -&self
+            }
+        }
+
+        impl<'a, 'b> Add<&'a $name_subgroup> for &'b $name_subgroup {
+            type Output = $name_subgroup;
+
+            fn add(self, rhs: &'a $name_subgroup) -> $name_subgroup {
+                $name_subgroup(self.0 + rhs.0)
+            }
+        }
+
+        impl<'a, 'b> Sub<&'a $name_subgroup> for &'b $name_subgroup {
+            type Output = $name_subgroup;
+
+            fn sub(self, rhs: &'a $name_subgroup) -> $name_subgroup {
+                $name_subgroup(self.0 - rhs.0)
+            }
+        }
+
+        impl<'a, 'b> Add<&'a $name_subgroup> for &'b $name {
+            type Output = $name;
+
+            fn add(self, rhs: &'a $name_subgroup) -> $name {
+                self + rhs.0
+            }
+        }
+
+        impl<'a, 'b> Sub<&'a $name_subgroup> for &'b $name {
+            type Output = $name;
+
+            fn sub(self, rhs: &'a $name_subgroup) -> $name {
+                self - rhs.0
+            }
+        }
+
+        impl<'a, 'b> Mul<&'b $scalar> for &'a $name_subgroup {
+            type Output = $name_subgroup;
+
+            fn mul(self, other: &'b $scalar) -> $name_subgroup {
+                $name_subgroup(self.0 * other)
+            }
+        }
+
+        impl_binops_additive!($name_subgroup, $name_subgroup);
+        impl_binops_additive!($name, $name_subgroup);
+        impl_binops_multiplicative!($name_subgroup, $scalar);
+
+        // Affine implementations
+
+        impl<'a> From<&'a $name> for $name_affine {
+            fn from(p: &'a $name) -> $name_affine {
+                p.to_affine()
+            }
+        }
+
+        impl From<$name> for $name_affine {
+            fn from(p: $name) -> $name_affine {
+                p.to_affine()
+            }
+        }
+
+        impl Default for $name_affine {
+            fn default() -> $name_affine {
+                $name_affine::identity()
+            }
+        }
+
+        impl subtle::ConstantTimeEq for $name_affine {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.x.ct_eq(&other.x) & self.y.ct_eq(&other.y)
+            }
+        }
+
+        impl subtle::ConditionallySelectable for $name_affine {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                $name_affine {
+                    x: $base::conditional_select(&a.x, &b.x, choice),
+                    y: $base::conditional_select(&a.y, &b.y, choice),
+                }
+            }
+        }
+
+        impl group::cofactor::CofactorCurveAffine for $name_affine {
+            type Curve = $name;
+            type Scalar = $scalar;
+
+            fn identity() -> Self {
+                Self {
+                    x: $base::zero(),
+                    y: $base::one(),
+                }
+            }
+
+            fn generator() -> Self {
+                $name_affine::generator()
+            }
+
+            fn is_identity(&self) -> Choice {
+                self.x.is_zero() & self.y.ct_eq(&$base::one())
+            }
+
+            fn to_curve(&self) -> Self::Curve {
+                $name {
+                    x: self.x,
+                    y: self.y,
+                    z: $base::one(),
+                    t: self.x * self.y,
+                }
+            }
+        }
+
+        impl_binops_additive!($name, $name);
+        impl_binops_additive!($name, $name_affine);
+        impl_binops_additive_specify_output!($name_affine, $name_affine, $name);
+        impl_binops_additive_specify_output!($name_affine, $name, $name);
+        impl_binops_multiplicative!($name, $scalar);
+        impl_binops_multiplicative_mixed!($name_affine, $scalar, $name);
+
+        impl<'a> Neg for &'a $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name {
+                    x: -self.x,
+                    y: self.y,
+                    z: self.z,
+                    t: -self.t,
+                }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                -&self
+            }
+        }
+
+        impl<T> Sum<T> for $name
+        where
+            T: core::borrow::Borrow<$name>,
+        {
+            fn sum<I>(iter: I) -> Self
+            where
+                I: Iterator<Item = T>,
+            {
+                iter.fold(Self::identity(), |acc, item| acc + item.borrow())
+            }
+        }
+
+        impl<'a, 'b> Add<&'a $name> for &'b $name {
+            type Output = $name;
+
+            fn add(self, rhs: &'a $name) -> $name {
+                // add-2008-hwcd, https://eprint.iacr.org/2008/522.pdf
+                let a = self.x * rhs.x;
+                let b = self.y * rhs.y;
+                let c = $name::curve_constant_d() * self.t * rhs.t;
+                let d = self.z * rhs.z;
+                let e = (self.x + self.y) * (rhs.x + rhs.y) - a - b;
+                let f = d - c;
+                let g = d + c;
+                let h = b - $name::mul_by_a(&a);
+
+                $name {
+                    x: e * f,
+                    y: g * h,
+                    z: f * g,
+                    t: e * h,
+                }
+            }
+        }
+
+        impl<'a, 'b> Add<&'a $name_affine> for &'b $name {
+            type Output = $name;
+
+            // Mixed addition
+            fn add(self, rhs: &'a $name_affine) -> $name {
+                // add-2008-hwcd with Z2 = 1 and T2 = x2 y2
+                let a = self.x * rhs.x;
+                let b = self.y * rhs.y;
+                let c = $name::curve_constant_d() * self.t * rhs.x * rhs.y;
+                let d = self.z;
+                let e = (self.x + self.y) * (rhs.x + rhs.y) - a - b;
+                let f = d - c;
+                let g = d + c;
+                let h = b - $name::mul_by_a(&a);
+
+                $name {
+                    x: e * f,
+                    y: g * h,
+                    z: f * g,
+                    t: e * h,
+                }
+            }
+        }
+
+        impl<'a, 'b> Sub<&'a $name> for &'b $name {
+            type Output = $name;
+
+            fn sub(self, other: &'a $name) -> $name {
+                self + (-other)
+            }
+        }
+
+        impl<'a, 'b> Sub<&'a $name_affine> for &'b $name {
+            type Output = $name;
+
+            fn sub(self, other: &'a $name_affine) -> $name {
+                self + (-other)
+            }
+        }
+
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        impl<'a, 'b> Mul<&'b $scalar> for &'a $name {
+            type Output = $name;
+
+            // This is a simple double-and-add implementation of point
+            // multiplication, moving from most significant to least
+            // significant bit of the scalar.
+
+            fn mul(self, other: &'b $scalar) -> Self::Output {
+                let mut acc = $name::identity();
+                for bit in other
+                    .to_repr()
+                    .iter()
+                    .rev()
+                    .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
+                {
+                    acc = acc.double();
+                    acc = $name::conditional_select(&acc, &(acc + self), bit);
+                }
+
+                acc
+            }
+        }
+
+        impl<'a> Neg for &'a $name_affine {
+            type Output = $name_affine;
+
+            fn neg(self) -> $name_affine {
+                $name_affine {
+                    x: -self.x,
+                    y: self.y,
+                }
+            }
+        }
+
+        impl Neg for $name_affine {
+            type Output = $name_affine;
+
+            fn neg(self) -> $name_affine {
+                -&self
+            }
+        }
+
+        impl<'a, 'b> Add<&'a $name> for &'b $name_affine {
+            type Output = $name;
+
+            fn add(self, rhs: &'a $name) -> $name {
+                rhs + self
+            }
+        }
+
+        impl<'a, 'b> Add<&'a $name_affine> for &'b $name_affine {
+            type Output = $name;
+
+            fn add(self, rhs: &'a $name_affine) -> $name {
+                self.to_curve() + rhs
+            }
+        }
+
+        impl<'a, 'b> Sub<&'a $name_affine> for &'b $name_affine {
+            type Output = $name;
+
+            fn sub(self, other: &'a $name_affine) -> $name {
+                self + (-other)
+            }
+        }
+
+        impl<'a, 'b> Sub<&'a $name> for &'b $name_affine {
+            type Output = $name;
+
+            fn sub(self, other: &'a $name) -> $name {
+                self + (-other)
+            }
+        }
+
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        impl<'a, 'b> Mul<&'b $scalar> for &'a $name_affine {
+            type Output = $name;
+
+            fn mul(self, other: &'b $scalar) -> Self::Output {
+                self.to_curve() * other
+            }
+        }
+    };
+}
@@ -25,6 +25,9 @@ macro_rules! field_common {
         $r3:ident
     ) => {
         impl $field {
+            /// The modulus, as little-endian 64-bit limbs.
+            pub const MODULUS_LIMBS: [u64; 4] = $modulus.0;
+
             /// Returns zero, the additive identity.
             #[inline]
             pub const fn zero() -> $field {
@@ -37,7 +40,92 @@ macro_rules! field_common {
                 $r
             }
 
-            fn from_u512(limbs: [u64; 8]) -> $field {
+            /// Returns minus one, the constant `a` of many twisted Edwards
+            /// curves.
+            pub const fn minus_one() -> $field {
+                $field::from_raw([
+                    $modulus.0[0] - 1,
+                    $modulus.0[1],
+                    $modulus.0[2],
+                    $modulus.0[3],
+                ])
+            }
+
+            /// Returns minus three, the constant `a` of many short Weierstrass
+            /// curves.
+            pub const fn minus_three() -> $field {
+                $field::from_raw([
+                    $modulus.0[0] - 3,
+                    $modulus.0[1],
+                    $modulus.0[2],
+                    $modulus.0[3],
+                ])
+            }
+
+            /// Returns true if `self` and `other` have the same limbs. Unlike
+            /// `==`, this is a `const fn`, so that the curve macros can test
+            /// their constants at compile time, and it runs in variable time.
+            pub const fn eq_vartime(&self, other: &Self) -> bool {
+                self.0[0] == other.0[0]
+                    && self.0[1] == other.0[1]
+                    && self.0[2] == other.0[2]
+                    && self.0[3] == other.0[3]
+            }
+
+            /// Squares `self` in place.
+            #[inline]
+            pub fn square_assign(&mut self) {
+                *self = self.square();
+            }
+
+            /// Doubles `self` in place.
+            #[inline]
+            pub fn double_assign(&mut self) {
+                *self = self.double();
+            }
+
+            /// Returns `1 / 2^k`. The values for `k <= S`, which are the ones
+            /// needed to scale inverse FFTs over every supported domain size,
+            /// are computed once and cached.
+            pub fn inv_2_pow(k: usize) -> $field {
+                lazy_static::lazy_static! {
+                    static ref INV_2_POW: Vec<$field> = {
+                        let s = <$field as ff::PrimeField>::S as usize;
+                        std::iter::successors(Some($field::one()), |x| Some(x * $two_inv))
+                            .take(s + 1)
+                            .collect()
+                    };
+                }
+                INV_2_POW
+                    .get(k)
+                    .copied()
+                    .unwrap_or_else(|| <$field as ff::Field>::pow_vartime(&$two_inv, [k as u64]))
+            }
+
+            /// `[w, w^2, w^4, ..., w^(2^S)]`, where `w` is `ROOT_OF_UNITY`, so
+            /// that entry `i` is a primitive `2^(S - i)`-th root of unity and
+            /// the last entry is one. The table is computed at compile time.
+            pub const ROOTS_OF_UNITY: [$field; <$field as ff::PrimeField>::S as usize + 1] = {
+                let mut roots = [<$field as ff::PrimeField>::ROOT_OF_UNITY;
+                    <$field as ff::PrimeField>::S as usize + 1];
+                let mut i = 1;
+                while i < roots.len() {
+                    let w = &roots[i - 1].0;
+                    roots[i] = $field($crate::arithmetic::montgomery_mul_loop(
+                        w,
+                        w,
+                        &$modulus.0,
+                        $inv,
+                    ));
+                    i += 1;
+                }
+                roots
+            };
+
+            /// Converts a 512-bit little endian integer, given as limbs, into
+            /// its (congruent) `$field` representation by reducing it modulo
+            /// the field's modulus.
+            pub fn from_u512(limbs: [u64; 8]) -> $field {
                 // We reduce an arbitrary 512-bit number by decomposing it into two 256-bit digits
                 // with the higher bits multiplied by 2^256. Thus, we perform two reductions
                 //
@@ -57,6 +145,23 @@ macro_rules! field_common {
                 d0 * $r2 + d1 * $r3
             }
 
+            /// Converts a 512-bit little endian integer, given as bytes, into
+            /// its (congruent) `$field` representation. See `from_u512`.
+            pub fn from_bytes_wide(bytes: &[u8; 64]) -> $field {
+                let mut limbs = [0u64; 8];
+                for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+                    *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+                }
+                Self::from_u512(limbs)
+            }
+
+            /// Converts a 256-bit little endian integer, which may exceed the
+            /// modulus, into its (congruent) `$field` representation. This is
+            /// the same as `from_raw`.
+            pub const fn from_u256_reduce(val: [u64; 4]) -> Self {
+                Self::from_raw(val)
+            }
+
             /// Converts from an integer represented in little endian
             /// into its (congruent) `$field` representation.
             pub const fn from_raw(val: [u64; 4]) -> Self {
@@ -126,7 +231,7 @@ macro_rules! field_common {
                 }
                 #[cfg(not(feature = "asm"))]
                 {
-                    (&$field(val)).mul(&$r2)
+                    (&$field(val)).mul_inner(&$r2)
                 }
             }
 
@@ -142,6 +247,63 @@ macro_rules! field_common {
                 <Self as ff::PrimeField>::to_repr(self)
             }
 
+            /// Returns the canonical (non-Montgomery) little-endian limbs of
+            /// this element.
+            pub fn to_canonical_limbs(&self) -> [u64; 4] {
+                let bytes = self.to_bytes();
+                let mut limbs = [0u64; 4];
+                for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+                    *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+                }
+                limbs
+            }
+
+            /// Returns true if this element is a square, zero included. This
+            /// runs in constant time, with a batched binary GCD instead of the
+            /// exponentiation of Euler's criterion; the `bn256_field` benchmark
+            /// compares the two.
+            pub fn is_quadratic_residue(&self) -> subtle::Choice {
+                let limbs = self.to_canonical_limbs();
+                !$crate::arithmetic::jacobi_is_negative(&limbs, &$modulus.0)
+                    | ff::Field::is_zero(self)
+            }
+
+            /// Returns the Legendre symbol of this element. See
+            /// `is_quadratic_residue`.
+            pub fn legendre(&self) -> $crate::LegendreSymbol {
+                if bool::from(ff::Field::is_zero(self)) {
+                    $crate::LegendreSymbol::Zero
+                } else if bool::from(self.is_quadratic_residue()) {
+                    $crate::LegendreSymbol::QuadraticResidue
+                } else {
+                    $crate::LegendreSymbol::QuadraticNonResidue
+                }
+            }
+
+            /// Converts a batch of elements into their canonical limbs in
+            /// parallel. See `to_canonical_limbs`.
+            pub fn to_canonical_limbs_batch(elements: &[Self]) -> Vec<[u64; 4]> {
+                let mut limbs = vec![[0u64; 4]; elements.len()];
+                $crate::arithmetic::parallelize(&mut limbs, |chunk, start| {
+                    for (limb, element) in chunk.iter_mut().zip(elements[start..].iter()) {
+                        *limb = element.to_canonical_limbs();
+                    }
+                });
+                limbs
+            }
+
+            /// Converts a batch of canonical little-endian limbs into
+            /// elements in parallel, reducing them as `from_raw` does.
+            pub fn from_canonical_limbs_batch(limbs: &[[u64; 4]]) -> Vec<Self> {
+                let mut elements = vec![Self::zero(); limbs.len()];
+                $crate::arithmetic::parallelize(&mut elements, |chunk, start| {
+                    for (element, limb) in chunk.iter_mut().zip(limbs[start..].iter()) {
+                        *element = Self::from_raw(*limb);
+                    }
+                });
+                elements
+            }
+
             /// Lexicographic comparison of Montgomery forms.
             #[inline(always)]
             const fn is_less_than(x: &[u64; 4], y: &[u64; 4]) -> bool {
@@ -279,6 +441,19 @@ macro_rules! field_common {
             }
         }
 
+        impl<'a> TryFrom<&'a [u8]> for $field {
+            type Error = $crate::serde::InvalidEncoding;
+
+            /// Decodes the canonical `PrimeField` representation.
+            fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+                let repr: [u8; 32] = bytes
+                    .try_into()
+                    .map_err(|_| $crate::serde::InvalidEncoding)?;
+                Option::from(<$field as ff::PrimeField>::from_repr(repr))
+                    .ok_or($crate::serde::InvalidEncoding)
+            }
+        }
+
         impl $crate::serde::SerdeObject for $field {
             fn from_raw_bytes_unchecked(bytes: &[u8]) -> Self {
                 debug_assert_eq!(bytes.len(), 32);
@@ -346,9 +521,45 @@ macro_rules! field_arithmetic {
                 self.add(self)
             }
 
+            /// Montgomery reduction of the 512-bit little-endian integer `r`,
+            /// returning `r / 2^256 mod p`. `r` must be smaller than `p * 2^256`,
+            /// which holds for the product of two field elements' limbs, so
+            /// wide products computed elsewhere can be reduced with the
+            /// constants of this field.
+            #[cfg(feature = "small")]
+            #[inline]
+            pub const fn montgomery_reduce(r: &[u64; 8]) -> $field {
+                $field($crate::arithmetic::montgomery_reduce_loop(
+                    r,
+                    &$modulus.0,
+                    $inv,
+                ))
+            }
+
             /// Squares this element.
+            #[cfg(not(feature = "op-count"))]
             #[inline]
             pub const fn square(&self) -> $field {
+                self.square_inner()
+            }
+
+            /// Squares this element.
+            #[cfg(feature = "op-count")]
+            #[inline]
+            pub fn square(&self) -> $field {
+                $crate::stats::record_square();
+                self.square_inner()
+            }
+
+            #[cfg(feature = "small")]
+            #[inline]
+            const fn square_inner(&self) -> $field {
+                self.mul_inner(self)
+            }
+
+            #[cfg(not(feature = "small"))]
+            #[inline(always)]
+            const fn square_inner(&self) -> $field {
                 let (r1, carry) = mac(0, self.0[0], self.0[1], 0);
                 let (r2, carry) = mac(0, self.0[0], self.0[2], carry);
                 let (r3, r4) = mac(0, self.0[0], self.0[3], carry);
@@ -379,8 +590,34 @@ macro_rules! field_arithmetic {
             }
 
             /// Multiplies `rhs` by `self`, returning the result.
+            #[cfg(not(feature = "op-count"))]
             #[inline]
             pub const fn mul(&self, rhs: &Self) -> $field {
+                self.mul_inner(rhs)
+            }
+
+            /// Multiplies `rhs` by `self`, returning the result.
+            #[cfg(feature = "op-count")]
+            #[inline]
+            pub fn mul(&self, rhs: &Self) -> $field {
+                $crate::stats::record_mul();
+                self.mul_inner(rhs)
+            }
+
+            #[cfg(feature = "small")]
+            #[inline]
+            const fn mul_inner(&self, rhs: &Self) -> $field {
+                $field($crate::arithmetic::montgomery_mul_loop(
+                    &self.0,
+                    &rhs.0,
+                    &$modulus.0,
+                    $inv,
+                ))
+            }
+
+            #[cfg(not(feature = "small"))]
+            #[inline(always)]
+            const fn mul_inner(&self, rhs: &Self) -> $field {
                 // Schoolbook multiplication
 
                 let (r0, carry) = mac(0, self.0[0], rhs.0[0], 0);
@@ -463,8 +700,14 @@ macro_rules! field_specific {
                 (&$field([d0, d1, d2, d3])).sub(&$modulus)
             }
 
+            /// Montgomery reduction of the 512-bit little-endian integer `r`,
+            /// returning `r / 2^256 mod p`. `r` must be smaller than `p * 2^256`,
+            /// which holds for the product of two field elements' limbs, so
+            /// wide products computed elsewhere can be reduced with the
+            /// constants of this field.
+            #[cfg(not(feature = "small"))]
             #[inline(always)]
-            pub(crate) const fn montgomery_reduce(r: &[u64; 8]) -> $field {
+            pub const fn montgomery_reduce(r: &[u64; 8]) -> $field {
                 // The Montgomery reduction here is based on Algorithm 14.32 in
                 // Handbook of Applied Cryptography
                 // <http://cacr.uwaterloo.ca/hac/about/chap14.pdf>.
@@ -528,8 +771,14 @@ macro_rules! field_specific {
                 $field([d0, d1, d2, d3])
             }
 
+            /// Montgomery reduction of the 512-bit little-endian integer `r`,
+            /// returning `r / 2^256 mod p`. `r` must be smaller than `p * 2^256`,
+            /// which holds for the product of two field elements' limbs, so
+            /// wide products computed elsewhere can be reduced with the
+            /// constants of this field.
+            #[cfg(not(feature = "small"))]
             #[inline(always)]
-            pub(crate) const fn montgomery_reduce(r: &[u64; 8]) -> Self {
+            pub const fn montgomery_reduce(r: &[u64; 8]) -> Self {
                 // The Montgomery reduction here is based on Algorithm 14.32 in
                 // Handbook of Applied Cryptography
                 // <http://cacr.uwaterloo.ca/hac/about/chap14.pdf>.
@@ -1,6 +1,8 @@
 #[macro_use]
 pub mod curve;
 #[macro_use]
+pub mod edwards;
+#[macro_use]
 pub mod field;
 
 #[macro_export]
@@ -0,0 +1,322 @@
+//! Points and scalars whose curve is chosen at runtime.
+//!
+//! `DynPoint` and `DynScalar` are enums over the curves of this crate, and
+//! `DynCurve` is an object-safe interface to a curve. Applications that pick
+//! the curve at runtime can use these without monomorphizing every code path
+//! over `CurveExt`. Operations mixing values from different curves return
+//! `None`. Baby Jubjub points are those of its prime-order subgroup.
+
+use crate::ff::{Field, PrimeField};
+use crate::group::{Group, GroupEncoding};
+use crate::id::CurveId;
+use crate::{babyjubjub, bn256, grumpkin, pasta, secp256k1, secp256r1, secq256k1};
+use rand_core::RngCore;
+
+/// Copies `bytes` into a default `Repr`, failing if the lengths differ.
+fn repr_from_slice<R: Default + AsMut<[u8]>>(bytes: &[u8]) -> Option<R> {
+    let mut repr = R::default();
+    if repr.as_mut().len() != bytes.len() {
+        return None;
+    }
+    repr.as_mut().copy_from_slice(bytes);
+    Some(repr)
+}
+
+macro_rules! dyn_curves {
+    ($($variant:ident: $curve:ty, $scalar:ty;)*) => {
+        /// A point on one of the curves of this crate.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        pub enum DynPoint {
+            $($variant($curve),)*
+        }
+
+        /// A scalar of one of the curves of this crate.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        pub enum DynScalar {
+            $($variant($scalar),)*
+        }
+
+        impl DynPoint {
+            /// Returns the curve this point belongs to.
+            pub fn curve_id(&self) -> CurveId {
+                match self {
+                    $(DynPoint::$variant(_) => CurveId::$variant,)*
+                }
+            }
+
+            /// Returns the generator of the curve `id`.
+            pub fn generator(id: CurveId) -> DynPoint {
+                match id {
+                    $(CurveId::$variant => DynPoint::$variant(<$curve>::generator()),)*
+                }
+            }
+
+            /// Returns the identity of the curve `id`.
+            pub fn identity(id: CurveId) -> DynPoint {
+                match id {
+                    $(CurveId::$variant => DynPoint::$variant(<$curve>::identity()),)*
+                }
+            }
+
+            /// Returns a uniformly random point of the curve `id`.
+            pub fn random(id: CurveId, rng: &mut dyn RngCore) -> DynPoint {
+                match id {
+                    $(CurveId::$variant => DynPoint::$variant(<$curve>::random(&mut *rng)),)*
+                }
+            }
+
+            /// Decodes a point of the curve `id` from its `GroupEncoding`.
+            pub fn from_bytes(id: CurveId, bytes: &[u8]) -> Option<DynPoint> {
+                match id {
+                    $(CurveId::$variant => {
+                        let repr = repr_from_slice::<<$curve as GroupEncoding>::Repr>(bytes)?;
+                        Option::from(<$curve>::from_bytes(&repr)).map(DynPoint::$variant)
+                    })*
+                }
+            }
+
+            /// Encodes this point with its `GroupEncoding`.
+            pub fn to_bytes(&self) -> Vec<u8> {
+                match self {
+                    $(DynPoint::$variant(p) => p.to_bytes().as_ref().to_vec(),)*
+                }
+            }
+
+            pub fn is_identity(&self) -> bool {
+                match self {
+                    $(DynPoint::$variant(p) => bool::from(p.is_identity()),)*
+                }
+            }
+
+            pub fn add(&self, other: &DynPoint) -> Option<DynPoint> {
+                match (self, other) {
+                    $((DynPoint::$variant(a), DynPoint::$variant(b)) => Some(DynPoint::$variant(a + b)),)*
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+
+            pub fn sub(&self, other: &DynPoint) -> Option<DynPoint> {
+                match (self, other) {
+                    $((DynPoint::$variant(a), DynPoint::$variant(b)) => Some(DynPoint::$variant(a - b)),)*
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+
+            pub fn neg(&self) -> DynPoint {
+                match self {
+                    $(DynPoint::$variant(p) => DynPoint::$variant(-*p),)*
+                }
+            }
+
+            pub fn double(&self) -> DynPoint {
+                match self {
+                    $(DynPoint::$variant(p) => DynPoint::$variant(p.double()),)*
+                }
+            }
+
+            pub fn mul(&self, scalar: &DynScalar) -> Option<DynPoint> {
+                match (self, scalar) {
+                    $((DynPoint::$variant(p), DynScalar::$variant(s)) => Some(DynPoint::$variant(p * s)),)*
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+        }
+
+        impl DynScalar {
+            /// Returns the curve this scalar belongs to.
+            pub fn curve_id(&self) -> CurveId {
+                match self {
+                    $(DynScalar::$variant(_) => CurveId::$variant,)*
+                }
+            }
+
+            pub fn zero(id: CurveId) -> DynScalar {
+                match id {
+                    $(CurveId::$variant => DynScalar::$variant(<$scalar>::ZERO),)*
+                }
+            }
+
+            pub fn one(id: CurveId) -> DynScalar {
+                match id {
+                    $(CurveId::$variant => DynScalar::$variant(<$scalar>::ONE),)*
+                }
+            }
+
+            pub fn from_u64(id: CurveId, value: u64) -> DynScalar {
+                match id {
+                    $(CurveId::$variant => DynScalar::$variant(<$scalar>::from(value)),)*
+                }
+            }
+
+            /// Returns a uniformly random scalar of the curve `id`.
+            pub fn random(id: CurveId, rng: &mut dyn RngCore) -> DynScalar {
+                match id {
+                    $(CurveId::$variant => DynScalar::$variant(<$scalar>::random(&mut *rng)),)*
+                }
+            }
+
+            /// Decodes a scalar of the curve `id` from its canonical `PrimeField`
+            /// representation.
+            pub fn from_bytes(id: CurveId, bytes: &[u8]) -> Option<DynScalar> {
+                match id {
+                    $(CurveId::$variant => {
+                        let repr = repr_from_slice::<<$scalar as PrimeField>::Repr>(bytes)?;
+                        Option::from(<$scalar>::from_repr(repr)).map(DynScalar::$variant)
+                    })*
+                }
+            }
+
+            /// Encodes this scalar with its `PrimeField` representation.
+            pub fn to_bytes(&self) -> Vec<u8> {
+                match self {
+                    $(DynScalar::$variant(s) => s.to_repr().as_ref().to_vec(),)*
+                }
+            }
+
+            pub fn is_zero(&self) -> bool {
+                match self {
+                    $(DynScalar::$variant(s) => bool::from(s.is_zero()),)*
+                }
+            }
+
+            pub fn add(&self, other: &DynScalar) -> Option<DynScalar> {
+                match (self, other) {
+                    $((DynScalar::$variant(a), DynScalar::$variant(b)) => Some(DynScalar::$variant(*a + b)),)*
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+
+            pub fn sub(&self, other: &DynScalar) -> Option<DynScalar> {
+                match (self, other) {
+                    $((DynScalar::$variant(a), DynScalar::$variant(b)) => Some(DynScalar::$variant(*a - b)),)*
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+
+            pub fn mul(&self, other: &DynScalar) -> Option<DynScalar> {
+                match (self, other) {
+                    $((DynScalar::$variant(a), DynScalar::$variant(b)) => Some(DynScalar::$variant(*a * b)),)*
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+
+            pub fn neg(&self) -> DynScalar {
+                match self {
+                    $(DynScalar::$variant(s) => DynScalar::$variant(-*s),)*
+                }
+            }
+
+            /// Returns the inverse of this scalar, or `None` if it is zero.
+            pub fn invert(&self) -> Option<DynScalar> {
+                match self {
+                    $(DynScalar::$variant(s) => Option::from(s.invert()).map(DynScalar::$variant),)*
+                }
+            }
+        }
+    };
+}
+
+dyn_curves!(
+    Bn256G1: bn256::G1, bn256::Fr;
+    Bn256G2: bn256::G2, bn256::Fr;
+    Grumpkin: grumpkin::G1, grumpkin::Fq;
+    Secp256k1: secp256k1::Secp256k1, secp256k1::Fq;
+    Secp256r1: secp256r1::Secp256r1, secp256r1::Fq;
+    Secq256k1: secq256k1::Secq256k1, secq256k1::Fq;
+    Pallas: pasta::Ep, pasta::Fq;
+    Vesta: pasta::Eq, pasta::Fp;
+    BabyJubjub: babyjubjub::BabyJubjubSubgroup, babyjubjub::Fr;
+);
+
+/// An object-safe interface to one of the curves of this crate.
+pub trait DynCurve {
+    fn id(&self) -> CurveId;
+
+    fn generator(&self) -> DynPoint {
+        DynPoint::generator(self.id())
+    }
+
+    fn identity(&self) -> DynPoint {
+        DynPoint::identity(self.id())
+    }
+
+    fn random_point(&self, rng: &mut dyn RngCore) -> DynPoint {
+        DynPoint::random(self.id(), rng)
+    }
+
+    fn random_scalar(&self, rng: &mut dyn RngCore) -> DynScalar {
+        DynScalar::random(self.id(), rng)
+    }
+
+    fn point_from_bytes(&self, bytes: &[u8]) -> Option<DynPoint> {
+        DynPoint::from_bytes(self.id(), bytes)
+    }
+
+    fn scalar_from_bytes(&self, bytes: &[u8]) -> Option<DynScalar> {
+        DynScalar::from_bytes(self.id(), bytes)
+    }
+}
+
+impl DynCurve for CurveId {
+    fn id(&self) -> CurveId {
+        *self
+    }
+}
+
+/// Returns the curve `id` as a trait object.
+pub fn dyn_curve(id: CurveId) -> Box<dyn DynCurve> {
+    Box::new(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dyn_curve, DynPoint, DynScalar};
+    use crate::bn256;
+    use crate::group::Group;
+    use crate::id::CurveId;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_dyn_curves() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for id in CurveId::ALL {
+            let curve = dyn_curve(id);
+            let g = curve.generator();
+            assert_eq!(g.curve_id(), id);
+            assert!(curve.identity().is_identity());
+
+            let a = curve.random_scalar(&mut rng);
+            let b = curve.random_scalar(&mut rng);
+            let ab = a.add(&b).unwrap();
+            assert_eq!(
+                g.mul(&ab).unwrap(),
+                g.mul(&a).unwrap().add(&g.mul(&b).unwrap()).unwrap()
+            );
+            assert_eq!(g.mul(&DynScalar::from_u64(id, 2)).unwrap(), g.double());
+            assert!(g.add(&g.neg()).unwrap().is_identity());
+            assert_eq!(a.mul(&a.invert().unwrap()).unwrap(), DynScalar::one(id));
+            assert!(a.sub(&a).unwrap().is_zero());
+
+            let p = curve.random_point(&mut rng);
+            assert_eq!(curve.point_from_bytes(&p.to_bytes()), Some(p));
+            assert_eq!(curve.scalar_from_bytes(&a.to_bytes()), Some(a));
+            assert_eq!(curve.point_from_bytes(&[]), None);
+        }
+
+        let p = DynPoint::Bn256G1(bn256::G1::generator());
+        let q = DynPoint::generator(CurveId::Secp256k1);
+        assert_eq!(p.add(&q), None);
+        assert_eq!(p.mul(&DynScalar::one(CurveId::Secp256k1)), None);
+    }
+}
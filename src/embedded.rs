@@ -0,0 +1,112 @@
+//! Curves embedded in the scalar field of another curve.
+//!
+//! A curve whose base field is the scalar field of an outer curve has a group
+//! law that can be computed natively in circuits over the outer curve. The
+//! pairs of this crate are the cycles Grumpkin with BN254, secq256k1 with
+//! secp256k1, and Vesta with Pallas, and the twisted Edwards curve Baby Jubjub
+//! embedded in BN254. secp256r1 has no embedded curve here.
+
+use crate::{
+    babyjubjub, bn256, grumpkin, pasta, secp256k1, secq256k1, Coordinates, CurveAffine, CurveExt,
+};
+use group::{Curve, Group};
+
+/// A curve whose base field is the scalar field of `Outer`.
+pub trait EmbeddedCurve<Outer: CurveExt>: Group {
+    /// Returns the affine coordinates of this point, or `None` for the point
+    /// at infinity of a Weierstrass curve.
+    fn affine_coordinates(&self) -> Option<(Outer::ScalarExt, Outer::ScalarExt)>;
+}
+
+/// A curve with a designated embedded curve, so that generic circuit code can
+/// select the embedded curve of the curve it is proving over. The designated
+/// curves are the short Weierstrass ones of the cycles.
+pub trait OuterCurve: CurveExt {
+    type Embedded: EmbeddedCurve<Self> + CurveExt<Base = Self::ScalarExt>;
+}
+
+macro_rules! impl_embedded_curve {
+    ($embedded:ty, $outer:ty) => {
+        impl EmbeddedCurve<$outer> for $embedded {
+            fn affine_coordinates(
+                &self,
+            ) -> Option<(
+                <$outer as CurveExt>::ScalarExt,
+                <$outer as CurveExt>::ScalarExt,
+            )> {
+                Option::<Coordinates<_>>::from(self.to_affine().coordinates())
+                    .map(|c| (*c.x(), *c.y()))
+            }
+        }
+
+        impl OuterCurve for $outer {
+            type Embedded = $embedded;
+        }
+    };
+}
+
+impl_embedded_curve!(grumpkin::G1, bn256::G1);
+impl_embedded_curve!(bn256::G1, grumpkin::G1);
+impl_embedded_curve!(secq256k1::Secq256k1, secp256k1::Secp256k1);
+impl_embedded_curve!(secp256k1::Secp256k1, secq256k1::Secq256k1);
+impl_embedded_curve!(pasta::Eq, pasta::Ep);
+impl_embedded_curve!(pasta::Ep, pasta::Eq);
+
+impl EmbeddedCurve<bn256::G1> for babyjubjub::BabyJubjub {
+    fn affine_coordinates(&self) -> Option<(bn256::Fr, bn256::Fr)> {
+        let p = self.to_affine();
+        Some((p.x, p.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EmbeddedCurve, OuterCurve};
+    use crate::{babyjubjub, bn256, grumpkin, pasta, secp256k1, secq256k1, CurveExt};
+    use ff::{Field, PrimeField};
+    use group::Group;
+
+    fn embedded_curve_test<C: OuterCurve>() {
+        // The base field of the embedded curve and the scalar field of the
+        // outer curve have the same modulus.
+        assert_eq!(
+            <<C::Embedded as CurveExt>::Base as PrimeField>::MODULUS,
+            <C::ScalarExt as PrimeField>::MODULUS
+        );
+
+        // The group law of the embedded curve holds in the outer scalar field.
+        let point =
+            <C::Embedded as Group>::generator() * <C::Embedded as CurveExt>::ScalarExt::from(7);
+        let (x, y): (C::ScalarExt, C::ScalarExt) = point.affine_coordinates().unwrap();
+        assert_eq!(
+            <C::Embedded as Group>::identity().affine_coordinates(),
+            None
+        );
+        assert_eq!(
+            y.square(),
+            x.square() * x + <C::Embedded as CurveExt>::a() * x + <C::Embedded as CurveExt>::b()
+        );
+    }
+
+    #[test]
+    fn test_embedded_curves() {
+        embedded_curve_test::<bn256::G1>();
+        embedded_curve_test::<grumpkin::G1>();
+        embedded_curve_test::<secp256k1::Secp256k1>();
+        embedded_curve_test::<secq256k1::Secq256k1>();
+        embedded_curve_test::<pasta::Ep>();
+        embedded_curve_test::<pasta::Eq>();
+
+        assert_eq!(
+            <babyjubjub::Fq as PrimeField>::MODULUS,
+            <bn256::Fr as PrimeField>::MODULUS
+        );
+        let point = babyjubjub::BabyJubjub::generator() * babyjubjub::Fr::from(7);
+        let (x, y) = EmbeddedCurve::<bn256::G1>::affine_coordinates(&point).unwrap();
+        let (x2, y2) = (x.square(), y.square());
+        assert_eq!(
+            babyjubjub::BabyJubjubAffine::a() * x2 + y2,
+            bn256::Fr::ONE + babyjubjub::BabyJubjubAffine::d() * x2 * y2
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Fixed-base scalar multiplication with precomputed tables.
+//!
+//! The scalar is recoded into signed radix-16 digits in `[-8, 8)`, and for
+//! every digit position `i` the table holds `j * 16^i * P` for `j` in
+//! `1..=8`. A multiplication is then one constant-time table lookup and one
+//! mixed addition per digit, with no doublings.
+
+use crate::arithmetic::parallelize_with;
+use crate::serde::{read_table_header, write_table_header, SerdeObject};
+use crate::{CurveAffine, CurveExt};
+use ff::PrimeField;
+use group::{Curve, Group};
+use std::io::{self, Read, Write};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+/// Precomputed multiples of a fixed base point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixedBaseTable<C: CurveAffine> {
+    windows: Vec<[C; 8]>,
+}
+
+/// Recodes the little-endian `bytes` into signed radix-16 digits in
+/// `[-8, 8)`, with one extra digit for the final carry.
+fn recode_signed_radix16(bytes: &[u8]) -> Vec<i8> {
+    let mut digits = Vec::with_capacity(2 * bytes.len() + 1);
+    for byte in bytes {
+        digits.push((byte & 0xf) as i8);
+        digits.push((byte >> 4) as i8);
+    }
+    digits.push(0);
+
+    let mut carry = 0;
+    for digit in digits.iter_mut() {
+        *digit += carry;
+        carry = (*digit + 8) >> 4;
+        *digit -= carry << 4;
+    }
+    debug_assert_eq!(carry, 0);
+    digits
+}
+
+impl<C: CurveAffine> FixedBaseTable<C> {
+    /// Precomputes the table for `base`.
+    pub fn new(base: &C) -> Self {
+        let num_windows = 2 * C::ScalarExt::default().to_repr().as_ref().len() + 1;
+
+        let mut points = Vec::with_capacity(8 * num_windows);
+        let mut window_base = base.to_curve();
+        for _ in 0..num_windows {
+            let mut acc = window_base;
+            for _ in 0..8 {
+                points.push(acc);
+                acc += window_base;
+            }
+            window_base = window_base.double().double().double().double();
+        }
+
+        let mut affine = vec![C::identity(); points.len()];
+        <C::CurveExt as Curve>::batch_normalize(&points, &mut affine);
+
+        let windows = affine
+            .chunks(8)
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect();
+        FixedBaseTable { windows }
+    }
+
+    /// Precomputes the tables for all of `bases`, in parallel with at most
+    /// `num_threads` threads, or one thread per core if `None`.
+    pub fn new_batch(bases: &[C], num_threads: Option<usize>) -> Vec<Self> {
+        let mut tables = vec![None; bases.len()];
+        parallelize_with(num_threads, &mut tables, |tables, start| {
+            for (table, base) in tables.iter_mut().zip(bases[start..].iter()) {
+                *table = Some(Self::new(base));
+            }
+        });
+        tables.into_iter().map(Option::unwrap).collect()
+    }
+
+    /// Precomputes the table for the generator of the curve.
+    pub fn generator() -> Self {
+        Self::new(&C::generator())
+    }
+
+    /// Returns `scalar * base` in constant time.
+    pub fn mul(&self, scalar: &C::ScalarExt) -> C::CurveExt {
+        let digits = recode_signed_radix16(scalar.to_repr().as_ref());
+        debug_assert_eq!(digits.len(), self.windows.len());
+
+        let mut acc = <C::CurveExt as Group>::identity();
+        for (window, digit) in self.windows.iter().zip(digits) {
+            let negative = Choice::from((digit as u8) >> 7);
+            let abs = digit.unsigned_abs();
+
+            let mut point = C::identity();
+            for (j, entry) in window.iter().enumerate() {
+                point.conditional_assign(entry, abs.ct_eq(&(j as u8 + 1)));
+            }
+            let point = C::conditional_select(&point, &-point, negative);
+
+            acc += point;
+        }
+        acc
+    }
+}
+
+impl<C: CurveAffine + SerdeObject> FixedBaseTable<C> {
+    /// The version of the format written by `write_raw`, increased whenever
+    /// the format or the contents of the table change.
+    pub const FORMAT_VERSION: u16 = 1;
+
+    const TAG: [u8; 4] = *b"FBT\0";
+
+    /// Writes the table in the raw format of `SerdeObject`, preceded by a
+    /// header naming the table type, the format version and the curve, and
+    /// by the number of windows as a little-endian `u32`.
+    pub fn write_raw<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_table_header(
+            writer,
+            &Self::TAG,
+            Self::FORMAT_VERSION,
+            C::CurveExt::CURVE_ID,
+        )?;
+        writer.write_all(&(self.windows.len() as u32).to_le_bytes())?;
+        for point in self.windows.iter().flatten() {
+            point.write_raw(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a table written by `write_raw`, checking the header and that
+    /// every point is on the curve.
+    pub fn read_raw<R: Read>(reader: &mut R) -> io::Result<Self> {
+        read_table_header(
+            reader,
+            &Self::TAG,
+            Self::FORMAT_VERSION,
+            C::CurveExt::CURVE_ID,
+        )?;
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let num_windows = u32::from_le_bytes(len) as usize;
+        if num_windows != 2 * C::ScalarExt::default().to_repr().as_ref().len() + 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid number of windows",
+            ));
+        }
+
+        let windows = (0..num_windows)
+            .map(|_| {
+                let mut window = [C::identity(); 8];
+                for point in window.iter_mut() {
+                    *point = C::read_raw(reader)?;
+                    if !bool::from(point.is_on_curve()) {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "point is not on the curve",
+                        ));
+                    }
+                }
+                Ok(window)
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(FixedBaseTable { windows })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{recode_signed_radix16, FixedBaseTable};
+    use crate::bn256::{Fr, G1Affine, G1};
+    use crate::group::{prime::PrimeCurveAffine, Group};
+    use crate::grumpkin;
+    use crate::secp256k1::{Fq, Secp256k1Affine};
+    use crate::CurveAffine;
+    use ff::{Field, PrimeField};
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn fixed_base_test<C: CurveAffine>(rng: &mut XorShiftRng) {
+        let table = FixedBaseTable::<C>::generator();
+        let g = C::generator();
+        for s in [C::ScalarExt::ZERO, C::ScalarExt::ONE, -C::ScalarExt::ONE] {
+            assert_eq!(table.mul(&s), g * s);
+        }
+        for _ in 0..100 {
+            let s = C::ScalarExt::random(&mut *rng);
+            assert_eq!(table.mul(&s), g * s);
+        }
+    }
+
+    #[test]
+    fn test_recode() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for _ in 0..100 {
+            let s = Fr::random(&mut rng);
+            let digits = recode_signed_radix16(s.to_repr().as_ref());
+            assert!(digits.iter().all(|d| (-8..8).contains(d)));
+            let recomposed = digits.iter().rev().fold(Fr::ZERO, |acc, d| {
+                let d = if *d < 0 {
+                    -Fr::from(d.unsigned_abs() as u64)
+                } else {
+                    Fr::from(*d as u64)
+                };
+                acc * Fr::from(16) + d
+            });
+            assert_eq!(recomposed, s);
+        }
+    }
+
+    #[test]
+    fn test_fixed_base() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        fixed_base_test::<G1Affine>(&mut rng);
+        fixed_base_test::<Secp256k1Affine>(&mut rng);
+
+        let base = (G1::generator() * Fr::random(&mut rng)).into();
+        let table = FixedBaseTable::<G1Affine>::new(&base);
+        let s = Fr::random(&mut rng);
+        assert_eq!(table.mul(&s), base * s);
+        assert_eq!(
+            FixedBaseTable::<Secp256k1Affine>::new(&Secp256k1Affine::identity()).mul(&Fq::ONE),
+            Secp256k1Affine::identity().to_curve()
+        );
+    }
+
+    #[test]
+    fn test_new_batch() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let bases: Vec<G1Affine> = (0..5)
+            .map(|_| (G1::generator() * Fr::random(&mut rng)).into())
+            .collect();
+        let expected: Vec<_> = bases.iter().map(FixedBaseTable::new).collect();
+        for num_threads in [None, Some(1), Some(3)] {
+            assert_eq!(FixedBaseTable::new_batch(&bases, num_threads), expected);
+        }
+    }
+
+    #[test]
+    fn test_serialization() {
+        let table = FixedBaseTable::<G1Affine>::generator();
+        let mut buf = vec![];
+        table.write_raw(&mut buf).unwrap();
+        assert_eq!(
+            FixedBaseTable::<G1Affine>::read_raw(&mut &buf[..]).unwrap(),
+            table
+        );
+        assert!(FixedBaseTable::<G1Affine>::read_raw(&mut &buf[..buf.len() - 1]).is_err());
+
+        // Tables of another curve or another format version are rejected.
+        assert!(FixedBaseTable::<grumpkin::G1Affine>::read_raw(&mut &buf[..]).is_err());
+        let mut old = buf.clone();
+        old[4] ^= 1;
+        assert!(FixedBaseTable::<G1Affine>::read_raw(&mut &old[..]).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! A thread-safe cache of the generators derived by `derive_generators` and
+//! of their fixed-base tables, so that test suites and long-running servers
+//! hash to the curve only once per generator.
+
+use crate::arithmetic::parallelize;
+use crate::fixed_base::FixedBaseTable;
+use crate::hash_to_curve::{generator_domain, GeneratorIndexError};
+use crate::CurveExt;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+type Entry = Arc<dyn Any + Send + Sync>;
+
+/// Generators keyed by `(curve, domain)` and tables keyed by
+/// `(curve, domain, index)`.
+#[derive(Default)]
+pub struct GeneratorCache {
+    generators: Mutex<HashMap<(&'static str, String), Entry>>,
+    tables: Mutex<HashMap<(&'static str, String, usize), Entry>>,
+}
+
+/// Derives the generators with indices `start..end`, in parallel.
+fn derive_generator_range<C: CurveExt>(
+    domain: &str,
+    start: usize,
+    end: usize,
+) -> Result<Vec<C::AffineExt>, GeneratorIndexError> {
+    if let Some(last) = end.checked_sub(1) {
+        generator_domain(domain, last)?;
+    }
+    let domains = (start..end)
+        .map(|i| generator_domain(domain, i))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut points = vec![C::identity(); domains.len()];
+    parallelize(&mut points, |chunk, offset| {
+        for (point, domain) in chunk.iter_mut().zip(&domains[offset..]) {
+            *point = C::hash_to_curve(domain)(&[]);
+        }
+    });
+    let mut generators = vec![C::AffineExt::identity(); points.len()];
+    C::batch_normalize(&points, &mut generators);
+    Ok(generators)
+}
+
+impl GeneratorCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cache shared by the whole process.
+    pub fn global() -> &'static GeneratorCache {
+        lazy_static::lazy_static! {
+            static ref GLOBAL: GeneratorCache = GeneratorCache::new();
+        }
+        &GLOBAL
+    }
+
+    /// Returns the first `n` generators of `domain`, i.e.
+    /// `derive_generators::<C>(domain, n)`, deriving the ones that are not
+    /// cached yet. The lock is not held while hashing, so concurrent callers
+    /// may occasionally derive the same generators twice.
+    pub fn generators<C: CurveExt>(
+        &self,
+        domain: &str,
+        n: usize,
+    ) -> Result<Vec<C::AffineExt>, GeneratorIndexError> {
+        let key = (C::CURVE_ID, domain.to_string());
+        let cached = self.cached_generators::<C>(&key);
+        if cached.len() >= n {
+            return Ok(cached[..n].to_vec());
+        }
+
+        let mut generators = cached.to_vec();
+        generators.extend(derive_generator_range::<C>(domain, cached.len(), n)?);
+
+        let mut entries = self.generators.lock().unwrap();
+        let longest = entries
+            .get(&key)
+            .map(|entry| entry.clone().downcast::<Vec<C::AffineExt>>().unwrap().len())
+            .unwrap_or(0);
+        if generators.len() > longest {
+            entries.insert(key, Arc::new(generators.clone()));
+        }
+        Ok(generators)
+    }
+
+    /// Returns the generator of `domain` with the given index.
+    pub fn generator<C: CurveExt>(
+        &self,
+        domain: &str,
+        index: usize,
+    ) -> Result<C::AffineExt, GeneratorIndexError> {
+        let n = index.checked_add(1).ok_or(GeneratorIndexError(index))?;
+        Ok(self.generators::<C>(domain, n)?[index])
+    }
+
+    /// Returns the fixed-base table of the generator of `domain` with the given
+    /// index, precomputing it if it is not cached yet.
+    pub fn table<C: CurveExt>(
+        &self,
+        domain: &str,
+        index: usize,
+    ) -> Result<Arc<FixedBaseTable<C::AffineExt>>, GeneratorIndexError> {
+        let key = (C::CURVE_ID, domain.to_string(), index);
+        if let Some(entry) = self.tables.lock().unwrap().get(&key) {
+            return Ok(entry.clone().downcast().unwrap());
+        }
+
+        let table = Arc::new(FixedBaseTable::new(&self.generator::<C>(domain, index)?));
+        Ok(self
+            .tables
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| table.clone() as Entry)
+            .clone()
+            .downcast()
+            .unwrap())
+    }
+
+    fn cached_generators<C: CurveExt>(
+        &self,
+        key: &(&'static str, String),
+    ) -> Arc<Vec<C::AffineExt>> {
+        self.generators
+            .lock()
+            .unwrap()
+            .get(key)
+            .map(|entry| entry.clone().downcast().unwrap())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeneratorCache;
+    use crate::bn256::{Fr, G1};
+    use crate::hash_to_curve::derive_generators;
+    use crate::hash_to_curve::GeneratorIndexError;
+    use crate::secp256k1::Secp256k1;
+    use ff::Field;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_generator_cache() {
+        let cache = GeneratorCache::new();
+
+        let expected = derive_generators::<G1>("cache", 40).unwrap();
+        assert_eq!(cache.generators::<G1>("cache", 10).unwrap(), expected[..10]);
+        assert_eq!(cache.generators::<G1>("cache", 40).unwrap(), expected);
+        assert_eq!(cache.generators::<G1>("cache", 5).unwrap(), expected[..5]);
+        assert_eq!(cache.generator::<G1>("cache", 17).unwrap(), expected[17]);
+        assert_eq!(
+            cache.generators::<Secp256k1>("cache", 3).unwrap(),
+            derive_generators::<Secp256k1>("cache", 3).unwrap()
+        );
+        assert_ne!(
+            cache.generator::<G1>("other", 0).unwrap(),
+            cache.generator::<G1>("cache", 0).unwrap()
+        );
+
+        let table = cache.table::<G1>("cache", 3).unwrap();
+        assert!(Arc::ptr_eq(&table, &cache.table::<G1>("cache", 3).unwrap()));
+        assert_eq!(table.mul(&Fr::ONE), G1::from(expected[3]));
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let generators = GeneratorCache::global()
+                        .generators::<G1>("cache", 40)
+                        .unwrap();
+                    assert_eq!(generators, expected);
+                });
+            }
+        });
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_generator_index_overflow() {
+        let cache = GeneratorCache::new();
+        let index = u32::MAX as usize + 1;
+        assert_eq!(
+            cache.generator::<G1>("cache", index),
+            Err(GeneratorIndexError(index))
+        );
+        assert_eq!(
+            cache.generator::<G1>("cache", usize::MAX),
+            Err(GeneratorIndexError(usize::MAX))
+        );
+        assert!(cache.table::<G1>("cache", index).is_err());
+    }
+}
@@ -0,0 +1,860 @@
+//! A short Weierstrass curve `y^2 = x^3 + a x + b` whose coefficients are
+//! chosen at runtime, along with Montgomery and twisted Edwards curves and the
+//! birational maps between the three forms. Montgomery curves also have the
+//! x-only ladder used by X25519 and X448.
+//!
+//! This is a simple, variable time, affine implementation intended for
+//! research and testing, e.g. to cross-check the curves generated by
+//! `new_curve_impl!` against an independent implementation. It should not be
+//! used with secret data. The ladder is the exception: its control flow and
+//! memory accesses do not depend on the bits of the scalar.
+
+use crate::ff::{Field, PrimeField};
+use subtle::{Choice, ConditionallySelectable};
+
+/// Returns `n` as an element of `F`, which need not be a prime field.
+fn small<F: Field>(n: u64) -> F {
+    (0..64).rev().fold(F::ZERO, |acc, i| {
+        let acc = acc.double();
+        if (n >> i) & 1 == 1 {
+            acc + F::ONE
+        } else {
+            acc
+        }
+    })
+}
+
+/// A point on a [`GenericCurve`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GenericPoint<F> {
+    Identity,
+    Affine(F, F),
+}
+
+impl<F: Field> GenericPoint<F> {
+    /// Returns the affine coordinates of this point, or `None` for the
+    /// identity.
+    pub fn coordinates(&self) -> Option<(F, F)> {
+        match self {
+            GenericPoint::Identity => None,
+            GenericPoint::Affine(x, y) => Some((*x, *y)),
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        matches!(self, GenericPoint::Identity)
+    }
+}
+
+/// The short Weierstrass curve `y^2 = x^3 + a x + b` over `F`. The field must
+/// not have characteristic two or three, where not every curve has this form
+/// and the addition formulas divide by zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GenericCurve<F> {
+    a: F,
+    b: F,
+}
+
+impl<F: Field> GenericCurve<F> {
+    /// Returns the curve with coefficients `a` and `b`, or `None` if the curve
+    /// is singular, i.e. `4 a^3 + 27 b^2 = 0`, or if `F` has characteristic two
+    /// or three.
+    pub fn new(a: F, b: F) -> Option<Self> {
+        let discriminant = a.square() * a * small::<F>(4) + b.square() * small::<F>(27);
+        if bool::from(discriminant.is_zero() | small::<F>(6).is_zero()) {
+            None
+        } else {
+            Some(GenericCurve { a, b })
+        }
+    }
+
+    pub fn a(&self) -> F {
+        self.a
+    }
+
+    pub fn b(&self) -> F {
+        self.b
+    }
+
+    /// Returns the quadratic twist `y^2 = x^3 + a d^2 x + b d^3` of this curve by
+    /// `d`. The twist is isomorphic to this curve over `F` unless `d` is a
+    /// non-square.
+    pub fn quadratic_twist(&self, d: &F) -> Self {
+        let d2 = d.square();
+        GenericCurve {
+            a: self.a * d2,
+            b: self.b * d2 * d,
+        }
+    }
+
+    /// Returns `x^3 + a x + b`.
+    fn rhs(&self, x: &F) -> F {
+        (x.square() + self.a) * x + self.b
+    }
+
+    /// Returns the point `(x, y)` if it is on the curve.
+    pub fn point(&self, x: F, y: F) -> Option<GenericPoint<F>> {
+        let p = GenericPoint::Affine(x, y);
+        self.is_on_curve(&p).then(|| p)
+    }
+
+    /// Returns a point with x-coordinate `x` if there is one.
+    pub fn lift_x(&self, x: F) -> Option<GenericPoint<F>> {
+        Option::<F>::from(self.rhs(&x).sqrt()).map(|y| GenericPoint::Affine(x, y))
+    }
+
+    pub fn is_on_curve(&self, p: &GenericPoint<F>) -> bool {
+        match p {
+            GenericPoint::Identity => true,
+            GenericPoint::Affine(x, y) => y.square() == self.rhs(x),
+        }
+    }
+
+    pub fn neg(&self, p: &GenericPoint<F>) -> GenericPoint<F> {
+        match p {
+            GenericPoint::Identity => GenericPoint::Identity,
+            GenericPoint::Affine(x, y) => GenericPoint::Affine(*x, -*y),
+        }
+    }
+
+    pub fn double(&self, p: &GenericPoint<F>) -> GenericPoint<F> {
+        match p {
+            GenericPoint::Identity => GenericPoint::Identity,
+            GenericPoint::Affine(x, y) => match Option::<F>::from(y.double().invert()) {
+                // Points with y = 0 have order two.
+                None => GenericPoint::Identity,
+                Some(inv) => {
+                    let lambda = (x.square() * small::<F>(3) + self.a) * inv;
+                    let x3 = lambda.square() - x.double();
+                    let y3 = lambda * (*x - x3) - y;
+                    GenericPoint::Affine(x3, y3)
+                }
+            },
+        }
+    }
+
+    pub fn add(&self, p: &GenericPoint<F>, q: &GenericPoint<F>) -> GenericPoint<F> {
+        match (p, q) {
+            (GenericPoint::Identity, _) => *q,
+            (_, GenericPoint::Identity) => *p,
+            (GenericPoint::Affine(x1, y1), GenericPoint::Affine(x2, y2)) => {
+                if x1 == x2 {
+                    if y1 == y2 {
+                        self.double(p)
+                    } else {
+                        GenericPoint::Identity
+                    }
+                } else {
+                    let lambda = (*y2 - y1) * (*x2 - x1).invert().unwrap();
+                    let x3 = lambda.square() - x1 - x2;
+                    let y3 = lambda * (*x1 - x3) - y1;
+                    GenericPoint::Affine(x3, y3)
+                }
+            }
+        }
+    }
+
+    /// Multiplies `p` by the integer whose little-endian bytes are `scalar`.
+    pub fn mul_by_bytes(&self, p: &GenericPoint<F>, scalar: &[u8]) -> GenericPoint<F> {
+        let mut acc = GenericPoint::Identity;
+        for byte in scalar.iter().rev() {
+            for i in (0..8).rev() {
+                acc = self.double(&acc);
+                if (byte >> i) & 1 == 1 {
+                    acc = self.add(&acc, p);
+                }
+            }
+        }
+        acc
+    }
+
+    /// Multiplies `p` by `scalar`, whose representation is assumed to be little
+    /// endian as for every field in this crate.
+    pub fn mul<S: PrimeField>(&self, p: &GenericPoint<F>, scalar: &S) -> GenericPoint<F> {
+        self.mul_by_bytes(p, scalar.to_repr().as_ref())
+    }
+
+    /// Returns the order of `p` by repeated addition, giving up after `bound`
+    /// additions. Only feasible for small orders.
+    pub fn point_order(&self, p: &GenericPoint<F>, bound: u64) -> Option<u64> {
+        let mut acc = *p;
+        for order in 1..=bound {
+            if acc.is_identity() {
+                return Some(order);
+            }
+            acc = self.add(&acc, p);
+        }
+        None
+    }
+}
+
+impl<F: PrimeField> GenericCurve<F> {
+    /// Enumerates every point of the curve, starting with the identity.
+    ///
+    /// The x-coordinates are enumerated by counting up from zero, so this is
+    /// only feasible for small fields.
+    pub fn points(&self) -> Vec<GenericPoint<F>> {
+        let mut points = vec![GenericPoint::Identity];
+        let mut x = F::ZERO;
+        loop {
+            if let Some(GenericPoint::Affine(px, py)) = self.lift_x(x) {
+                points.push(GenericPoint::Affine(px, py));
+                if !bool::from(py.is_zero()) {
+                    points.push(GenericPoint::Affine(px, -py));
+                }
+            }
+            x += F::ONE;
+            if bool::from(x.is_zero()) {
+                break;
+            }
+        }
+        points
+    }
+
+    /// Returns the number of points on the curve, including the identity.
+    /// Only feasible for small fields, see [`GenericCurve::points`].
+    pub fn count_points(&self) -> u64 {
+        let mut count = 1;
+        let mut x = F::ZERO;
+        loop {
+            let rhs = self.rhs(&x);
+            if bool::from(rhs.is_zero()) {
+                count += 1;
+            } else if bool::from(rhs.sqrt().is_some()) {
+                count += 2;
+            }
+            x += F::ONE;
+            if bool::from(x.is_zero()) {
+                break;
+            }
+        }
+        count
+    }
+}
+
+/// The Montgomery curve `B v^2 = u^3 + A u^2 + u` over `F`. Points are
+/// represented with `GenericPoint`, using `(u, v)` as coordinates. The field
+/// must not have characteristic two or three, which the maps and the ladder
+/// divide by.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MontgomeryCurve<F> {
+    a: F,
+    b: F,
+}
+
+impl<F: Field> MontgomeryCurve<F> {
+    /// Returns the curve with coefficients `A` and `B`, or `None` if
+    /// `B (A^2 - 4) = 0` or if `F` has characteristic two or three.
+    pub fn new(a: F, b: F) -> Option<Self> {
+        let singular = (b * (a.square() - small::<F>(4))).is_zero();
+        if bool::from(singular | small::<F>(6).is_zero()) {
+            None
+        } else {
+            Some(MontgomeryCurve { a, b })
+        }
+    }
+
+    pub fn a(&self) -> F {
+        self.a
+    }
+
+    pub fn b(&self) -> F {
+        self.b
+    }
+
+    pub fn is_on_curve(&self, p: &GenericPoint<F>) -> bool {
+        match p {
+            GenericPoint::Identity => true,
+            GenericPoint::Affine(u, v) => {
+                self.b * v.square() == (u.square() + self.a * u + F::ONE) * u
+            }
+        }
+    }
+
+    /// Returns the short Weierstrass curve birationally equivalent to this
+    /// curve, `y^2 = x^3 + a x + b` with `a = (3 - A^2) / (3 B^2)` and
+    /// `b = (2 A^3 - 9 A) / (27 B^3)`.
+    pub fn weierstrass_curve(&self) -> GenericCurve<F> {
+        let b2 = self.b.square();
+        let a = (small::<F>(3) - self.a.square()) * (small::<F>(3) * b2).invert().unwrap();
+        let b = (self.a.square() * self.a.double() - small::<F>(9) * self.a)
+            * (small::<F>(27) * b2 * self.b).invert().unwrap();
+        GenericCurve::new(a, b).unwrap()
+    }
+
+    /// Maps a point of this curve to `weierstrass_curve`, sending `(u, v)`
+    /// to `(u / B + A / (3 B), v / B)`.
+    pub fn map_to_weierstrass(&self, p: &GenericPoint<F>) -> GenericPoint<F> {
+        match p {
+            GenericPoint::Identity => GenericPoint::Identity,
+            GenericPoint::Affine(u, v) => {
+                let b_inv = self.b.invert().unwrap();
+                let shift = self.a * (small::<F>(3) * self.b).invert().unwrap();
+                GenericPoint::Affine(*u * b_inv + shift, *v * b_inv)
+            }
+        }
+    }
+
+    /// Inverse of `map_to_weierstrass`.
+    pub fn map_from_weierstrass(&self, p: &GenericPoint<F>) -> GenericPoint<F> {
+        match p {
+            GenericPoint::Identity => GenericPoint::Identity,
+            GenericPoint::Affine(x, y) => {
+                let shift = self.a * small::<F>(3).invert().unwrap();
+                GenericPoint::Affine(self.b * x - shift, self.b * y)
+            }
+        }
+    }
+
+    pub fn neg(&self, p: &GenericPoint<F>) -> GenericPoint<F> {
+        match p {
+            GenericPoint::Identity => GenericPoint::Identity,
+            GenericPoint::Affine(u, v) => GenericPoint::Affine(*u, -*v),
+        }
+    }
+
+    pub fn double(&self, p: &GenericPoint<F>) -> GenericPoint<F> {
+        match p {
+            GenericPoint::Identity => GenericPoint::Identity,
+            GenericPoint::Affine(u, v) => match Option::<F>::from((self.b * v).double().invert()) {
+                // Points with v = 0 have order two.
+                None => GenericPoint::Identity,
+                Some(inv) => {
+                    let lambda = (u.square() * small::<F>(3) + self.a * u.double() + F::ONE) * inv;
+                    let u3 = self.b * lambda.square() - self.a - u.double();
+                    let v3 = lambda * (*u - u3) - v;
+                    GenericPoint::Affine(u3, v3)
+                }
+            },
+        }
+    }
+
+    /// Adds two points with the affine Montgomery addition law.
+    pub fn add(&self, p: &GenericPoint<F>, q: &GenericPoint<F>) -> GenericPoint<F> {
+        match (p, q) {
+            (GenericPoint::Identity, _) => *q,
+            (_, GenericPoint::Identity) => *p,
+            (GenericPoint::Affine(u1, v1), GenericPoint::Affine(u2, v2)) => {
+                if u1 == u2 {
+                    if v1 == v2 {
+                        self.double(p)
+                    } else {
+                        GenericPoint::Identity
+                    }
+                } else {
+                    let lambda = (*v2 - v1) * (*u2 - u1).invert().unwrap();
+                    let u3 = self.b * lambda.square() - self.a - u1 - u2;
+                    let v3 = lambda * (*u1 - u3) - v1;
+                    GenericPoint::Affine(u3, v3)
+                }
+            }
+        }
+    }
+
+    /// Multiplies `p` by the integer whose little-endian bytes are `scalar`.
+    pub fn mul_by_bytes(&self, p: &GenericPoint<F>, scalar: &[u8]) -> GenericPoint<F> {
+        let mut acc = GenericPoint::Identity;
+        for byte in scalar.iter().rev() {
+            for i in (0..8).rev() {
+                acc = self.double(&acc);
+                if (byte >> i) & 1 == 1 {
+                    acc = self.add(&acc, p);
+                }
+            }
+        }
+        acc
+    }
+
+    /// Doubles the point with projective u-coordinate `X / Z`, returning the
+    /// projective u-coordinate of the result. The point at infinity is
+    /// `(X, 0)`.
+    pub fn x_double(&self, p: &(F, F)) -> (F, F) {
+        let (x, z) = *p;
+        let a24 = (self.a + small::<F>(2)) * small::<F>(4).invert().unwrap();
+        let t1 = (x + z).square();
+        let t2 = (x - z).square();
+        let t3 = t1 - t2;
+        (t1 * t2, t3 * (t2 + a24 * t3))
+    }
+
+    /// Returns the projective u-coordinate of `P + Q` given those of `P`, `Q`
+    /// and `P - Q`, which is the differential addition of the ladder.
+    pub fn x_add(&self, p: &(F, F), q: &(F, F), diff: &(F, F)) -> (F, F) {
+        let u = (p.0 - p.1) * (q.0 + q.1);
+        let v = (p.0 + p.1) * (q.0 - q.1);
+        (diff.1 * (u + v).square(), diff.0 * (u - v).square())
+    }
+
+    /// Multiplies the point with u-coordinate `u` by the integer whose
+    /// little-endian bytes are `scalar` with the Montgomery ladder, returning
+    /// the u-coordinate of the result, or `None` for the point at infinity.
+    ///
+    /// The ladder only needs `A`, so `u` may also be the u-coordinate of a
+    /// point on the quadratic twist. The differential additions degenerate for
+    /// the point of order two `u = 0`, for which the ladder always returns
+    /// `None`.
+    ///
+    /// As in RFC 7748, every bit costs one differential addition and one
+    /// doubling, and the two accumulators are exchanged with
+    /// `conditional_swap` instead of branching on the bit. The running time
+    /// depends on the length of `scalar` but not on its value.
+    pub fn ladder(&self, u: &F, scalar: &[u8]) -> Option<F> {
+        let base = (*u, F::ONE);
+        let mut r0 = (F::ONE, F::ZERO);
+        let mut r1 = base;
+        let mut swap = Choice::from(0);
+        for byte in scalar.iter().rev() {
+            for i in (0..8).rev() {
+                let bit = Choice::from((byte >> i) & 1);
+                swap ^= bit;
+                F::conditional_swap(&mut r0.0, &mut r1.0, swap);
+                F::conditional_swap(&mut r0.1, &mut r1.1, swap);
+                swap = bit;
+                r1 = self.x_add(&r0, &r1, &base);
+                r0 = self.x_double(&r0);
+            }
+        }
+        F::conditional_swap(&mut r0.0, &mut r1.0, swap);
+        F::conditional_swap(&mut r0.1, &mut r1.1, swap);
+        Option::<F>::from(r0.1.invert()).map(|inv| r0.0 * inv)
+    }
+}
+
+/// The twisted Edwards curve `a x^2 + y^2 = 1 + d x^2 y^2` over `F`, with `a` a
+/// square and `d` a non-square so that the addition law is complete. Points
+/// are affine `(x, y)` pairs, the identity being `(0, 1)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EdwardsCurve<F> {
+    a: F,
+    d: F,
+}
+
+impl<F: Field> EdwardsCurve<F> {
+    /// Returns the curve with coefficients `a` and `d`, or `None` if the curve
+    /// is singular, i.e. `a d (a - d) = 0`, if its addition law is not
+    /// complete, or if `F` has characteristic two or three.
+    pub fn new(a: F, d: F) -> Option<Self> {
+        let singular = (a * d * (a - d)).is_zero();
+        let complete = a.sqrt().is_some() & !d.sqrt().is_some();
+        if bool::from(singular | !complete | small::<F>(6).is_zero()) {
+            None
+        } else {
+            Some(EdwardsCurve { a, d })
+        }
+    }
+
+    pub fn a(&self) -> F {
+        self.a
+    }
+
+    pub fn d(&self) -> F {
+        self.d
+    }
+
+    pub fn identity(&self) -> (F, F) {
+        (F::ZERO, F::ONE)
+    }
+
+    pub fn is_on_curve(&self, p: &(F, F)) -> bool {
+        let (x2, y2) = (p.0.square(), p.1.square());
+        self.a * x2 + y2 == F::ONE + self.d * x2 * y2
+    }
+
+    /// Returns a point with y-coordinate `y` if there is one.
+    pub fn lift_y(&self, y: F) -> Option<(F, F)> {
+        let y2 = y.square();
+        let x2 = Option::<F>::from((self.a - self.d * y2).invert())? * (F::ONE - y2);
+        Option::<F>::from(x2.sqrt()).map(|x| (x, y))
+    }
+
+    /// Adds two points with the unified twisted Edwards addition law, whose
+    /// denominators do not vanish on the curve since it is complete.
+    pub fn add(&self, p: &(F, F), q: &(F, F)) -> (F, F) {
+        let (x1, y1) = *p;
+        let (x2, y2) = *q;
+        let t = self.d * x1 * x2 * y1 * y2;
+        let x3 = (x1 * y2 + y1 * x2) * (F::ONE + t).invert().unwrap();
+        let y3 = (y1 * y2 - self.a * x1 * x2) * (F::ONE - t).invert().unwrap();
+        (x3, y3)
+    }
+
+    /// Returns the Montgomery curve birationally equivalent to this curve,
+    /// with `A = 2 (a + d) / (a - d)` and `B = 4 / (a - d)`.
+    pub fn montgomery_curve(&self) -> MontgomeryCurve<F> {
+        let inv = (self.a - self.d).invert().unwrap();
+        MontgomeryCurve::new((self.a + self.d).double() * inv, small::<F>(4) * inv).unwrap()
+    }
+
+    /// Maps a point of this curve to `montgomery_curve`, sending `(x, y)` to
+    /// `((1 + y) / (1 - y), (1 + y) / ((1 - y) x))`. The identity maps to the
+    /// point at infinity and `(0, -1)` to `(0, 0)`.
+    pub fn map_to_montgomery(&self, p: &(F, F)) -> GenericPoint<F> {
+        let (x, y) = *p;
+        if y == F::ONE {
+            return GenericPoint::Identity;
+        }
+        if bool::from(x.is_zero()) {
+            return GenericPoint::Affine(F::ZERO, F::ZERO);
+        }
+        let u = (F::ONE + y) * (F::ONE - y).invert().unwrap();
+        GenericPoint::Affine(u, u * x.invert().unwrap())
+    }
+
+    /// Inverse of `map_to_montgomery`, sending `(u, v)` to `(u / v, (u - 1) / (u + 1))`.
+    /// No point has `u = -1`, since `v^2 = d` there.
+    pub fn map_from_montgomery(&self, p: &GenericPoint<F>) -> (F, F) {
+        match p {
+            GenericPoint::Identity => self.identity(),
+            GenericPoint::Affine(u, v) => {
+                if bool::from(v.is_zero()) {
+                    (F::ZERO, -F::ONE)
+                } else {
+                    let x = *u * v.invert().unwrap();
+                    let y = (*u - F::ONE) * (*u + F::ONE).invert().unwrap();
+                    (x, y)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EdwardsCurve, GenericCurve, GenericPoint, MontgomeryCurve};
+    use crate::ff::{Field, PrimeField};
+    use crate::group::{Curve, Group};
+    use crate::secp256k1::{Fp, Fq, Secp256k1};
+    use crate::{impl_binops_additive, impl_binops_multiplicative, impl_sum_prod};
+    use crate::{Coordinates, CurveAffine, CurveExt};
+    use core::ops::{Add, Mul, Neg, Sub};
+    use rand_core::RngCore;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+    /// The field of 103 elements, small enough to enumerate.
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    struct F103(u64);
+
+    const P: u64 = 103;
+
+    impl ConstantTimeEq for F103 {
+        fn ct_eq(&self, other: &Self) -> Choice {
+            self.0.ct_eq(&other.0)
+        }
+    }
+
+    impl ConditionallySelectable for F103 {
+        fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+            F103(u64::conditional_select(&a.0, &b.0, choice))
+        }
+    }
+
+    impl Neg for F103 {
+        type Output = F103;
+
+        fn neg(self) -> F103 {
+            F103((P - self.0) % P)
+        }
+    }
+
+    impl<'a, 'b> Add<&'b F103> for &'a F103 {
+        type Output = F103;
+
+        fn add(self, rhs: &'b F103) -> F103 {
+            F103((self.0 + rhs.0) % P)
+        }
+    }
+
+    impl<'a, 'b> Sub<&'b F103> for &'a F103 {
+        type Output = F103;
+
+        fn sub(self, rhs: &'b F103) -> F103 {
+            F103((self.0 + P - rhs.0) % P)
+        }
+    }
+
+    impl<'a, 'b> Mul<&'b F103> for &'a F103 {
+        type Output = F103;
+
+        fn mul(self, rhs: &'b F103) -> F103 {
+            F103(self.0 * rhs.0 % P)
+        }
+    }
+
+    impl_binops_additive!(F103, F103);
+    impl_binops_multiplicative!(F103, F103);
+    impl_sum_prod!(F103);
+
+    impl From<u64> for F103 {
+        fn from(n: u64) -> F103 {
+            F103(n % P)
+        }
+    }
+
+    impl Field for F103 {
+        const ZERO: Self = F103(0);
+        const ONE: Self = F103(1);
+
+        fn random(mut rng: impl RngCore) -> Self {
+            F103(rng.next_u64() % P)
+        }
+
+        fn square(&self) -> Self {
+            self * self
+        }
+
+        fn double(&self) -> Self {
+            self + self
+        }
+
+        fn invert(&self) -> CtOption<Self> {
+            CtOption::new(self.pow_vartime([P - 2]), !self.is_zero())
+        }
+
+        fn sqrt(&self) -> CtOption<Self> {
+            // P = 3 mod 4.
+            let root = self.pow_vartime([(P + 1) / 4]);
+            CtOption::new(root, root.square().ct_eq(self))
+        }
+
+        fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+            ff::helpers::sqrt_ratio_generic(num, div)
+        }
+    }
+
+    impl PrimeField for F103 {
+        type Repr = [u8; 1];
+
+        const MODULUS: &'static str = "0x67";
+        const NUM_BITS: u32 = 7;
+        const CAPACITY: u32 = 6;
+        const TWO_INV: Self = F103(52);
+        const MULTIPLICATIVE_GENERATOR: Self = F103(5);
+        const S: u32 = 1;
+        const ROOT_OF_UNITY: Self = F103(P - 1);
+        const ROOT_OF_UNITY_INV: Self = F103(P - 1);
+        const DELTA: Self = F103(25);
+
+        fn from_repr(repr: [u8; 1]) -> CtOption<Self> {
+            let n = u64::from(repr[0]);
+            CtOption::new(F103(n), Choice::from((n < P) as u8))
+        }
+
+        fn to_repr(&self) -> [u8; 1] {
+            [self.0 as u8]
+        }
+
+        fn is_odd(&self) -> Choice {
+            Choice::from((self.0 & 1) as u8)
+        }
+    }
+
+    #[test]
+    fn test_small_curve() {
+        // y^2 = x^3 + 7 over F_103 has 111 = 3 * 37 points.
+        let curve = GenericCurve::new(F103::ZERO, F103::from(7)).unwrap();
+        assert_eq!(curve.count_points(), 111);
+
+        let points = curve.points();
+        assert_eq!(points.len(), 111);
+        assert_eq!(points[0], GenericPoint::Identity);
+        assert!(points.iter().all(|p| curve.is_on_curve(p)));
+        for (i, p) in points.iter().enumerate() {
+            assert!(!points[i + 1..].contains(p));
+        }
+
+        // (0, 25) has order 3, and (1, 27) generates the group.
+        let p = curve.point(F103::ZERO, F103::from(25)).unwrap();
+        assert_eq!(curve.point_order(&p, 200), Some(3));
+        let g = curve.point(F103::ONE, F103::from(27)).unwrap();
+        assert_eq!(curve.point_order(&g, 200), Some(111));
+        assert_eq!(curve.point_order(&g, 110), None);
+        assert_eq!(curve.point_order(&GenericPoint::Identity, 1), Some(1));
+        for p in points.iter() {
+            assert_eq!(111 % curve.point_order(p, 111).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn test_degenerate_parameters() {
+        assert!(GenericCurve::new(F103::ZERO, F103::ZERO).is_none());
+        // x^3 - 3 x + 2 = (x - 1)^2 (x + 2) has a double root.
+        assert!(GenericCurve::new(-F103::from(3), F103::from(2)).is_none());
+        assert!(GenericCurve::new(F103::ZERO, F103::from(7)).is_some());
+
+        assert!(MontgomeryCurve::new(F103::from(3), F103::ONE).is_some());
+        assert!(EdwardsCurve::new(F103::ONE, F103::from(5)).is_some());
+
+        // 5 generates the multiplicative group, so it is not a square.
+        assert!(EdwardsCurve::new(F103::ONE, F103::from(4)).is_none());
+        assert!(EdwardsCurve::new(F103::from(5), F103::from(10)).is_none());
+        assert!(EdwardsCurve::new(F103::ONE, F103::ONE).is_none());
+        assert!(MontgomeryCurve::new(F103::from(2), F103::ONE).is_none());
+        assert!(MontgomeryCurve::new(F103::from(3), F103::ZERO).is_none());
+
+        // Every pair of points of a complete curve adds without exception.
+        let edwards = EdwardsCurve::new(F103::ONE, F103::from(5)).unwrap();
+        let montgomery = edwards.montgomery_curve();
+        let points: Vec<_> = (0..P).filter_map(|y| edwards.lift_y(F103(y))).collect();
+        for p in points.iter() {
+            let m = edwards.map_to_montgomery(p);
+            assert!(montgomery.is_on_curve(&m));
+            assert_eq!(edwards.map_from_montgomery(&m), *p);
+            for q in points.iter() {
+                assert!(edwards.is_on_curve(&edwards.add(p, q)));
+            }
+        }
+    }
+
+    fn from_affine<C: CurveAffine>(p: &C) -> GenericPoint<C::Base> {
+        let coordinates: Option<Coordinates<C>> = p.coordinates().into();
+        coordinates
+            .map(|c| GenericPoint::Affine(*c.x(), *c.y()))
+            .unwrap_or(GenericPoint::Identity)
+    }
+
+    #[test]
+    fn test_singular() {
+        assert!(GenericCurve::new(Fp::ZERO, Fp::ZERO).is_none());
+        assert!(GenericCurve::new(Fp::ZERO, Fp::from(7)).is_some());
+    }
+
+    #[test]
+    fn test_against_secp256k1() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let curve = GenericCurve::new(Secp256k1::a(), Secp256k1::b()).unwrap();
+
+        for _ in 0..10 {
+            let p = Secp256k1::random(&mut rng);
+            let q = Secp256k1::random(&mut rng);
+            let s = Fq::random(&mut rng);
+
+            let gp = from_affine(&p.to_affine());
+            let gq = from_affine(&q.to_affine());
+            assert!(curve.is_on_curve(&gp));
+
+            assert_eq!(curve.add(&gp, &gq), from_affine(&(p + q).to_affine()));
+            assert_eq!(curve.double(&gp), from_affine(&p.double().to_affine()));
+            assert_eq!(curve.neg(&gp), from_affine(&(-p).to_affine()));
+            assert_eq!(curve.mul(&gp, &s), from_affine(&(p * s).to_affine()));
+            assert!(curve.add(&gp, &curve.neg(&gp)).is_identity());
+        }
+    }
+
+    #[test]
+    fn test_baby_jubjub_forms() {
+        use crate::bn256::Fr;
+        use crate::tests::fe_from_str;
+
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // Baby Jubjub, see https://eips.ethereum.org/EIPS/eip-2494
+        let edwards = EdwardsCurve::new(Fr::from(168700), Fr::from(168696)).unwrap();
+        let montgomery = edwards.montgomery_curve();
+        assert_eq!(montgomery.a(), Fr::from(168698));
+        assert_eq!(montgomery.b(), Fr::ONE);
+        let weierstrass = montgomery.weierstrass_curve();
+
+        let base8: (Fr, Fr) = (
+            fe_from_str(
+                "5299619240641551281634865583518297030282874472190772894086521144482721001553",
+            ),
+            fe_from_str(
+                "16950150798460657717958625567821834550301663161624707787222815936182638968203",
+            ),
+        );
+        assert!(edwards.is_on_curve(&base8));
+
+        let mut points = vec![edwards.identity(), (Fr::ZERO, -Fr::ONE), base8];
+        points.extend(
+            std::iter::repeat_with(|| edwards.lift_y(Fr::random(&mut rng)))
+                .flatten()
+                .take(10),
+        );
+
+        for p in points.iter() {
+            let m = edwards.map_to_montgomery(p);
+            assert!(montgomery.is_on_curve(&m));
+            assert_eq!(edwards.map_from_montgomery(&m), *p);
+
+            let w = montgomery.map_to_weierstrass(&m);
+            assert!(weierstrass.is_on_curve(&w));
+            assert_eq!(montgomery.map_from_weierstrass(&w), m);
+
+            for q in points.iter() {
+                let sum = edwards.add(p, q);
+                assert!(edwards.is_on_curve(&sum));
+                assert_eq!(
+                    edwards.map_to_montgomery(&sum),
+                    montgomery.add(&m, &edwards.map_to_montgomery(q))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_montgomery_ladder() {
+        use crate::bn256::Fr;
+
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // The Montgomery form of Baby Jubjub.
+        let montgomery = MontgomeryCurve::new(Fr::from(168698), Fr::ONE).unwrap();
+        let weierstrass = montgomery.weierstrass_curve();
+
+        let points: Vec<_> = std::iter::repeat_with(|| {
+            let w = weierstrass.lift_x(Fr::random(&mut rng))?;
+            Some(montgomery.map_from_weierstrass(&w))
+        })
+        .flatten()
+        .take(10)
+        .collect();
+
+        for p in points.iter() {
+            assert!(montgomery.is_on_curve(p));
+            let wp = montgomery.map_to_weierstrass(p);
+            assert_eq!(
+                montgomery.map_to_weierstrass(&montgomery.double(p)),
+                weierstrass.double(&wp)
+            );
+            for q in points.iter() {
+                assert_eq!(
+                    montgomery.map_to_weierstrass(&montgomery.add(p, q)),
+                    weierstrass.add(&wp, &montgomery.map_to_weierstrass(q))
+                );
+            }
+            assert!(montgomery.add(p, &montgomery.neg(p)).is_identity());
+
+            let (u, _) = p.coordinates().unwrap();
+            for scalar in [
+                &[1u8][..],
+                &[2][..],
+                &[3][..],
+                &[0x5d, 0x76, 0x3d, 0x31, 0x8d][..],
+            ] {
+                let expected = montgomery.mul_by_bytes(p, scalar);
+                assert_eq!(
+                    montgomery.ladder(&u, scalar),
+                    expected.coordinates().map(|(u, _)| u)
+                );
+            }
+        }
+
+        // (0, 0) has order two, and is the only point with u = 0.
+        let order_two = GenericPoint::Affine(Fr::ZERO, Fr::ZERO);
+        assert!(montgomery.is_on_curve(&order_two));
+        assert!(montgomery.double(&order_two).is_identity());
+        assert_eq!(montgomery.ladder(&Fr::ZERO, &[1]), None);
+    }
+}
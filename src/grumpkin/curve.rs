@@ -69,6 +69,17 @@ const ENDO_PARAMS_GRUMPKIN: EndoParameters = EndoParameters {
 
 endo!(G1, Fr, ENDO_PARAMS_GRUMPKIN);
 
+/// Returns the constants of Grumpkin and of its fields.
+pub fn params() -> crate::params::CurveParams {
+    crate::params::CurveParams::of::<G1>(Some(&ENDO_PARAMS_GRUMPKIN))
+}
+
+impl G1 {
+    /// The cofactor of the prime-order subgroup, as little-endian 64-bit
+    /// limbs.
+    pub const COFACTOR: [u64; 4] = [1, 0, 0, 0];
+}
+
 impl group::cofactor::CofactorGroup for G1 {
     type Subgroup = G1;
 
@@ -105,6 +116,8 @@ mod tests {
     #[test]
     fn test_curve() {
         crate::tests::curve::curve_tests::<G1>();
+        crate::tests::curve::batch_add_test::<G1>();
+        crate::tests::curve::group_traits_test::<G1>();
     }
 
     #[test]
@@ -1,4 +1,6 @@
 mod curve;
 
+// Grumpkin and BN254 form a cycle: each base field is the other scalar field,
+// so the BN254 field types are reused under swapped names.
 pub use crate::bn256::{Fq as Fr, Fr as Fq};
 pub use curve::*;
@@ -1,26 +1,30 @@
 #![allow(clippy::op_ref)]
 
+use core::fmt;
 use ff::{Field, FromUniformBytes, PrimeField};
 use pasta_curves::arithmetic::CurveExt;
 use static_assertions::const_assert;
 use subtle::{ConditionallySelectable, ConstantTimeEq};
 
-/// Hashes over a message and writes the output to all of `buf`.
+/// Hashes over a message and writes the output to all of `buf`, which holds
+/// one or two elements.
 /// Modified from https://github.com/zcash/pasta_curves/blob/7e3fc6a4919f6462a32b79dd226cb2587b7961eb/src/hashtocurve.rs#L11.
+///
+/// A domain separation tag longer than 255 bytes is first hashed as in
+/// section 5.3.3 of RFC 9380, rather than rejected, so that any domain
+/// prefix is accepted.
 fn hash_to_field<F: FromUniformBytes<64>>(
     method: &str,
     curve_id: &str,
     domain_prefix: &str,
     message: &[u8],
-    buf: &mut [F; 2],
+    buf: &mut [F],
 ) {
-    assert!(domain_prefix.len() < 256);
-    assert!((18 + method.len() + curve_id.len() + domain_prefix.len()) < 256);
-
     // Assume that the field size is 32 bytes and k is 256, where k is defined in
     // <https://www.ietf.org/archive/id/draft-irtf-cfrg-hash-to-curve-10.html#name-security-considerations-3>.
     const CHUNKLEN: usize = 64;
     const_assert!(CHUNKLEN * 2 < 256);
+    debug_assert!(!buf.is_empty() && buf.len() <= 2);
 
     // Input block size of BLAKE2b.
     const R_IN_BYTES: usize = 128;
@@ -31,58 +35,193 @@ fn hash_to_field<F: FromUniformBytes<64>>(
         .personal(&personal)
         .to_state();
 
+    let dst = format!("{domain_prefix}-{curve_id}_XMD:BLAKE2b_{method}_RO_").into_bytes();
+    let dst = if dst.len() > 255 {
+        empty_hasher
+            .clone()
+            .update(b"H2C-OVERSIZE-DST-")
+            .update(&dst)
+            .finalize()
+            .as_bytes()
+            .to_vec()
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
     let b_0 = empty_hasher
         .clone()
         .update(&[0; R_IN_BYTES])
         .update(message)
-        .update(&[0, (CHUNKLEN * 2) as u8, 0])
-        .update(domain_prefix.as_bytes())
-        .update(b"-")
-        .update(curve_id.as_bytes())
-        .update(b"_XMD:BLAKE2b_")
-        .update(method.as_bytes())
-        .update(b"_RO_")
-        .update(&[(18 + method.len() + curve_id.len() + domain_prefix.len()) as u8])
+        .update(&[0, (CHUNKLEN * buf.len()) as u8, 0])
+        .update(&dst)
+        .update(&dst_len)
         .finalize();
 
-    let b_1 = empty_hasher
+    // b_1 = H(b_0 || 1 || DST'), and b_i = H((b_0 xor b_(i-1)) || i || DST').
+    let mut b_i = empty_hasher
         .clone()
         .update(b_0.as_array())
         .update(&[1])
-        .update(domain_prefix.as_bytes())
-        .update(b"-")
-        .update(curve_id.as_bytes())
-        .update(b"_XMD:BLAKE2b_")
-        .update(method.as_bytes())
-        .update(b"_RO_")
-        .update(&[(18 + method.len() + curve_id.len() + domain_prefix.len()) as u8])
+        .update(&dst)
+        .update(&dst_len)
         .finalize();
-
-    let b_2 = {
-        let mut empty_hasher = empty_hasher;
-        for (l, r) in b_0.as_array().iter().zip(b_1.as_array().iter()) {
-            empty_hasher.update(&[*l ^ *r]);
+    for (i, buf) in buf.iter_mut().enumerate() {
+        if i > 0 {
+            let mut hasher = empty_hasher.clone();
+            for (l, r) in b_0.as_array().iter().zip(b_i.as_array().iter()) {
+                hasher.update(&[*l ^ *r]);
+            }
+            b_i = hasher
+                .update(&[i as u8 + 1])
+                .update(&dst)
+                .update(&dst_len)
+                .finalize();
         }
-        empty_hasher
-            .update(&[2])
-            .update(domain_prefix.as_bytes())
-            .update(b"-")
-            .update(curve_id.as_bytes())
-            .update(b"_XMD:BLAKE2b_")
-            .update(method.as_bytes())
-            .update(b"_RO_")
-            .update(&[(18 + method.len() + curve_id.len() + domain_prefix.len()) as u8])
-            .finalize()
-    };
-
-    for (big, buf) in [b_1, b_2].iter().zip(buf.iter_mut()) {
         let mut little = [0u8; CHUNKLEN];
-        little.copy_from_slice(big.as_array());
+        little.copy_from_slice(b_i.as_array());
         little.reverse();
         *buf = F::from_uniform_bytes(&little);
     }
 }
 
+/// Implementation of `expand_message_xmd` with SHA-256 from RFC 9380, writing
+/// `out.len()` uniform bytes derived from `message` under the domain
+/// separation tag `dst`.
+fn expand_message_xmd_sha256(message: &[u8], dst: &[u8], out: &mut [u8]) {
+    use sha2::{Digest, Sha256};
+
+    // Output and input block sizes of SHA-256.
+    const B_IN_BYTES: usize = 32;
+    const S_IN_BYTES: usize = 64;
+
+    // The callers ask for at most 96 bytes.
+    debug_assert!(out.len() <= 255 * B_IN_BYTES);
+
+    // A tag longer than 255 bytes is hashed, as in section 5.3.3 of RFC 9380.
+    let oversize_dst;
+    let dst = if dst.len() > 255 {
+        oversize_dst = Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize();
+        &oversize_dst[..]
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
+    let b_0 = Sha256::new()
+        .chain_update([0u8; S_IN_BYTES])
+        .chain_update(message)
+        .chain_update((out.len() as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+
+    // b_1 = H(b_0 || 1 || DST'), and b_i = H((b_0 xor b_(i-1)) || i || DST').
+    let mut b_i = Sha256::new()
+        .chain_update(b_0)
+        .chain_update([1u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+    for (i, chunk) in out.chunks_mut(B_IN_BYTES).enumerate() {
+        if i > 0 {
+            let mut xor = [0u8; B_IN_BYTES];
+            for (x, (l, r)) in xor.iter_mut().zip(b_0.iter().zip(b_i.iter())) {
+                *x = l ^ r;
+            }
+            b_i = Sha256::new()
+                .chain_update(xor)
+                .chain_update([i as u8 + 1])
+                .chain_update(dst)
+                .chain_update(dst_len)
+                .finalize();
+        }
+        chunk.copy_from_slice(&b_i[..chunk.len()]);
+    }
+}
+
+/// Implementation of `hash_to_field` from RFC 9380 with `expand_message_xmd`
+/// and SHA-256, for fields of at most 256 bits with `L = 48`.
+fn hash_to_field_sha256<F: FromUniformBytes<64>>(message: &[u8], dst: &[u8], buf: &mut [F; 2]) {
+    const L: usize = 48;
+
+    let mut uniform = [0u8; 2 * L];
+    expand_message_xmd_sha256(message, dst, &mut uniform);
+    for (big, buf) in uniform.chunks(L).zip(buf.iter_mut()) {
+        let mut little = [0u8; 64];
+        little[..L].copy_from_slice(big);
+        little[..L].reverse();
+        *buf = F::from_uniform_bytes(&little);
+    }
+}
+
+/// Hashes a message to a uniformly distributed scalar of the curve `C`, using
+/// the same `expand_message_xmd` construction with BLAKE2b as the hash to
+/// curve functions. `domain_prefix` separates independent uses of the hash.
+pub fn hash_to_scalar<C>(message: &[u8], domain_prefix: &str) -> C::ScalarExt
+where
+    C: CurveExt,
+    C::ScalarExt: FromUniformBytes<64>,
+{
+    let mut scalar = [C::ScalarExt::ZERO];
+    hash_to_field("H2S", C::CURVE_ID, domain_prefix, message, &mut scalar);
+    scalar[0]
+}
+
+/// The error returned when a generator index does not fit in a `u32`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GeneratorIndexError(pub usize);
+
+impl fmt::Display for GeneratorIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "generator index {} does not fit in a u32", self.0)
+    }
+}
+
+impl std::error::Error for GeneratorIndexError {}
+
+/// Derives `n` independent generators of `C` with unknown discrete logarithms
+/// to each other. The `i`-th generator is the hash to curve of the empty
+/// message under its own domain, `generator_domain(domain, i)`.
+///
+/// Returns an error if `n` exceeds `u32::MAX + 1`, since the last index would
+/// not fit in its encoding.
+pub fn derive_generators<C: CurveExt>(
+    domain: &str,
+    n: usize,
+) -> Result<Vec<C::AffineExt>, GeneratorIndexError> {
+    if let Some(last) = n.checked_sub(1) {
+        generator_domain(domain, last)?;
+    }
+    let points = (0..n)
+        .map(|i| Ok(C::hash_to_curve(&generator_domain(domain, i)?)(&[])))
+        .collect::<Result<Vec<C>, _>>()?;
+    let mut generators = vec![C::AffineExt::identity(); n];
+    C::batch_normalize(&points, &mut generators);
+    Ok(generators)
+}
+
+/// Returns the domain of the generator with the given index in
+/// `derive_generators`: `domain`, a dash and the index as a `u32` in eight
+/// hexadecimal digits. The fixed width keeps the domains of distinct
+/// `(domain, index)` pairs distinct.
+pub(crate) fn generator_domain(domain: &str, index: usize) -> Result<String, GeneratorIndexError> {
+    let index = u32::try_from(index).map_err(|_| GeneratorIndexError(index))?;
+    Ok(format!("{domain}-{index:08x}"))
+}
+
+/// Maps the field element `u` to the curve with the Shallue-van de Woestijne
+/// method.
+///
+/// The map runs in constant time with respect to `u`: the square roots are
+/// computed with a fixed exponentiation or a fixed number of Tonelli-Shanks
+/// steps, and every branch of the specification is a `CMOV`, implemented with
+/// `conditional_select`. The final `unwrap`s never fail, since `gx` is always
+/// square and `(x, y)` is always on the curve.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn svdw_map_to_curve<C>(
     u: C::Base,
@@ -173,7 +312,51 @@ where
     C::new_jacobian(x, y, one).unwrap()
 }
 
+/// The variable time counterpart of `svdw_map_to_curve`, used by the hash to
+/// curve functions unless the `ct` feature is enabled. It returns the first
+/// candidate `x` that is on the curve, and so computes a single square root
+/// half of the time instead of three.
+#[cfg(not(feature = "ct"))]
+pub(crate) fn svdw_map_to_curve_vartime<C>(
+    u: C::Base,
+    [c1, c2, c3, c4]: [C::Base; 4],
+    z: C::Base,
+) -> C
+where
+    C: CurveExt,
+{
+    let one = C::Base::ONE;
+    let g = |x: C::Base| (x.square() + C::a()) * x + C::b();
+
+    let tv1 = u.square() * c1;
+    let tv2 = one + tv1;
+    let tv1 = one - tv1;
+    let tv3 = (tv1 * tv2).invert().unwrap_or(C::Base::ZERO);
+    let tv4 = u * tv1 * tv3 * c3;
+    let x1 = c2 - tv4;
+    let x2 = c2 + tv4;
+    let x3 = (tv2.square() * tv3).square() * c4 + z;
+
+    let (x, y) = [x1, x2, x3]
+        .into_iter()
+        .find_map(|x| Option::<C::Base>::from(g(x).sqrt()).map(|y| (x, y)))
+        .unwrap();
+    let y = if bool::from(u.is_odd().ct_eq(&y.is_odd())) {
+        y
+    } else {
+        -y
+    };
+    C::new_jacobian(x, y, one).unwrap()
+}
+
 /// Implementation of https://www.ietf.org/id/draft-irtf-cfrg-hash-to-curve-16.html#name-shallue-van-de-woestijne-met
+///
+/// With the `ct` feature, the whole pipeline is constant time with respect to
+/// the message, so it may be used to hash secret inputs. The curves of this
+/// crate that use it have prime order or are hashed directly onto their
+/// `j = 0` model, so there is neither an isogeny nor a cofactor clearing step,
+/// and the two mapped points are combined with the complete addition
+/// formulas. Without it, the faster `svdw_map_to_curve_vartime` is used.
 #[allow(clippy::type_complexity)]
 pub(crate) fn svdw_hash_to_curve<'a, C>(
     curve_id: &'static str,
@@ -190,7 +373,10 @@ where
         let mut us = [C::Base::ZERO; 2];
         hash_to_field("SVDW", curve_id, domain_prefix, message, &mut us);
 
+        #[cfg(feature = "ct")]
         let [q0, q1]: [C; 2] = us.map(|u| svdw_map_to_curve(u, c1, c2, c3, c4, z));
+        #[cfg(not(feature = "ct"))]
+        let [q0, q1]: [C; 2] = us.map(|u| svdw_map_to_curve_vartime(u, [c1, c2, c3, c4], z));
 
         let r = q0 + &q1;
         debug_assert!(bool::from(r.is_on_curve()));
@@ -220,3 +406,221 @@ pub(crate) fn svdw_precomputed_constants<C: CurveExt>(z: C::Base) -> [C::Base; 4
 
     [c1, c2, c3, c4]
 }
+
+/// Maps the field element `u` to the curve with the simplified
+/// Shallue-van de Woestijne-Ulas method, for curves with `A != 0` and
+/// `B != 0`, as in section 6.6.2 of RFC 9380.
+///
+/// Like `svdw_map_to_curve`, the map runs in constant time with respect to
+/// `u`, and the final `unwrap`s never fail.
+pub(crate) fn sswu_map_to_curve<C>(u: C::Base, z: C::Base) -> C
+where
+    C: CurveExt,
+{
+    let one = C::Base::ONE;
+    let a = C::a();
+    let b = C::b();
+
+    // 1. tv1 = inv0(Z^2 * u^4 + Z * u^2)
+    let zu2 = z * u.square();
+    let tv1 = (zu2.square() + zu2).invert();
+    let exceptional = tv1.is_none();
+    let tv1 = tv1.unwrap_or(C::Base::ZERO);
+    // 2. x1 = (-B / A) * (1 + tv1)
+    let a_inv = a.invert().unwrap();
+    let x1 = -b * a_inv * (one + tv1);
+    // 3. If tv1 == 0, set x1 = B / (Z * A)
+    let x1 = C::Base::conditional_select(&x1, &(b * (z * a).invert().unwrap()), exceptional);
+    // 4. gx1 = x1^3 + A * x1 + B
+    let gx1 = (x1.square() + a) * x1 + b;
+    // 5. x2 = Z * u^2 * x1
+    let x2 = zu2 * x1;
+    // 6. gx2 = x2^3 + A * x2 + B
+    let gx2 = (x2.square() + a) * x2 + b;
+    // 7. If is_square(gx1), set x = x1 and y = sqrt(gx1)
+    // 8. Else set x = x2 and y = sqrt(gx2)
+    let y1 = gx1.sqrt();
+    let e1 = y1.is_some();
+    let x = C::Base::conditional_select(&x2, &x1, e1);
+    let y = C::Base::conditional_select(
+        &gx2.sqrt().unwrap_or(C::Base::ZERO),
+        &y1.unwrap_or(C::Base::ZERO),
+        e1,
+    );
+    // 9. If sgn0(u) != sgn0(y), set y = -y
+    let e3 = u.is_odd().ct_eq(&y.is_odd());
+    let y = C::Base::conditional_select(&-y, &y, e3);
+    // 10. return (x, y)
+    C::new_jacobian(x, y, one).unwrap()
+}
+
+/// The variable time counterpart of `sswu_map_to_curve`, used by the hash to
+/// curve functions unless the `ct` feature is enabled. It computes a single
+/// square root half of the time instead of two.
+#[cfg(not(feature = "ct"))]
+pub(crate) fn sswu_map_to_curve_vartime<C>(u: C::Base, z: C::Base) -> C
+where
+    C: CurveExt,
+{
+    let one = C::Base::ONE;
+    let a = C::a();
+    let b = C::b();
+    let g = |x: C::Base| (x.square() + a) * x + b;
+
+    let zu2 = z * u.square();
+    let x1 = match Option::<C::Base>::from((zu2.square() + zu2).invert()) {
+        Some(tv1) => -b * a.invert().unwrap() * (one + tv1),
+        None => b * (z * a).invert().unwrap(),
+    };
+    let x2 = zu2 * x1;
+
+    let (x, y) = [x1, x2]
+        .into_iter()
+        .find_map(|x| Option::<C::Base>::from(g(x).sqrt()).map(|y| (x, y)))
+        .unwrap();
+    let y = if bool::from(u.is_odd().ct_eq(&y.is_odd())) {
+        y
+    } else {
+        -y
+    };
+    C::new_jacobian(x, y, one).unwrap()
+}
+
+/// Implementation of `hash_to_field` from RFC 9380 with `expand_message_xmd`
+/// and SHA-256 for a single element, with `L = 48`, as the `HashToScalar` of
+/// the NIST P-256 ciphersuites of RFC 9497.
+pub(crate) fn hash_to_scalar_sha256<F: FromUniformBytes<64>>(message: &[u8], dst: &[u8]) -> F {
+    const L: usize = 48;
+
+    let mut little = [0u8; 64];
+    expand_message_xmd_sha256(message, dst, &mut little[..L]);
+    little[..L].reverse();
+    F::from_uniform_bytes(&little)
+}
+
+/// Implementation of the `hash_to_curve` encoding of RFC 9380 with
+/// `expand_message_xmd`, SHA-256 and the simplified SWU map `map` under the
+/// domain separation tag `dst`, for the curves of prime order with `A != 0`
+/// and `B != 0`.
+fn sswu_hash_to_curve_sha256_with<C>(message: &[u8], dst: &[u8], map: impl Fn(C::Base) -> C) -> C
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<64>,
+{
+    let mut us = [C::Base::ZERO; 2];
+    hash_to_field_sha256(message, dst, &mut us);
+
+    let [q0, q1]: [C; 2] = us.map(map);
+
+    let r = q0 + &q1;
+    debug_assert!(bool::from(r.is_on_curve()));
+    r
+}
+
+/// `sswu_hash_to_curve_sha256_with` the constant time map, whatever the
+/// features, for the protocols that hash secret inputs such as the OPRF.
+pub(crate) fn sswu_hash_to_curve_sha256_dst<C>(message: &[u8], dst: &[u8], z: C::Base) -> C
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<64>,
+{
+    sswu_hash_to_curve_sha256_with(message, dst, |u| sswu_map_to_curve(u, z))
+}
+
+/// `sswu_hash_to_curve_sha256_with` the domain separation tag
+/// `domain_prefix-with-suite`, so that the prefix `QUUX-V01-CS02` gives the
+/// tags of the RFC test vectors. The map is constant time with the `ct`
+/// feature, and `sswu_map_to_curve_vartime` otherwise.
+#[allow(clippy::type_complexity)]
+pub(crate) fn sswu_hash_to_curve_sha256<'a, C>(
+    suite: &'static str,
+    domain_prefix: &'a str,
+    z: C::Base,
+) -> Box<dyn Fn(&[u8]) -> C + 'a>
+where
+    C: CurveExt,
+    C::Base: FromUniformBytes<64>,
+{
+    let dst = format!("{domain_prefix}-with-{suite}");
+
+    Box::new(move |message| {
+        #[cfg(feature = "ct")]
+        let map = |u| sswu_map_to_curve(u, z);
+        #[cfg(not(feature = "ct"))]
+        let map = |u| sswu_map_to_curve_vartime(u, z);
+        sswu_hash_to_curve_sha256_with(message, dst.as_bytes(), map)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_message_xmd_sha256;
+    use crate::bn256::G1;
+    use crate::CurveExt;
+
+    #[test]
+    #[cfg(not(feature = "ct"))]
+    fn test_vartime_maps() {
+        use super::{
+            sswu_map_to_curve, sswu_map_to_curve_vartime, svdw_map_to_curve,
+            svdw_map_to_curve_vartime, svdw_precomputed_constants,
+        };
+        use crate::bn256::Fq;
+        use crate::secp256r1::{Fp, Secp256r1};
+        use ff::Field;
+        use rand_core::OsRng;
+
+        // The variable time maps give the same points, including on the
+        // exceptional input zero.
+        let z = Fq::ONE;
+        let constants = svdw_precomputed_constants::<G1>(z);
+        let [c1, c2, c3, c4] = constants;
+        for u in (0..100).map(|i| if i == 0 { Fq::ZERO } else { Fq::random(OsRng) }) {
+            assert_eq!(
+                svdw_map_to_curve_vartime::<G1>(u, constants, z),
+                svdw_map_to_curve::<G1>(u, c1, c2, c3, c4, z)
+            );
+        }
+        for u in (0..100).map(|i| if i == 0 { Fp::ZERO } else { Fp::random(OsRng) }) {
+            assert_eq!(
+                sswu_map_to_curve_vartime::<Secp256r1>(u, Secp256r1::SSWU_Z),
+                sswu_map_to_curve::<Secp256r1>(u, Secp256r1::SSWU_Z)
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xmd_long_dst() {
+        // Test vectors of RFC 9380, appendix K.1, for a DST of 256 bytes.
+        let dst = format!(
+            "QUUX-V01-CS02-with-expander-SHA256-128-long-DST-{}",
+            "1".repeat(208)
+        );
+        for (message, expected) in [
+            (
+                "",
+                "e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3",
+            ),
+            (
+                "abc",
+                "52dbf4f36cf560fca57dedec2ad924ee9c266341d8f3d6afe5171733b16bbb12",
+            ),
+        ] {
+            let mut out = [0u8; 32];
+            expand_message_xmd_sha256(message.as_bytes(), dst.as_bytes(), &mut out);
+            let out: String = out.iter().map(|byte| format!("{byte:02x}")).collect();
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn test_long_domain_prefix() {
+        // Domains too long for a DST are hashed rather than rejected, and
+        // still separate the hashes.
+        let domain = "x".repeat(300);
+        let p = G1::hash_to_curve(&domain)(b"message");
+        assert!(bool::from(p.is_on_curve()));
+        assert_eq!(p, G1::hash_to_curve(&domain)(b"message"));
+        assert_ne!(p, G1::hash_to_curve(&"y".repeat(300))(b"message"));
+    }
+}
@@ -0,0 +1,186 @@
+//! Identifiers for the curves and fields of this crate, so that serialized
+//! data and multi-curve registries can record which curve an object belongs
+//! to.
+
+use crate::{bn256, pasta, secp256k1, secp256r1, CurveExt};
+
+/// The curves implemented or re-exported by this crate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CurveId {
+    Bn256G1,
+    Bn256G2,
+    Grumpkin,
+    Secp256k1,
+    Secp256r1,
+    Secq256k1,
+    Pallas,
+    Vesta,
+    BabyJubjub,
+}
+
+impl CurveId {
+    /// Every curve, in declaration order.
+    pub const ALL: [CurveId; 9] = [
+        CurveId::Bn256G1,
+        CurveId::Bn256G2,
+        CurveId::Grumpkin,
+        CurveId::Secp256k1,
+        CurveId::Secp256r1,
+        CurveId::Secq256k1,
+        CurveId::Pallas,
+        CurveId::Vesta,
+        CurveId::BabyJubjub,
+    ];
+
+    /// Returns the name of the curve, which is the `CURVE_ID` of its projective
+    /// type.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            CurveId::Bn256G1 => "bn256_g1",
+            CurveId::Bn256G2 => "bn256_g2",
+            CurveId::Grumpkin => "grumpkin_g1",
+            CurveId::Secp256k1 => "secp256k1",
+            CurveId::Secp256r1 => "secp256r1",
+            CurveId::Secq256k1 => "secq256k1",
+            CurveId::Pallas => "pallas",
+            CurveId::Vesta => "vesta",
+            CurveId::BabyJubjub => "babyjubjub",
+        }
+    }
+
+    /// Returns the ASN.1 object identifier of the curve, if it has one.
+    pub const fn oid(&self) -> Option<&'static str> {
+        match self {
+            // SEC 2, section A.2.1.
+            CurveId::Secp256k1 => Some("1.3.132.0.10"),
+            // RFC 5480, section 2.1.1.1.
+            CurveId::Secp256r1 => Some("1.2.840.10045.3.1.7"),
+            _ => None,
+        }
+    }
+
+    /// Returns the name used for the curve in IETF documents, if it has one.
+    pub const fn ietf_name(&self) -> Option<&'static str> {
+        match self {
+            CurveId::Secp256k1 => Some("secp256k1"),
+            CurveId::Secp256r1 => Some("P-256"),
+            _ => None,
+        }
+    }
+
+    /// Returns the curve with the given name, as returned by `name`.
+    pub fn from_name(name: &str) -> Option<CurveId> {
+        CurveId::ALL.into_iter().find(|id| id.name() == name)
+    }
+
+    /// Returns the curve with the given object identifier.
+    pub fn from_oid(oid: &str) -> Option<CurveId> {
+        CurveId::ALL.into_iter().find(|id| id.oid() == Some(oid))
+    }
+
+    /// Returns the identifier of the curve `C`.
+    pub fn of<C: CurveExt>() -> Option<CurveId> {
+        CurveId::from_name(C::CURVE_ID)
+    }
+}
+
+/// Types with a stable, human readable name.
+pub trait Named {
+    const NAME: &'static str;
+}
+
+macro_rules! impl_named {
+    ($($ty:ty => $name:expr),* $(,)?) => {
+        $(
+            impl Named for $ty {
+                const NAME: &'static str = $name;
+            }
+        )*
+    };
+}
+
+impl_named!(
+    bn256::Fq => "bn256_fq",
+    bn256::Fr => "bn256_fr",
+    bn256::Fq2 => "bn256_fq2",
+    bn256::Fq6 => "bn256_fq6",
+    bn256::Fq12 => "bn256_fq12",
+    secp256k1::Fp => "secp256k1_fp",
+    secp256k1::Fq => "secp256k1_fq",
+    secp256r1::Fp => "secp256r1_fp",
+    secp256r1::Fq => "secp256r1_fq",
+    pasta::Fp => "pasta_fp",
+    pasta::Fq => "pasta_fq",
+    crate::babyjubjub::Fr => "babyjubjub_fr",
+);
+
+macro_rules! impl_named_curve {
+    ($($curve:ty, $affine:ty => $id:ident),* $(,)?) => {
+        $(
+            impl Named for $curve {
+                const NAME: &'static str = CurveId::$id.name();
+            }
+
+            impl Named for $affine {
+                const NAME: &'static str = CurveId::$id.name();
+            }
+        )*
+    };
+}
+
+impl_named_curve!(
+    bn256::G1, bn256::G1Affine => Bn256G1,
+    bn256::G2, bn256::G2Affine => Bn256G2,
+    crate::grumpkin::G1, crate::grumpkin::G1Affine => Grumpkin,
+    secp256k1::Secp256k1, secp256k1::Secp256k1Affine => Secp256k1,
+    secp256r1::Secp256r1, secp256r1::Secp256r1Affine => Secp256r1,
+    crate::secq256k1::Secq256k1, crate::secq256k1::Secq256k1Affine => Secq256k1,
+    pasta::Ep, pasta::EpAffine => Pallas,
+    pasta::Eq, pasta::EqAffine => Vesta,
+    crate::babyjubjub::BabyJubjub, crate::babyjubjub::BabyJubjubAffine => BabyJubjub,
+);
+
+impl Named for crate::babyjubjub::BabyJubjubSubgroup {
+    const NAME: &'static str = CurveId::BabyJubjub.name();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CurveId, Named};
+    use crate::{babyjubjub, bn256, grumpkin, pasta, secp256k1, secp256r1, secq256k1, CurveExt};
+
+    fn check<C: CurveExt + Named>(id: CurveId) {
+        assert_eq!(CurveId::of::<C>(), Some(id));
+        assert_eq!(C::NAME, C::CURVE_ID);
+    }
+
+    #[test]
+    fn test_curve_id() {
+        check::<bn256::G1>(CurveId::Bn256G1);
+        check::<bn256::G2>(CurveId::Bn256G2);
+        check::<grumpkin::G1>(CurveId::Grumpkin);
+        check::<secp256k1::Secp256k1>(CurveId::Secp256k1);
+        check::<secp256r1::Secp256r1>(CurveId::Secp256r1);
+        check::<secq256k1::Secq256k1>(CurveId::Secq256k1);
+        check::<pasta::Ep>(CurveId::Pallas);
+        check::<pasta::Eq>(CurveId::Vesta);
+
+        // Baby Jubjub is not a `CurveExt`.
+        assert_eq!(
+            babyjubjub::BabyJubjub::NAME,
+            babyjubjub::BabyJubjub::CURVE_ID
+        );
+        assert_eq!(
+            CurveId::from_name(babyjubjub::BabyJubjub::CURVE_ID),
+            Some(CurveId::BabyJubjub)
+        );
+
+        for id in CurveId::ALL {
+            assert_eq!(CurveId::from_name(id.name()), Some(id));
+            if let Some(oid) = id.oid() {
+                assert_eq!(CurveId::from_oid(oid), Some(id));
+            }
+        }
+        assert_eq!(CurveId::from_name("bn254"), None);
+    }
+}
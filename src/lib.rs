@@ -1,7 +1,34 @@
 mod arithmetic;
+pub mod batch_verify;
+pub mod convert;
+pub mod coset;
+pub mod decode;
+pub mod dynamic;
+pub mod embedded;
+pub mod fixed_base;
+pub mod generator_cache;
+pub mod generic_curve;
 pub mod hash_to_curve;
+pub mod id;
+pub mod msm;
+pub mod normalize;
+pub mod order;
+pub mod params;
+pub mod pedersen;
+pub mod prelude;
+pub mod repr;
+pub mod scalar_mul;
+pub mod secret;
+pub mod seeded;
 pub mod serde;
+pub mod signed;
+pub mod sinsemilla;
+pub mod sqrt;
+#[cfg(feature = "op-count")]
+pub mod stats;
+pub mod torsion;
 
+pub mod babyjubjub;
 pub mod bn256;
 pub mod grumpkin;
 pub mod pasta;
@@ -11,6 +38,7 @@ pub mod secq256k1;
 
 #[macro_use]
 mod derive;
+pub use arithmetic::{CurveAffineExt, CurveDoubling, LegendreSymbol};
 pub use pasta_curves::arithmetic::{Coordinates, CurveAffine, CurveExt};
 
 // Re-export ff and group to simplify down stream dependencies
@@ -26,6 +54,13 @@ use group;
 #[cfg(test)]
 pub mod tests;
 
+// The `small` feature trades speed for code size, which the assembly backend
+// and the lookup table would undo.
+#[cfg(all(feature = "small", feature = "asm"))]
+compile_error!("the `small` feature cannot be combined with `asm`");
+#[cfg(all(feature = "small", feature = "bn256-table"))]
+compile_error!("the `small` feature cannot be combined with `bn256-table`");
+
 #[cfg(all(feature = "prefetch", target_arch = "x86_64"))]
 #[inline(always)]
 pub fn prefetch<T>(data: &[T], offset: usize) {
@@ -0,0 +1,213 @@
+//! Variable-time multi-scalar multiplication.
+
+use crate::CurveAffine;
+use ff::PrimeField;
+use group::{Curve, Group};
+
+/// Returns the `len` bits of the little-endian `bytes` starting at bit
+/// `start`, as an integer.
+fn window(bytes: &[u8], start: usize, len: usize) -> usize {
+    (start..start + len)
+        .take_while(|bit| bit / 8 < bytes.len())
+        .filter(|bit| (bytes[bit / 8] >> (bit % 8)) & 1 == 1)
+        .map(|bit| 1 << (bit - start))
+        .sum()
+}
+
+/// Returns the window size for an MSM of `n` points.
+fn window_size(n: usize) -> usize {
+    if n < 4 {
+        1
+    } else if n < 32 {
+        3
+    } else {
+        (n as f64).ln().ceil() as usize
+    }
+}
+
+/// Adds the `digits` multiples of `bases` into `acc`, which has already been
+/// shifted by the window size `c`.
+fn accumulate_window<C: CurveAffine>(
+    acc: &mut C::CurveExt,
+    digits: &[usize],
+    bases: &[C],
+    c: usize,
+) {
+    let mut buckets = vec![C::CurveExt::identity(); (1 << c) - 1];
+    for (digit, base) in digits.iter().zip(bases) {
+        if *digit != 0 {
+            buckets[digit - 1] += base;
+        }
+    }
+
+    // sum_j j * buckets[j - 1], as a running sum from the top bucket.
+    let mut running = C::CurveExt::identity();
+    for bucket in buckets.into_iter().rev() {
+        running += bucket;
+        *acc += running;
+    }
+}
+
+/// Returns `sum_i scalars[i] * bases[i]`, using Pippenger's bucket method.
+/// The scalars are public: this leaks them through its timing.
+pub fn msm<C: CurveAffine>(scalars: &[C::ScalarExt], bases: &[C]) -> C::CurveExt {
+    assert_eq!(scalars.len(), bases.len());
+
+    let c = window_size(bases.len());
+    let reprs: Vec<_> = scalars.iter().map(|scalar| scalar.to_repr()).collect();
+    let num_windows = (C::ScalarExt::NUM_BITS as usize + c - 1) / c;
+
+    let mut acc = C::CurveExt::identity();
+    let mut digits = vec![0; reprs.len()];
+    for i in (0..num_windows).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+        for (digit, repr) in digits.iter_mut().zip(&reprs) {
+            *digit = window(repr.as_ref(), i * c, c);
+        }
+        accumulate_window(&mut acc, &digits, bases, c);
+    }
+    acc
+}
+
+/// Returns `acc * challenge + sum_i scalars[i] * points[i]`, the folding step
+/// of Nova-style accumulation schemes. The accumulator joins the MSM as one
+/// more base, so the whole expression shares a single set of doublings. Like
+/// `msm`, this is variable-time.
+pub fn fold_msm<C: CurveAffine>(
+    acc: &C::CurveExt,
+    points: &[C],
+    scalars: &[C::ScalarExt],
+    challenge: &C::ScalarExt,
+) -> C::CurveExt {
+    assert_eq!(scalars.len(), points.len());
+
+    let mut bases = Vec::with_capacity(points.len() + 1);
+    bases.extend_from_slice(points);
+    bases.push(acc.to_affine());
+    let mut all_scalars = Vec::with_capacity(scalars.len() + 1);
+    all_scalars.extend_from_slice(scalars);
+    all_scalars.push(*challenge);
+    msm(&all_scalars, &bases)
+}
+
+/// Returns `(sum_i scalars[i] * bases1[i], sum_i scalars[i] * bases2[i])`
+/// for two groups with the same scalar field, such as the G1 and G2 of a
+/// pairing-friendly curve. The scalars are recoded into windows once and the
+/// digits are shared by both groups. Like `msm`, this is variable-time.
+pub fn msm_pair<C1, C2>(
+    scalars: &[C1::ScalarExt],
+    bases1: &[C1],
+    bases2: &[C2],
+) -> (C1::CurveExt, C2::CurveExt)
+where
+    C1: CurveAffine,
+    C2: CurveAffine<ScalarExt = C1::ScalarExt>,
+{
+    assert_eq!(scalars.len(), bases1.len());
+    assert_eq!(scalars.len(), bases2.len());
+
+    let c = window_size(scalars.len());
+    let reprs: Vec<_> = scalars.iter().map(|scalar| scalar.to_repr()).collect();
+    let num_windows = (C1::ScalarExt::NUM_BITS as usize + c - 1) / c;
+
+    let mut acc1 = C1::CurveExt::identity();
+    let mut acc2 = C2::CurveExt::identity();
+    let mut digits = vec![0; reprs.len()];
+    for i in (0..num_windows).rev() {
+        for _ in 0..c {
+            acc1 = acc1.double();
+            acc2 = acc2.double();
+        }
+        for (digit, repr) in digits.iter_mut().zip(&reprs) {
+            *digit = window(repr.as_ref(), i * c, c);
+        }
+        accumulate_window(&mut acc1, &digits, bases1, c);
+        accumulate_window(&mut acc2, &digits, bases2, c);
+    }
+    (acc1, acc2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fold_msm, msm, msm_pair};
+    use crate::bn256::{Fr, G1Affine, G2Affine, G1, G2};
+    use crate::secp256k1::{Fq, Secp256k1, Secp256k1Affine};
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_msm() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        assert_eq!(msm::<G1Affine>(&[], &[]), G1::identity());
+        for n in [1, 2, 5, 40, 100] {
+            let scalars: Vec<_> = (0..n).map(|_| Fr::random(&mut rng)).collect();
+            let bases: Vec<_> = (0..n).map(|_| G1::random(&mut rng).to_affine()).collect();
+            let expected: G1 = scalars.iter().zip(&bases).map(|(s, b)| b * s).sum();
+            assert_eq!(msm(&scalars, &bases), expected);
+
+            let scalars: Vec<_> = (0..n).map(|_| -Fq::ONE).collect();
+            let bases: Vec<_> = (0..n)
+                .map(|_| Secp256k1::random(&mut rng).to_affine())
+                .collect();
+            let expected: Secp256k1 = bases.iter().map(|b| -b.to_curve()).sum();
+            assert_eq!(msm::<Secp256k1Affine>(&scalars, &bases), expected);
+        }
+    }
+
+    #[test]
+    fn test_msm_pair() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        assert_eq!(
+            msm_pair::<G1Affine, G2Affine>(&[], &[], &[]),
+            (G1::identity(), G2::identity())
+        );
+        for n in [1, 5, 40] {
+            let scalars: Vec<_> = (0..n).map(|_| Fr::random(&mut rng)).collect();
+            let bases1: Vec<_> = (0..n).map(|_| G1::random(&mut rng).to_affine()).collect();
+            let bases2: Vec<_> = (0..n).map(|_| G2::random(&mut rng).to_affine()).collect();
+            assert_eq!(
+                msm_pair(&scalars, &bases1, &bases2),
+                (msm(&scalars, &bases1), msm(&scalars, &bases2))
+            );
+        }
+    }
+
+    #[test]
+    fn test_fold_msm() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let acc = G1::random(&mut rng);
+        let challenge = Fr::random(&mut rng);
+        assert_eq!(
+            fold_msm::<G1Affine>(&acc, &[], &[], &challenge),
+            acc * challenge
+        );
+        assert_eq!(
+            fold_msm::<G1Affine>(&G1::identity(), &[], &[], &challenge),
+            G1::identity()
+        );
+        for n in [1, 5, 40] {
+            let scalars: Vec<_> = (0..n).map(|_| Fr::random(&mut rng)).collect();
+            let points: Vec<_> = (0..n).map(|_| G1::random(&mut rng).to_affine()).collect();
+            assert_eq!(
+                fold_msm(&acc, &points, &scalars, &challenge),
+                acc * challenge + msm(&scalars, &points)
+            );
+        }
+    }
+}
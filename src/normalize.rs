@@ -0,0 +1,159 @@
+//! Normalization policies choosing between a value and its negation.
+//!
+//! Several specifications make a value canonical by picking one of `x` and
+//! `-x`: ECDSA signatures with a low `s` (BIP-146), BIP-340 points with an
+//! even y-coordinate, and decompression conventions that take the smaller of
+//! the two square roots. A `Normalization` names one of these rules, so that
+//! the APIs applying it share one constant-time implementation: low `s` in
+//! `batch_verify`, even y in the BIP-340 keys of `secp256k1` and in `order`,
+//! and the smallest root in the decompression of gnark-crypto and arkworks
+//! points of `bn256`.
+//!
+//! The comparisons read the canonical representation of the field elements
+//! as little-endian integers, which is the case for every field of this
+//! crate.
+
+#![cfg_attr(
+    all(feature = "panic-lints", not(test)),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unreachable,
+        clippy::indexing_slicing
+    )
+)]
+
+use crate::{Coordinates, CurveAffine};
+use ff::PrimeField;
+use subtle::{Choice, ConditionallySelectable};
+
+/// A rule choosing between `x` and `-x`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Normalization {
+    /// Leaves every value as it is.
+    None,
+    /// Keeps `x` if it is at most `(m - 1) / 2` as an integer, for `m` the
+    /// modulus, as ECDSA with low `s`.
+    LowS,
+    /// Keeps `x` if it is even as an integer, as BIP-340 does for the
+    /// y-coordinates of points.
+    EvenY,
+    /// Keeps the smaller of `x` and `-x` as integers, as decompression
+    /// conventions do for square roots. This is the same rule as `LowS`,
+    /// named for its use on roots. The gnark-crypto and arkworks encodings
+    /// flag the root that this policy negates.
+    SmallestRoot,
+}
+
+/// Returns a `Choice` set when the little-endian integer `a` is smaller than
+/// `b`, in constant time.
+fn ct_less_than(a: &[u8], b: &[u8]) -> Choice {
+    debug_assert_eq!(a.len(), b.len());
+    let mut borrow = 0u16;
+    for (a, b) in a.iter().zip(b) {
+        let diff = (*a as u16).wrapping_sub(*b as u16).wrapping_sub(borrow);
+        borrow = diff >> 15;
+    }
+    Choice::from(borrow as u8)
+}
+
+/// Returns a `Choice` set when `x` is greater than `(m - 1) / 2`.
+fn is_high<F: PrimeField>(x: &F) -> Choice {
+    let half = F::TWO_INV - F::ONE;
+    ct_less_than(half.to_repr().as_ref(), x.to_repr().as_ref())
+}
+
+impl Normalization {
+    /// Returns a `Choice` set when `x` has to be negated to satisfy this
+    /// policy.
+    pub fn needs_negation<F: PrimeField>(&self, x: &F) -> Choice {
+        match self {
+            Normalization::None => Choice::from(0),
+            Normalization::LowS | Normalization::SmallestRoot => is_high(x),
+            Normalization::EvenY => x.is_odd(),
+        }
+    }
+
+    /// Returns `x` or `-x`, whichever satisfies this policy, together with a
+    /// flag set when `x` was negated.
+    pub fn normalize<F: PrimeField>(&self, x: &F) -> (F, Choice) {
+        let negate = self.needs_negation(x);
+        (F::conditional_select(x, &-*x, negate), negate)
+    }
+
+    /// Returns `p` or `-p`, whichever has a y-coordinate satisfying this
+    /// policy, together with a flag set when `p` was negated. The identity
+    /// is never negated, and is the only input that takes another branch.
+    pub fn normalize_point<C: CurveAffine>(&self, p: &C) -> (C, Choice)
+    where
+        C::Base: PrimeField,
+    {
+        let negate = Option::<Coordinates<C>>::from(p.coordinates())
+            .map_or(Choice::from(0), |c| self.needs_negation(c.y()));
+        (C::conditional_select(p, &-*p, negate), negate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Normalization;
+    use crate::{bn256, secp256k1};
+    use ff::PrimeField;
+    use group::{prime::PrimeCurveAffine, Curve, Group};
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn normalize_test<F: PrimeField>(rng: &mut XorShiftRng) {
+        let half = F::TWO_INV - F::ONE;
+        let (low, negated) = Normalization::LowS.normalize(&half);
+        assert_eq!(low, half);
+        assert!(!bool::from(negated));
+        let (low, negated) = Normalization::LowS.normalize(&(half + F::ONE));
+        assert_eq!(low, half);
+        assert!(bool::from(negated));
+        assert_eq!(Normalization::LowS.normalize(&F::ZERO).0, F::ZERO);
+        assert_eq!(Normalization::LowS.normalize(&-F::ONE).0, F::ONE);
+        assert_eq!(Normalization::EvenY.normalize(&F::ONE).0, -F::ONE);
+
+        for _ in 0..100 {
+            let x = F::random(&mut *rng);
+            for policy in [
+                Normalization::None,
+                Normalization::LowS,
+                Normalization::EvenY,
+                Normalization::SmallestRoot,
+            ] {
+                let (y, negated) = policy.normalize(&x);
+                assert_eq!(y, if bool::from(negated) { -x } else { x });
+                assert!(!bool::from(policy.needs_negation(&y)));
+                // Both of `x` and `-x` normalize to the same value.
+                if policy != Normalization::None {
+                    assert_eq!(policy.normalize(&-x).0, y);
+                }
+            }
+            assert!(!bool::from(Normalization::EvenY.normalize(&x).0.is_odd()));
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        normalize_test::<bn256::Fr>(&mut rng);
+        normalize_test::<secp256k1::Fp>(&mut rng);
+        normalize_test::<secp256k1::Fq>(&mut rng);
+
+        let identity = secp256k1::Secp256k1Affine::identity();
+        assert_eq!(Normalization::EvenY.normalize_point(&identity).0, identity);
+        for _ in 0..20 {
+            let p = secp256k1::Secp256k1::random(&mut rng).to_affine();
+            let (q, negated) = Normalization::EvenY.normalize_point(&p);
+            assert!(bool::from(q.has_even_y()));
+            assert_eq!(q, if bool::from(negated) { -p } else { p });
+            assert_eq!(Normalization::EvenY.normalize_point(&-p).0, q);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Constant-time arithmetic modulo a group order, on raw little-endian limbs.
+//!
+//! Nonce derivations such as RFC 6979 work with integers modulo the order of
+//! a curve before there is a scalar to convert them into. These functions
+//! take the order as limbs, for instance a curve's `ORDER_LIMBS`, and accept
+//! any odd or even modulus greater than one. They run in time independent of
+//! the values, but are bit-serial and much slower than the field types.
+//!
+//! Square roots modulo the order are taken with the scalar field of a curve
+//! instead, as for adaptor signatures over a BIP-340 style curve.
+
+#![cfg_attr(
+    all(feature = "panic-lints", not(test)),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unreachable,
+        clippy::indexing_slicing
+    )
+)]
+
+use crate::arithmetic::{adc, sbb};
+use crate::normalize::Normalization;
+use crate::CurveExt;
+use ff::{Field, PrimeField};
+use subtle::{Choice, CtOption};
+
+/// Returns a `Choice` set when `a < modulus`.
+pub fn is_reduced(a: &[u64; 4], modulus: &[u64; 4]) -> Choice {
+    let (_, borrow) = sbb(a[0], modulus[0], 0);
+    let (_, borrow) = sbb(a[1], modulus[1], borrow);
+    let (_, borrow) = sbb(a[2], modulus[2], borrow);
+    let (_, borrow) = sbb(a[3], modulus[3], borrow);
+    Choice::from((borrow & 1) as u8)
+}
+
+/// Returns `a + b mod modulus` for `a, b < modulus`.
+fn add_reduced(a: &[u64; 4], b: &[u64; 4], modulus: &[u64; 4]) -> [u64; 4] {
+    let (d0, carry) = adc(a[0], b[0], 0);
+    let (d1, carry) = adc(a[1], b[1], carry);
+    let (d2, carry) = adc(a[2], b[2], carry);
+    let (d3, carry) = adc(a[3], b[3], carry);
+
+    // Subtract the modulus, and add it back if that underflowed.
+    let (d0, borrow) = sbb(d0, modulus[0], 0);
+    let (d1, borrow) = sbb(d1, modulus[1], borrow);
+    let (d2, borrow) = sbb(d2, modulus[2], borrow);
+    let (d3, borrow) = sbb(d3, modulus[3], borrow);
+    let (_, borrow) = sbb(carry, 0, borrow);
+
+    let (d0, carry) = adc(d0, modulus[0] & borrow, 0);
+    let (d1, carry) = adc(d1, modulus[1] & borrow, carry);
+    let (d2, carry) = adc(d2, modulus[2] & borrow, carry);
+    let (d3, _) = adc(d3, modulus[3] & borrow, carry);
+    [d0, d1, d2, d3]
+}
+
+/// Returns `a - b mod modulus` for `a, b < modulus`.
+fn sub_reduced(a: &[u64; 4], b: &[u64; 4], modulus: &[u64; 4]) -> [u64; 4] {
+    let (d0, borrow) = sbb(a[0], b[0], 0);
+    let (d1, borrow) = sbb(a[1], b[1], borrow);
+    let (d2, borrow) = sbb(a[2], b[2], borrow);
+    let (d3, borrow) = sbb(a[3], b[3], borrow);
+
+    let (d0, carry) = adc(d0, modulus[0] & borrow, 0);
+    let (d1, carry) = adc(d1, modulus[1] & borrow, carry);
+    let (d2, carry) = adc(d2, modulus[2] & borrow, carry);
+    let (d3, _) = adc(d3, modulus[3] & borrow, carry);
+    [d0, d1, d2, d3]
+}
+
+/// Reduces the little-endian integer `limbs` of any length, one bit at a
+/// time from the most significant.
+fn reduce_limbs(limbs: &[u64], modulus: &[u64; 4]) -> [u64; 4] {
+    let mut r = [0u64; 4];
+    for limb in limbs.iter().rev() {
+        for i in (0..64).rev() {
+            r = add_reduced(&r, &r, modulus);
+            r = add_reduced(&r, &[(limb >> i) & 1, 0, 0, 0], modulus);
+        }
+    }
+    r
+}
+
+/// Returns `a mod modulus` for any 256-bit `a`.
+pub fn reduce(a: &[u64; 4], modulus: &[u64; 4]) -> [u64; 4] {
+    reduce_limbs(a, modulus)
+}
+
+/// Returns `a mod modulus` for any 512-bit `a`, such as the output of a hash
+/// expanded to twice the size of the order to make the bias negligible.
+pub fn reduce_wide(a: &[u64; 8], modulus: &[u64; 4]) -> [u64; 4] {
+    reduce_limbs(a, modulus)
+}
+
+/// Returns `a + b mod modulus`, or none if `a` or `b` is not reduced.
+pub fn add_mod_order(a: &[u64; 4], b: &[u64; 4], modulus: &[u64; 4]) -> CtOption<[u64; 4]> {
+    CtOption::new(
+        add_reduced(a, b, modulus),
+        is_reduced(a, modulus) & is_reduced(b, modulus),
+    )
+}
+
+/// Returns `a - b mod modulus`, or none if `a` or `b` is not reduced.
+pub fn sub_mod_order(a: &[u64; 4], b: &[u64; 4], modulus: &[u64; 4]) -> CtOption<[u64; 4]> {
+    CtOption::new(
+        sub_reduced(a, b, modulus),
+        is_reduced(a, modulus) & is_reduced(b, modulus),
+    )
+}
+
+/// Returns `a * b mod modulus`, or none if `a` or `b` is not reduced.
+pub fn mul_mod_order(a: &[u64; 4], b: &[u64; 4], modulus: &[u64; 4]) -> CtOption<[u64; 4]> {
+    let mut r = [0u64; 4];
+    for limb in b.iter().rev() {
+        for i in (0..64).rev() {
+            let mask = ((limb >> i) & 1).wrapping_neg();
+            r = add_reduced(&r, &r, modulus);
+            r = add_reduced(
+                &r,
+                &[a[0] & mask, a[1] & mask, a[2] & mask, a[3] & mask],
+                modulus,
+            );
+        }
+    }
+    CtOption::new(r, is_reduced(a, modulus) & is_reduced(b, modulus))
+}
+
+/// Converts reduced limbs to the scalar field of `C`.
+fn to_scalar<C: CurveExt>(a: &[u64; 4]) -> CtOption<C::ScalarExt> {
+    let mut repr = <C::ScalarExt as PrimeField>::Repr::default();
+    for (chunk, limb) in repr.as_mut().chunks_mut(8).zip(a) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    C::ScalarExt::from_repr(repr)
+}
+
+/// Converts a scalar of `C` to limbs.
+fn from_scalar<C: CurveExt>(scalar: &C::ScalarExt) -> [u64; 4] {
+    let repr = scalar.to_repr();
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(repr.as_ref().chunks(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap_or([0; 8]));
+    }
+    limbs
+}
+
+/// Returns a `Choice` set when `a` is a square modulo the order of `C`,
+/// zero included. Returns none if `a` is not reduced.
+pub fn is_square_mod_order<C: CurveExt>(a: &[u64; 4]) -> CtOption<Choice> {
+    to_scalar::<C>(a).map(|a| a.sqrt().is_some())
+}
+
+/// Returns the even square root of `a` modulo the order of `C`, or none if
+/// `a` is not reduced or not a square. The other root is `order - root`.
+///
+/// The square roots go through the scalar field of `C`, which is the field
+/// modulo the group order, and not through the base field of the curve.
+pub fn sqrt_mod_order<C: CurveExt>(a: &[u64; 4]) -> CtOption<[u64; 4]> {
+    to_scalar::<C>(a).and_then(|a| {
+        a.sqrt().map(|root| {
+            let (root, _) = Normalization::EvenY.normalize(&root);
+            from_scalar::<C>(&root)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bn256, secp256k1};
+    use num_bigint::BigUint;
+    use rand_core::{RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    fn int(limbs: &[u64]) -> BigUint {
+        let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    fn random_limbs<const N: usize>(rng: &mut XorShiftRng) -> [u64; N] {
+        let mut limbs = [0u64; N];
+        for limb in limbs.iter_mut() {
+            *limb = rng.next_u64();
+        }
+        limbs
+    }
+
+    #[test]
+    fn test_mod_order() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        for modulus in [
+            secp256k1::Secp256k1::ORDER_LIMBS,
+            bn256::G1::ORDER_LIMBS,
+            [0xffffffff00000001, 0, 0, 0],
+        ] {
+            let m = int(&modulus);
+            for _ in 0..50 {
+                let wide: [u64; 8] = random_limbs(&mut rng);
+                assert_eq!(int(&reduce_wide(&wide, &modulus)), int(&wide) % &m);
+                let a = reduce(&random_limbs(&mut rng), &modulus);
+                let b = reduce(&random_limbs(&mut rng), &modulus);
+
+                assert_eq!(
+                    int(&add_mod_order(&a, &b, &modulus).unwrap()),
+                    (int(&a) + int(&b)) % &m
+                );
+                assert_eq!(
+                    int(&sub_mod_order(&a, &b, &modulus).unwrap()),
+                    (int(&a) + &m - int(&b)) % &m
+                );
+                assert_eq!(
+                    int(&mul_mod_order(&a, &b, &modulus).unwrap()),
+                    int(&a) * int(&b) % &m
+                );
+            }
+
+            assert!(bool::from(
+                add_mod_order(&modulus, &[0; 4], &modulus).is_none()
+            ));
+            assert!(bool::from(
+                mul_mod_order(&[1, 0, 0, 0], &modulus, &modulus).is_none()
+            ));
+            assert_eq!(reduce(&modulus, &modulus), [0; 4]);
+        }
+
+        // The results agree with the scalar field.
+        let a: [u64; 4] = reduce(&random_limbs(&mut rng), &bn256::G1::ORDER_LIMBS);
+        let b: [u64; 4] = reduce(&random_limbs(&mut rng), &bn256::G1::ORDER_LIMBS);
+        assert_eq!(
+            bn256::Fr::from_raw(mul_mod_order(&a, &b, &bn256::G1::ORDER_LIMBS).unwrap()),
+            bn256::Fr::from_raw(a) * bn256::Fr::from_raw(b)
+        );
+    }
+
+    #[test]
+    fn test_sqrt_mod_order() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let modulus = secp256k1::Secp256k1::ORDER_LIMBS;
+        let m = int(&modulus);
+
+        assert_eq!(
+            sqrt_mod_order::<secp256k1::Secp256k1>(&[0; 4]).unwrap(),
+            [0; 4]
+        );
+        assert!(bool::from(
+            sqrt_mod_order::<secp256k1::Secp256k1>(&modulus).is_none()
+        ));
+        assert!(bool::from(
+            is_square_mod_order::<secp256k1::Secp256k1>(&modulus).is_none()
+        ));
+
+        let mut squares = 0;
+        for _ in 0..100 {
+            let a = reduce(&random_limbs(&mut rng), &modulus);
+            let is_square = is_square_mod_order::<secp256k1::Secp256k1>(&a).unwrap();
+            let root = sqrt_mod_order::<secp256k1::Secp256k1>(&a);
+            assert_eq!(bool::from(is_square), bool::from(root.is_some()));
+            if let Some(root) = Option::<[u64; 4]>::from(root) {
+                squares += 1;
+                assert_eq!(int(&root) * int(&root) % &m, int(&a));
+                assert_eq!(root[0] & 1, 0);
+            }
+        }
+        // About half of the residues are squares.
+        assert!(squares > 25 && squares < 75);
+
+        // The order of bn256 G1 has a large power of two in `n - 1`.
+        let a = reduce(&random_limbs(&mut rng), &bn256::G1::ORDER_LIMBS);
+        let square = mul_mod_order(&a, &a, &bn256::G1::ORDER_LIMBS).unwrap();
+        let root = sqrt_mod_order::<bn256::G1>(&square).unwrap();
+        let negated = sub_mod_order(&[0; 4], &a, &bn256::G1::ORDER_LIMBS).unwrap();
+        assert!(root == a || root == negated);
+    }
+}
@@ -0,0 +1,238 @@
+//! Machine-readable curve and field parameters, for circuit code generators.
+//!
+//! Every curve module exposes a `params` function returning the constants of
+//! the curve and of its base and scalar fields. All integers are given as
+//! little-endian 64-bit limbs. The Montgomery constants are those of the
+//! four-limb representation used by every field of this crate, and are
+//! checked against the field arithmetic by the tests below.
+
+use crate::arithmetic::EndoParameters;
+use crate::repr::FieldRepr;
+use crate::{CurveAffine, CurveExt};
+use ff::{PrimeField, WithSmallOrderMulGroup};
+use group::prime::PrimeCurveAffine;
+use num_bigint::BigUint;
+
+/// Constants of a prime field of at most 256 bits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldParams {
+    pub modulus: [u64; 4],
+    pub num_bits: u32,
+    /// `2^256 mod p`, the Montgomery form of one.
+    pub r: [u64; 4],
+    /// `2^512 mod p`.
+    pub r2: [u64; 4],
+    /// `2^768 mod p`.
+    pub r3: [u64; 4],
+    /// `-p^-1 mod 2^64`.
+    pub inv: u64,
+    /// `S` such that `p - 1 = 2^S * t` with `t` odd.
+    pub two_adicity: u32,
+    /// `MULTIPLICATIVE_GENERATOR`, also a quadratic non-residue.
+    pub multiplicative_generator: [u64; 4],
+    /// `ROOT_OF_UNITY`, a primitive `2^S`-th root of unity.
+    pub root_of_unity: [u64; 4],
+    /// `ZETA`, a primitive cube root of unity.
+    pub zeta: [u64; 4],
+}
+
+/// Constants of the GLV decomposition of the scalars of a curve.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndoParams {
+    pub gamma1: [u64; 4],
+    pub gamma2: [u64; 4],
+    pub b1: [u64; 4],
+    pub b2: [u64; 4],
+}
+
+/// Constants of a short Weierstrass curve `y^2 = x^3 + a x + b` over a prime
+/// field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CurveParams {
+    pub name: &'static str,
+    pub base: FieldParams,
+    pub scalar: FieldParams,
+    pub a: [u64; 4],
+    pub b: [u64; 4],
+    pub generator: ([u64; 4], [u64; 4]),
+    /// Present for the curves implementing `CurveEndo`.
+    pub endo: Option<EndoParams>,
+}
+
+/// Constants of a twisted Edwards curve `a x^2 + y^2 = 1 + d x^2 y^2` over a
+/// prime field. `scalar` is the field of its subgroup of prime order, of index
+/// `cofactor`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EdwardsCurveParams {
+    pub name: &'static str,
+    pub base: FieldParams,
+    pub scalar: FieldParams,
+    pub a: [u64; 4],
+    pub d: [u64; 4],
+    pub cofactor: u64,
+    /// A generator of the prime-order subgroup.
+    pub generator: ([u64; 4], [u64; 4]),
+}
+
+fn limbs(x: &BigUint) -> [u64; 4] {
+    let mut limbs = [0u64; 4];
+    for (limb, digit) in limbs.iter_mut().zip(x.to_u64_digits()) {
+        *limb = digit;
+    }
+    limbs
+}
+
+fn canonical<F: PrimeField>(x: &F) -> [u64; 4] {
+    FieldRepr::from_field(x).limbs()
+}
+
+impl FieldParams {
+    pub fn of<F: WithSmallOrderMulGroup<3>>() -> Self {
+        let bytes = (-F::ONE).to_repr();
+        let modulus = BigUint::from_bytes_le(bytes.as_ref()) + 1u32;
+        let r = (BigUint::from(1u32) << 256) % &modulus;
+        let r2 = &r * &r % &modulus;
+        let r3 = &r2 * &r % &modulus;
+
+        let p0 = limbs(&modulus)[0];
+        let mut inv = 1u64;
+        for _ in 0..63 {
+            inv = inv.wrapping_mul(inv).wrapping_mul(p0);
+        }
+
+        FieldParams {
+            modulus: limbs(&modulus),
+            num_bits: F::NUM_BITS,
+            r: limbs(&r),
+            r2: limbs(&r2),
+            r3: limbs(&r3),
+            inv: inv.wrapping_neg(),
+            two_adicity: F::S,
+            multiplicative_generator: canonical(&F::MULTIPLICATIVE_GENERATOR),
+            root_of_unity: canonical(&F::ROOT_OF_UNITY),
+            zeta: canonical(&F::ZETA),
+        }
+    }
+}
+
+impl CurveParams {
+    pub(crate) fn of<C: CurveExt>(endo: Option<&EndoParameters>) -> Self {
+        let generator = C::AffineExt::generator().coordinates().unwrap();
+        CurveParams {
+            name: C::CURVE_ID,
+            base: FieldParams::of::<C::Base>(),
+            scalar: FieldParams::of::<C::ScalarExt>(),
+            a: canonical(&C::a()),
+            b: canonical(&C::b()),
+            generator: (canonical(generator.x()), canonical(generator.y())),
+            endo: endo.map(|endo| EndoParams {
+                gamma1: endo.gamma1,
+                gamma2: endo.gamma2,
+                b1: endo.b1,
+                b2: endo.b2,
+            }),
+        }
+    }
+}
+
+impl EdwardsCurveParams {
+    pub(crate) fn of<Base, Scalar>(
+        name: &'static str,
+        a: Base,
+        d: Base,
+        cofactor: u64,
+        generator: (Base, Base),
+    ) -> Self
+    where
+        Base: WithSmallOrderMulGroup<3>,
+        Scalar: WithSmallOrderMulGroup<3>,
+    {
+        EdwardsCurveParams {
+            name,
+            base: FieldParams::of::<Base>(),
+            scalar: FieldParams::of::<Scalar>(),
+            a: canonical(&a),
+            d: canonical(&d),
+            cofactor,
+            generator: (canonical(&generator.0), canonical(&generator.1)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FieldParams;
+    use crate::{babyjubjub, bn256, grumpkin, pasta, secp256k1, secp256r1, secq256k1};
+    use ff::{Field, PrimeField};
+    use num_bigint::BigUint;
+
+    macro_rules! field_params_test {
+        ($module:ident, $field:ident) => {{
+            use $module::$field;
+
+            let params = FieldParams::of::<$field>();
+            assert_eq!(params.modulus, $field::MODULUS_LIMBS);
+            assert_eq!($field::from_raw(params.modulus), $field::ZERO);
+            assert_eq!($field(params.r), $field::ONE);
+            assert_eq!($field(params.r2), $field::from_raw(params.r));
+            assert_eq!($field(params.r3), $field::from_raw(params.r2));
+            assert_eq!(params.modulus[0].wrapping_mul(params.inv), u64::MAX);
+            assert_eq!(
+                $field::from_raw(params.multiplicative_generator),
+                $field::MULTIPLICATIVE_GENERATOR
+            );
+            assert_eq!($field::from_raw(params.zeta).pow_vartime([3]), $field::ONE);
+        }};
+    }
+
+    #[test]
+    fn test_field_params() {
+        field_params_test!(bn256, Fq);
+        field_params_test!(bn256, Fr);
+        field_params_test!(secp256k1, Fp);
+        field_params_test!(secp256k1, Fq);
+        field_params_test!(secp256r1, Fp);
+        field_params_test!(secp256r1, Fq);
+        field_params_test!(babyjubjub, Fr);
+    }
+
+    #[test]
+    fn test_curve_params() {
+        let params = bn256::params();
+        assert_eq!(params.name, "bn256_g1");
+        assert_eq!(params.base, FieldParams::of::<bn256::Fq>());
+        assert_eq!(params.a, [0; 4]);
+        assert_eq!(params.b, [3, 0, 0, 0]);
+        assert_eq!(params.generator, ([1, 0, 0, 0], [2, 0, 0, 0]));
+        assert!(params.endo.is_some());
+        assert_eq!(bn256::G1::ORDER_LIMBS, params.scalar.modulus);
+        assert_eq!(bn256::G1::COFACTOR, [1, 0, 0, 0]);
+
+        // The twist has order r * (2p - r).
+        let int = |limbs: [u64; 4]| {
+            let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+            BigUint::from_bytes_le(&bytes)
+        };
+        assert_eq!(
+            int(bn256::G2::COFACTOR) + int(bn256::G2::ORDER_LIMBS),
+            int(bn256::Fq::MODULUS_LIMBS) * 2u32
+        );
+
+        assert_eq!(grumpkin::params().base, params.scalar);
+        assert_eq!(grumpkin::params().scalar, params.base);
+        assert_eq!(secq256k1::params().base, secp256k1::params().scalar);
+        assert!(secp256k1::params().endo.is_none());
+        assert_eq!(secp256r1::params().scalar.num_bits, 256);
+        assert_eq!(pasta::pallas_params().base, pasta::vesta_params().scalar);
+        assert!(pasta::vesta_params().endo.is_some());
+
+        let params = babyjubjub::params();
+        assert_eq!(params.name, "babyjubjub");
+        assert_eq!(params.base, FieldParams::of::<bn256::Fr>());
+        assert_eq!(params.a, [168700, 0, 0, 0]);
+        assert_eq!(params.d, [168696, 0, 0, 0]);
+        // The curve has order 8 r.
+        assert_eq!(params.cofactor, 8);
+        assert_eq!(params.scalar.num_bits, 251);
+    }
+}
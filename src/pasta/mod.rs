@@ -1,7 +1,7 @@
 use crate::arithmetic::mul_512;
 use crate::arithmetic::sbb;
 use crate::{
-    arithmetic::{CurveEndo, EndoParameters},
+    arithmetic::{batch_add_projective, CurveAffineExt, CurveDoubling, CurveEndo, EndoParameters},
     endo,
 };
 use ff::PrimeField;
@@ -38,6 +38,55 @@ const ENDO_PARAMS_EP: EndoParameters = EndoParameters {
 endo!(Eq, Fp, ENDO_PARAMS_EQ);
 endo!(Ep, Fq, ENDO_PARAMS_EP);
 
+macro_rules! impl_curve_affine_ext {
+    ($affine:ident) => {
+        impl CurveAffineExt for $affine {
+            fn batch_add<const COMPLETE: bool, const LOAD_POINTS: bool>(
+                points: &mut [Self],
+                output_indices: &[u32],
+                num_points: usize,
+                offset: usize,
+                bases: &[Self],
+                base_positions: &[u32],
+            ) {
+                // The coordinates of the pasta points are private, so the
+                // additions go through projective coordinates, which are
+                // complete anyway.
+                batch_add_projective::<_, LOAD_POINTS>(
+                    points,
+                    output_indices,
+                    num_points,
+                    offset,
+                    bases,
+                    base_positions,
+                )
+            }
+        }
+    };
+}
+
+impl_curve_affine_ext!(EpAffine);
+impl_curve_affine_ext!(EqAffine);
+
+impl CurveDoubling for Ep {}
+impl CurveDoubling for Eq {}
+
+/// Returns the constants of Pallas and of its fields.
+pub fn pallas_params() -> crate::params::CurveParams {
+    crate::params::CurveParams::of::<Ep>(Some(&ENDO_PARAMS_EP))
+}
+
+/// Returns the constants of Vesta and of its fields.
+pub fn vesta_params() -> crate::params::CurveParams {
+    crate::params::CurveParams::of::<Eq>(Some(&ENDO_PARAMS_EQ))
+}
+
+#[test]
+fn test_batch_add() {
+    crate::tests::curve::batch_add_test::<Ep>();
+    crate::tests::curve::batch_add_test::<Eq>();
+}
+
 #[test]
 fn test_endo() {
     use ff::Field;
@@ -0,0 +1,128 @@
+//! Windowed Pedersen hashes, with the encoding of section 5.4.1.7 of the
+//! Zcash protocol specification.
+//!
+//! The message is split into 3-bit chunks `(s0, s1, s2)`, each encoded as
+//! `(1 + s0 + 2 * s1) * (1 - 2 * s2)`, and into segments of
+//! `CHUNKS_PER_SEGMENT` chunks. The `j`-th chunk of a segment is scaled by
+//! `2^(4 * j)`, and the `i`-th segment is multiplied by the `i`-th generator
+//! of the domain, as derived by `derive_generators`. The generators and their
+//! fixed-base tables are shared through the global `GeneratorCache`.
+
+use crate::fixed_base::FixedBaseTable;
+use crate::generator_cache::GeneratorCache;
+use crate::hash_to_curve::GeneratorIndexError;
+use crate::CurveExt;
+use ff::PrimeField;
+use group::Group;
+use std::sync::Arc;
+
+/// The number of 3-bit chunks of a segment, which keeps the encoded segments
+/// below the order of the Jubjub subgroup in Sapling.
+pub const CHUNKS_PER_SEGMENT: usize = 63;
+
+/// Returns the encoding of the segment `chunks` as a scalar.
+fn encode_segment<F: PrimeField>(chunks: &[[bool; 3]]) -> F {
+    let mut acc = F::ZERO;
+    let mut scale = F::ONE;
+    for [s0, s1, s2] in chunks {
+        let magnitude = F::from(1 + *s0 as u64 + 2 * *s1 as u64);
+        acc += if *s2 { -magnitude } else { magnitude } * scale;
+        scale = scale.double().double().double().double();
+    }
+    acc
+}
+
+/// The generators of a Pedersen hash domain and their fixed-base tables.
+#[derive(Clone, Debug)]
+pub struct PedersenDomain<G: CurveExt> {
+    tables: Vec<Arc<FixedBaseTable<G::AffineExt>>>,
+}
+
+impl<G: CurveExt> PedersenDomain<G> {
+    /// Derives the domain with the given personalization, for messages of at
+    /// most `max_bits` bits. Returns an error if this takes more generators
+    /// than `derive_generators` can index.
+    pub fn new(domain: &str, max_bits: usize) -> Result<Self, GeneratorIndexError> {
+        let num_segments = (max_bits + 3 * CHUNKS_PER_SEGMENT - 1) / (3 * CHUNKS_PER_SEGMENT);
+        // Derives all the generators at once, in parallel.
+        GeneratorCache::global().generators::<G>(domain, num_segments)?;
+        let tables = (0..num_segments)
+            .map(|i| GeneratorCache::global().table::<G>(domain, i))
+            .collect::<Result<_, _>>()?;
+        Ok(PedersenDomain { tables })
+    }
+
+    /// Returns the maximum number of bits of a message.
+    pub fn max_bits(&self) -> usize {
+        3 * CHUNKS_PER_SEGMENT * self.tables.len()
+    }
+
+    /// Returns the hash of `message`, which is padded with zeros to a
+    /// multiple of 3 bits, or `None` if it is longer than `max_bits`.
+    pub fn hash_to_point(&self, message: &[bool]) -> Option<G> {
+        if message.len() > self.max_bits() {
+            return None;
+        }
+
+        let chunks: Vec<[bool; 3]> = message
+            .chunks(3)
+            .map(|chunk| {
+                let mut bits = [false; 3];
+                bits[..chunk.len()].copy_from_slice(chunk);
+                bits
+            })
+            .collect();
+        Some(
+            chunks
+                .chunks(CHUNKS_PER_SEGMENT)
+                .zip(self.tables.iter())
+                .fold(G::identity(), |acc, (segment, table)| {
+                    acc + table.mul(&encode_segment(segment))
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PedersenDomain, CHUNKS_PER_SEGMENT};
+    use crate::hash_to_curve::derive_generators;
+    use crate::pasta::{Ep, Fq};
+    use ff::Field;
+    use group::Group;
+
+    #[test]
+    fn test_pedersen_hash() {
+        let domain = PedersenDomain::<Ep>::new("halo2curves:pedersen", 400).unwrap();
+        assert_eq!(domain.max_bits(), 3 * 3 * CHUNKS_PER_SEGMENT);
+        let generators = derive_generators::<Ep>("halo2curves:pedersen", 3).unwrap();
+
+        // One chunk (s0, s1, s2) = (1, 1, 1) encodes -4.
+        assert_eq!(
+            domain.hash_to_point(&[true, true, true]),
+            Some(Ep::from(generators[0]) * -Fq::from(4))
+        );
+        // The second chunk is scaled by 16, and missing bits are zeros.
+        assert_eq!(
+            domain.hash_to_point(&[false, false, false, true]),
+            Some(Ep::from(generators[0]) * Fq::from(1 + 2 * 16))
+        );
+
+        // The first chunk of the second segment goes to the second generator.
+        let mut message = vec![false; 3 * CHUNKS_PER_SEGMENT + 2];
+        message[3 * CHUNKS_PER_SEGMENT + 1] = true;
+        let first: Fq = (0..CHUNKS_PER_SEGMENT as u64)
+            .map(|j| Fq::from(2).pow([4 * j]))
+            .sum();
+        assert_eq!(
+            domain.hash_to_point(&message),
+            Some(Ep::from(generators[0]) * first + Ep::from(generators[1]) * Fq::from(3))
+        );
+
+        assert_eq!(domain.hash_to_point(&[]), Some(Ep::identity()));
+        assert_eq!(
+            domain.hash_to_point(&vec![false; domain.max_bits() + 1]),
+            None
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! The traits and entry points that most code using this crate needs, for a
+//! single glob import that does not depend on the modules they live in.
+//!
+//! ```
+//! use halo2curves::bn256::{Fr, G1Affine, G1};
+//! use halo2curves::prelude::*;
+//!
+//! let scalars = [Fr::from(2), Fr::from(3)];
+//! let bases = [G1Affine::generator(), G1::hash_to_curve("prelude")(b"base").to_affine()];
+//! assert_eq!(
+//!     msm(&scalars, &bases),
+//!     bases[0] * scalars[0] + bases[1] * scalars[1]
+//! );
+//! ```
+
+pub use crate::arithmetic::{CurveAffineExt, CurveDoubling};
+pub use crate::convert::convert_scalar;
+pub use crate::hash_to_curve::{derive_generators, hash_to_scalar};
+pub use crate::msm::msm;
+pub use crate::serde::SerdeObject;
+pub use crate::{Coordinates, CurveAffine, CurveExt};
+
+#[cfg(feature = "bits")]
+pub use ff::PrimeFieldBits;
+pub use ff::{BatchInvert, Field, FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+// `CofactorCurveAffine` is left out: its methods have the same names as those
+// of `PrimeCurveAffine`, and the curves implement both.
+pub use group::cofactor::CofactorGroup;
+pub use group::prime::{PrimeCurve, PrimeCurveAffine, PrimeGroup};
+pub use group::{Curve, Group, GroupEncoding, UncompressedEncoding};
+pub use pairing::{Engine, MillerLoopResult, MultiMillerLoop, PairingCurveAffine};
@@ -0,0 +1,182 @@
+//! Bit operations on the canonical representation of field elements.
+//!
+//! `FieldRepr` holds the canonical integer of an element of a field of at
+//! most 256 bits as four little-endian limbs, and supports shifts, bitwise
+//! operations and bit access on it, so that scalar decompositions and range
+//! checks do not have to convert through a big integer crate.
+
+use ff::PrimeField;
+use std::ops::{BitAnd, BitOr, BitXor, Shl, Shr};
+
+/// A 256-bit unsigned integer, as four little-endian limbs.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FieldRepr([u64; 4]);
+
+impl FieldRepr {
+    pub const fn from_limbs(limbs: [u64; 4]) -> Self {
+        FieldRepr(limbs)
+    }
+
+    pub const fn limbs(&self) -> [u64; 4] {
+        self.0
+    }
+
+    /// Returns the canonical integer of `element`. The representation of `F`
+    /// must be little-endian and at most 32 bytes long, as is the case for
+    /// every field of this crate.
+    pub fn from_field<F: PrimeField>(element: &F) -> Self {
+        let repr = element.to_repr();
+        let bytes = repr.as_ref();
+        assert!(bytes.len() <= 32);
+
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+            let mut buf = [0u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            *limb = u64::from_le_bytes(buf);
+        }
+        FieldRepr(limbs)
+    }
+
+    /// Returns the field element with this canonical integer, or `None` if it
+    /// is not smaller than the modulus.
+    pub fn into_field<F: PrimeField>(self) -> Option<F> {
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes.chunks_mut(8).zip(self.0) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+
+        let mut repr = F::Repr::default();
+        let len = repr.as_ref().len();
+        if bytes[len.min(32)..].iter().any(|byte| *byte != 0) {
+            return None;
+        }
+        repr.as_mut().copy_from_slice(&bytes[..len]);
+        F::from_repr(repr).into()
+    }
+
+    /// Returns bit `i`, counting from the least significant one.
+    pub fn bit(&self, i: u32) -> bool {
+        i < 256 && (self.0[(i / 64) as usize] >> (i % 64)) & 1 == 1
+    }
+
+    /// Returns the number of bits needed to represent this integer, which is
+    /// zero for zero.
+    pub fn bit_len(&self) -> u32 {
+        self.0
+            .iter()
+            .rposition(|limb| *limb != 0)
+            .map_or(0, |i| 64 * i as u32 + 64 - self.0[i].leading_zeros())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+}
+
+impl Shl<u32> for FieldRepr {
+    type Output = FieldRepr;
+
+    /// Shifts left, dropping the bits shifted past bit 255.
+    fn shl(self, n: u32) -> FieldRepr {
+        if n >= 256 {
+            return FieldRepr::default();
+        }
+        let (words, bits) = ((n / 64) as usize, n % 64);
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate().skip(words) {
+            *limb = self.0[i - words] << bits;
+            if bits > 0 && i > words {
+                *limb |= self.0[i - words - 1] >> (64 - bits);
+            }
+        }
+        FieldRepr(limbs)
+    }
+}
+
+impl Shr<u32> for FieldRepr {
+    type Output = FieldRepr;
+
+    fn shr(self, n: u32) -> FieldRepr {
+        if n >= 256 {
+            return FieldRepr::default();
+        }
+        let (words, bits) = ((n / 64) as usize, n % 64);
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate().take(4 - words) {
+            *limb = self.0[i + words] >> bits;
+            if bits > 0 && i + words + 1 < 4 {
+                *limb |= self.0[i + words + 1] << (64 - bits);
+            }
+        }
+        FieldRepr(limbs)
+    }
+}
+
+macro_rules! impl_bitwise {
+    ($trait:ident, $fn:ident, $op:tt) => {
+        impl $trait for FieldRepr {
+            type Output = FieldRepr;
+
+            fn $fn(self, rhs: FieldRepr) -> FieldRepr {
+                let mut limbs = self.0;
+                for (limb, rhs) in limbs.iter_mut().zip(rhs.0) {
+                    *limb = *limb $op rhs;
+                }
+                FieldRepr(limbs)
+            }
+        }
+    };
+}
+
+impl_bitwise!(BitAnd, bitand, &);
+impl_bitwise!(BitOr, bitor, |);
+impl_bitwise!(BitXor, bitxor, ^);
+
+#[cfg(test)]
+mod tests {
+    use super::FieldRepr;
+    use crate::bn256::Fr;
+    use crate::secp256k1::Fp;
+    use ff::{Field, PrimeField};
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_field_repr() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let five = FieldRepr::from_field(&Fr::from(5));
+        assert_eq!(five << 3, FieldRepr::from_field(&Fr::from(40)));
+        assert_eq!(five >> 1, FieldRepr::from_field(&Fr::from(2)));
+        assert_eq!(five << 64, FieldRepr::from_limbs([0, 5, 0, 0]));
+        assert_eq!(five << 254 >> 254, FieldRepr::from_limbs([1, 0, 0, 0]));
+        assert_eq!(five << 256, FieldRepr::default());
+        assert_eq!(five.bit_len(), 3);
+        assert!(five.bit(0) && !five.bit(1) && five.bit(2) && !five.bit(300));
+        assert_eq!(FieldRepr::default().bit_len(), 0);
+        assert_eq!(FieldRepr::from_field(&-Fr::ONE).bit_len(), Fr::NUM_BITS);
+        assert_eq!(FieldRepr::from_field(&-Fp::ONE).bit_len(), Fp::NUM_BITS);
+
+        for _ in 0..100 {
+            let a = Fr::random(&mut rng);
+            let repr = FieldRepr::from_field(&a);
+            assert_eq!(repr.into_field::<Fr>(), Some(a));
+            for n in [1, 31, 64, 65, 128, 200] {
+                assert_eq!(
+                    (repr << n) >> n,
+                    repr & (FieldRepr::from_limbs([!0; 4]) >> n)
+                );
+                assert_eq!(repr >> n << n, repr & (FieldRepr::from_limbs([!0; 4]) << n));
+            }
+            let doubled = (repr << 1).into_field::<Fr>();
+            assert!(doubled.is_none() || doubled == Some(a.double()));
+            assert_eq!(repr ^ repr, FieldRepr::default());
+            assert_eq!(repr | FieldRepr::default(), repr);
+        }
+        assert_eq!(FieldRepr::from_limbs([!0; 4]).into_field::<Fr>(), None);
+    }
+}
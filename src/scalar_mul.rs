@@ -0,0 +1,169 @@
+//! Variable-time single and double scalar multiplications.
+//!
+//! Verifiers usually multiply a handful of points by public scalars, one at a
+//! time, so neither the constant-time double-and-add of `Mul` nor the batch
+//! techniques used by provers are the best fit. The functions here leak the
+//! scalars through their timing and must only be used with public scalars.
+
+use crate::arithmetic::{CurveDoubling, CurveEndo};
+use ff::PrimeField;
+use group::Group;
+
+/// How to compute a single scalar multiplication.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MulStrategy {
+    /// The constant-time double-and-add of `Mul`.
+    DoubleAndAdd,
+    /// Fixed 4-bit windows over the full scalar.
+    Window4,
+    /// GLV decomposition into two half-size scalars, processed together with
+    /// 4-bit windows so that they share the doublings.
+    Glv4,
+}
+
+impl MulStrategy {
+    /// Returns `scalar * point` computed with this strategy.
+    pub fn mul<C: CurveEndo + CurveDoubling>(&self, point: &C, scalar: &C::ScalarExt) -> C {
+        match self {
+            MulStrategy::DoubleAndAdd => *point * scalar,
+            MulStrategy::Window4 => mul_window4(point, scalar),
+            MulStrategy::Glv4 => mul_glv4(point, scalar),
+        }
+    }
+}
+
+/// Returns `[0, P, 2P, ..., 15P]`.
+fn window_table<C: Group>(point: &C) -> [C; 16] {
+    let mut table = [C::identity(); 16];
+    let mut acc = C::identity();
+    for entry in table.iter_mut().skip(1) {
+        acc += point;
+        *entry = acc;
+    }
+    table
+}
+
+/// Returns `scalar * point` using fixed 4-bit windows, in variable time.
+pub fn mul_window4<C: CurveDoubling>(point: &C, scalar: &C::ScalarExt) -> C {
+    let table = window_table(point);
+
+    let mut acc = C::identity();
+    for byte in scalar.to_repr().as_ref().iter().rev() {
+        for nibble in [byte >> 4, byte & 0xf] {
+            acc = acc.double_n(4);
+            acc += table[nibble as usize];
+        }
+    }
+    acc
+}
+
+/// Returns `a * p + b * q` in variable time, using Shamir's trick: the 4-bit
+/// windows of both scalars are interleaved so that they share the doublings.
+/// This is the shape of ECDSA and Schnorr verification equations.
+pub fn multi_scalar_mul_2<C: CurveDoubling>(a: &C::ScalarExt, p: &C, b: &C::ScalarExt, q: &C) -> C {
+    let table_p = window_table(p);
+    let table_q = window_table(q);
+    let (a, b) = (a.to_repr(), b.to_repr());
+
+    let mut acc = C::identity();
+    for (byte_a, byte_b) in a.as_ref().iter().zip(b.as_ref()).rev() {
+        for shift in [4, 0] {
+            acc = acc.double_n(4);
+            acc += table_p[((byte_a >> shift) & 0xf) as usize];
+            acc += table_q[((byte_b >> shift) & 0xf) as usize];
+        }
+    }
+    acc
+}
+
+/// Returns `scalar * point` using the GLV endomorphism and 4-bit windows, in
+/// variable time.
+pub fn mul_glv4<C: CurveEndo + CurveDoubling>(point: &C, scalar: &C::ScalarExt) -> C {
+    // scalar = ±k1 ∓ ZETA * k2, and ZETA * P = endo(P).
+    let (k1, k1_neg, k2, k2_neg) = C::decompose_scalar(scalar);
+    let p1 = if k1_neg { -*point } else { *point };
+    let p2 = if k2_neg { point.endo() } else { -point.endo() };
+    let table1 = window_table(&p1);
+    let table2 = window_table(&p2);
+
+    let mut acc = C::identity();
+    for i in (0..32).rev() {
+        acc = acc.double_n(4);
+        acc += table1[((k1 >> (4 * i)) & 0xf) as usize];
+        acc += table2[((k2 >> (4 * i)) & 0xf) as usize];
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{multi_scalar_mul_2, MulStrategy};
+    use crate::arithmetic::{CurveDoubling, CurveEndo};
+    use crate::{bn256, grumpkin, pasta};
+    use ff::Field;
+    use group::Group;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn mul_strategy_test<C: CurveEndo + CurveDoubling>(rng: &mut XorShiftRng) {
+        let strategies = [
+            MulStrategy::DoubleAndAdd,
+            MulStrategy::Window4,
+            MulStrategy::Glv4,
+        ];
+        let scalars = [
+            C::ScalarExt::ZERO,
+            C::ScalarExt::ONE,
+            -C::ScalarExt::ONE,
+            C::ScalarExt::random(&mut *rng),
+        ];
+        for point in [C::identity(), C::generator(), C::random(&mut *rng)] {
+            for scalar in scalars {
+                for strategy in strategies {
+                    assert_eq!(strategy.mul(&point, &scalar), point * scalar);
+                }
+            }
+        }
+        for _ in 0..100 {
+            let point = C::random(&mut *rng);
+            let scalar = C::ScalarExt::random(&mut *rng);
+            assert_eq!(MulStrategy::Glv4.mul(&point, &scalar), point * scalar);
+        }
+    }
+
+    fn multi_scalar_mul_2_test<C: CurveEndo + CurveDoubling>(rng: &mut XorShiftRng) {
+        let zero = C::ScalarExt::ZERO;
+        let p = C::random(&mut *rng);
+        let q = C::random(&mut *rng);
+        assert_eq!(multi_scalar_mul_2(&zero, &p, &zero, &q), C::identity());
+        assert_eq!(multi_scalar_mul_2(&-C::ScalarExt::ONE, &p, &zero, &q), -p);
+        for _ in 0..20 {
+            let a = C::ScalarExt::random(&mut *rng);
+            let b = C::ScalarExt::random(&mut *rng);
+            assert_eq!(multi_scalar_mul_2(&a, &p, &b, &q), p * a + q * b);
+            assert_eq!(multi_scalar_mul_2(&a, &p, &b, &p), p * (a + b));
+        }
+    }
+
+    #[test]
+    fn test_multi_scalar_mul_2() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        multi_scalar_mul_2_test::<bn256::G1>(&mut rng);
+        multi_scalar_mul_2_test::<pasta::Ep>(&mut rng);
+    }
+
+    #[test]
+    fn test_mul_strategies() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        mul_strategy_test::<bn256::G1>(&mut rng);
+        mul_strategy_test::<grumpkin::G1>(&mut rng);
+        mul_strategy_test::<pasta::Ep>(&mut rng);
+        mul_strategy_test::<pasta::Eq>(&mut rng);
+    }
+}
@@ -0,0 +1,314 @@
+//! Hierarchical deterministic key derivation over secp256k1, as in BIP-32.
+//!
+//! This module provides the primitives of the specification: `parse256`,
+//! `ser256` and `serP`, HMAC-SHA512, and the private and public child key
+//! derivations with their rules for invalid keys. It does not implement the
+//! base58 serialization of extended keys, which needs the key fingerprints
+//! (HASH160 of the public key) and is left to the caller.
+
+use crate::group::{prime::PrimeCurveAffine, Curve, Group};
+use crate::secp256k1::{Fq, Secp256k1, Secp256k1Affine};
+use crate::secret::Secret;
+use ff::{Field, PrimeField};
+use sha2::{Digest, Sha512};
+use std::fmt;
+
+/// Offset of the hardened child indices.
+pub const HARDENED: u32 = 1 << 31;
+
+/// The chain code of an extended key.
+pub type ChainCode = [u8; 32];
+
+/// Why a key derivation failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DerivationError {
+    /// The derived key is invalid: its tweak is not smaller than the order
+    /// of the curve, or the key is zero (or the identity). BIP-32 asks to
+    /// proceed with the next index, which happens with probability below
+    /// 2^-127.
+    InvalidKey,
+    /// A hardened child was requested from a public key.
+    HardenedFromPublic,
+    /// The seed is not between 16 and 64 bytes long.
+    InvalidSeedLength,
+}
+
+impl fmt::Display for DerivationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DerivationError::InvalidKey => write!(f, "derived key is invalid"),
+            DerivationError::HardenedFromPublic => {
+                write!(f, "cannot derive a hardened child from a public key")
+            }
+            DerivationError::InvalidSeedLength => write!(f, "seed must be 16 to 64 bytes long"),
+        }
+    }
+}
+
+impl std::error::Error for DerivationError {}
+
+/// Returns the HMAC-SHA512 of the concatenation of `data` under `key`.
+pub fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    const BLOCK_SIZE: usize = 128;
+
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..64].copy_from_slice(&Sha512::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha512::new().chain_update(block.map(|byte| byte ^ 0x36));
+    for data in data {
+        inner.update(data);
+    }
+    let outer = Sha512::new()
+        .chain_update(block.map(|byte| byte ^ 0x5c))
+        .chain_update(inner.finalize())
+        .finalize();
+
+    let mut mac = [0u8; 64];
+    mac.copy_from_slice(&outer);
+    mac
+}
+
+/// Interprets `bytes` as a big-endian integer, as `parse256` does. Fails if
+/// the integer is not smaller than the order of the curve.
+pub fn parse256(bytes: &[u8; 32]) -> Option<Fq> {
+    let mut repr = *bytes;
+    repr.reverse();
+    Option::from(Fq::from_repr(repr))
+}
+
+/// Serializes `k` as a big-endian integer, as `ser256` does.
+pub fn ser256(k: &Fq) -> [u8; 32] {
+    let mut bytes = k.to_repr();
+    bytes.reverse();
+    bytes
+}
+
+/// Serializes `p` in the SEC1 compressed form, as `serP` does. The identity
+/// is never a valid key, and serializes to 33 zero bytes.
+pub fn ser_p(p: &Secp256k1Affine) -> [u8; 33] {
+    let mut bytes = [0u8; 33];
+    if !bool::from(p.is_identity()) {
+        bytes[0] = 0x02 | p.y.is_odd().unwrap_u8();
+        bytes[1..].copy_from_slice(&p.x.to_repr());
+        bytes[1..].reverse();
+    }
+    bytes
+}
+
+/// Splits the output of the HMAC into the tweak `I_L` and the chain code
+/// `I_R`, failing if the tweak is not a canonical scalar.
+fn split(mac: &[u8; 64]) -> Result<(Fq, ChainCode), DerivationError> {
+    let mut tweak = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    tweak.copy_from_slice(&mac[..32]);
+    chain_code.copy_from_slice(&mac[32..]);
+    let tweak = parse256(&tweak).ok_or(DerivationError::InvalidKey)?;
+    Ok((tweak, chain_code))
+}
+
+/// An extended private key: a secret key and its chain code. Both are
+/// redacted from `Debug` with the `redact-secrets` feature.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct ExtendedPrivateKey {
+    pub key: Fq,
+    pub chain_code: ChainCode,
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("key", &Secret::new(self.key))
+            .field("chain_code", &Secret::new(self.chain_code))
+            .finish()
+    }
+}
+
+/// An extended public key: a public key and its chain code.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    pub key: Secp256k1Affine,
+    pub chain_code: ChainCode,
+}
+
+impl ExtendedPrivateKey {
+    /// Derives the master key from `seed`.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, DerivationError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(DerivationError::InvalidSeedLength);
+        }
+        let (key, chain_code) = split(&hmac_sha512(b"Bitcoin seed", &[seed]))?;
+        if bool::from(key.is_zero()) {
+            return Err(DerivationError::InvalidKey);
+        }
+        Ok(Self { key, chain_code })
+    }
+
+    /// Derives the child of this key at `index`, as `CKDpriv` does. Indices
+    /// from `HARDENED` on give hardened children.
+    pub fn derive_child(&self, index: u32) -> Result<Self, DerivationError> {
+        let mac = if index >= HARDENED {
+            hmac_sha512(
+                &self.chain_code,
+                &[&[0], &ser256(&self.key), &index.to_be_bytes()],
+            )
+        } else {
+            let public = (Secp256k1::generator() * self.key).to_affine();
+            hmac_sha512(&self.chain_code, &[&ser_p(&public), &index.to_be_bytes()])
+        };
+        let (tweak, chain_code) = split(&mac)?;
+        let key = tweak + self.key;
+        if bool::from(key.is_zero()) {
+            return Err(DerivationError::InvalidKey);
+        }
+        Ok(Self { key, chain_code })
+    }
+
+    /// Derives the descendant of this key along `path`.
+    pub fn derive_path(&self, path: &[u32]) -> Result<Self, DerivationError> {
+        path.iter()
+            .try_fold(*self, |key, &index| key.derive_child(index))
+    }
+
+    /// Returns the extended public key of this key, as `N` does.
+    pub fn public_key(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            key: (Secp256k1::generator() * self.key).to_affine(),
+            chain_code: self.chain_code,
+        }
+    }
+}
+
+impl ExtendedPublicKey {
+    /// Derives the non-hardened child of this key at `index`, as `CKDpub`
+    /// does.
+    pub fn derive_child(&self, index: u32) -> Result<Self, DerivationError> {
+        if index >= HARDENED {
+            return Err(DerivationError::HardenedFromPublic);
+        }
+        let mac = hmac_sha512(&self.chain_code, &[&ser_p(&self.key), &index.to_be_bytes()]);
+        let (tweak, chain_code) = split(&mac)?;
+        let key = (Secp256k1::generator() * tweak + self.key).to_affine();
+        if bool::from(key.is_identity()) {
+            return Err(DerivationError::InvalidKey);
+        }
+        Ok(Self { key, chain_code })
+    }
+
+    /// Derives the descendant of this key along `path`, which must not
+    /// contain hardened indices.
+    pub fn derive_path(&self, path: &[u32]) -> Result<Self, DerivationError> {
+        path.iter()
+            .try_fold(*self, |key, &index| key.derive_child(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn scalar(s: &str) -> Fq {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex(s));
+        parse256(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_hmac_sha512() {
+        // RFC 4231, test cases 2 and 6.
+        assert_eq!(
+            hmac_sha512(b"Jefe", &[b"what do ya want ", b"for nothing?"]).to_vec(),
+            hex(
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+                 9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+            )
+        );
+        assert_eq!(
+            hmac_sha512(
+                &[0xaa; 131],
+                &[b"Test Using Larger Than Block-Size Key - Hash Key First"]
+            )
+            .to_vec(),
+            hex(
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+                 6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse256() {
+        assert_eq!(parse256(&[0xff; 32]), None);
+        let k = scalar("e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35");
+        assert_eq!(parse256(&ser256(&k)), Some(k));
+        assert_eq!(ser_p(&Secp256k1Affine::identity()), [0; 33]);
+    }
+
+    #[test]
+    fn test_vector_1() {
+        // BIP-32, test vector 1.
+        let master =
+            ExtendedPrivateKey::from_seed(&hex("000102030405060708090a0b0c0d0e0f")).unwrap();
+        let vectors = [
+            (
+                vec![],
+                "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+                "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2",
+            ),
+            (
+                vec![HARDENED],
+                "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+                "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                "035a784662a4a20a65bf6aab9ae98a6c068a81c52e4b032c0fb5400c706cfccc56",
+            ),
+            (
+                vec![HARDENED, 1],
+                "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+                "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                "03501e454bf00751f24b1b489aa925215d66af2234e3891c3b21a52bedb3cd711c",
+            ),
+            (
+                vec![HARDENED, 1, HARDENED + 2],
+                "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+                "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+                "0357bfe1e341d01c69fe5654309956cbea516822fba8a601743a012a7896ee8dc2",
+            ),
+        ];
+        for (path, key, chain_code, public) in vectors {
+            let child = master.derive_path(&path).unwrap();
+            assert_eq!(child.key, scalar(key));
+            assert_eq!(child.chain_code.to_vec(), hex(chain_code));
+            assert_eq!(ser_p(&child.public_key().key).to_vec(), hex(public));
+        }
+    }
+
+    #[test]
+    fn test_public_derivation() {
+        let master = ExtendedPrivateKey::from_seed(&[7; 32]).unwrap();
+        let parent = master.derive_child(HARDENED).unwrap();
+        let path = [0, 1, 2, 1000];
+        assert_eq!(
+            parent.public_key().derive_path(&path).unwrap(),
+            parent.derive_path(&path).unwrap().public_key()
+        );
+        assert_eq!(
+            parent.public_key().derive_child(HARDENED),
+            Err(DerivationError::HardenedFromPublic)
+        );
+        assert_eq!(
+            ExtendedPrivateKey::from_seed(&[0; 8]),
+            Err(DerivationError::InvalidSeedLength)
+        );
+    }
+}
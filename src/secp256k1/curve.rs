@@ -2,6 +2,7 @@ use crate::ff::WithSmallOrderMulGroup;
 use crate::ff::{Field, PrimeField};
 use crate::group::{prime::PrimeCurveAffine, Curve, Group as _, GroupEncoding};
 use crate::hash_to_curve::svdw_hash_to_curve;
+use crate::normalize::Normalization;
 use crate::secp256k1::Fp;
 use crate::secp256k1::Fq;
 use crate::{Coordinates, CurveAffine, CurveExt};
@@ -15,6 +16,12 @@ use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 #[cfg(feature = "derive_serde")]
 use serde::{Deserialize, Serialize};
 
+impl Secp256k1 {
+    /// The cofactor of the prime-order subgroup, as little-endian 64-bit
+    /// limbs.
+    pub const COFACTOR: [u64; 4] = [1, 0, 0, 0];
+}
+
 impl group::cofactor::CofactorGroup for Secp256k1 {
     type Subgroup = Secp256k1;
 
@@ -72,9 +79,65 @@ impl Secp256k1 {
     const SVDW_Z: Fp = Fp::ONE;
 }
 
+/// Returns the constants of secp256k1 and of its fields.
+pub fn params() -> crate::params::CurveParams {
+    crate::params::CurveParams::of::<Secp256k1>(None)
+}
+
+impl Secp256k1Affine {
+    /// Returns the point with the given x-coordinate and an even
+    /// y-coordinate, as in `lift_x` of BIP-340. Fails if `x` is not the
+    /// x-coordinate of a point on the curve.
+    pub fn lift_x(x: &Fp) -> CtOption<Self> {
+        let y2 = x.square() * x + SECP_B;
+        y2.sqrt().map(|y| Secp256k1Affine {
+            x: *x,
+            y: Normalization::EvenY.normalize(&y).0,
+        })
+    }
+
+    /// Returns true if the y-coordinate of this point is even. The identity
+    /// is considered to have an even y-coordinate.
+    pub fn has_even_y(&self) -> Choice {
+        !self.y.is_odd()
+    }
+
+    /// Returns the point with the same x-coordinate and an even
+    /// y-coordinate, together with a flag that is set when the point had to
+    /// be negated.
+    pub fn to_even_y(&self) -> (Self, Choice) {
+        Normalization::EvenY.normalize_point(self)
+    }
+
+    /// Tweaks the x-only public key `self` by `tweak` as in BIP-341
+    /// `taproot_tweak_pubkey`: the point is first normalized to an even
+    /// y-coordinate and then `tweak * G` is added. Returns the output key
+    /// normalized to an even y-coordinate along with its parity (set when
+    /// the output key has an odd y-coordinate). Fails if the output key is
+    /// the identity.
+    pub fn tweak_add(&self, tweak: &Fq) -> CtOption<(Self, Choice)> {
+        let (p, _) = self.to_even_y();
+        let q = (p + Secp256k1::generator() * tweak).to_affine();
+        CtOption::new(q.to_even_y(), !q.is_identity())
+    }
+
+    /// Tweaks the secret key `sk` to match `tweak_add` on its x-only public
+    /// key, as in BIP-341 `taproot_tweak_seckey`. The secret key is negated
+    /// first if its public key has an odd y-coordinate. Fails if the
+    /// resulting secret key is zero.
+    pub fn tweak_secret_key(sk: &Fq, tweak: &Fq) -> CtOption<Fq> {
+        let p = (Secp256k1::generator() * sk).to_affine();
+        let sk = Fq::conditional_select(sk, &-sk, Normalization::EvenY.needs_negation(&p.y));
+        let sk = sk + tweak;
+        CtOption::new(sk, !sk.is_zero())
+    }
+}
+
 #[test]
 fn test_curve() {
     crate::tests::curve::curve_tests::<Secp256k1>();
+    crate::tests::curve::batch_add_test::<Secp256k1>();
+    crate::tests::curve::group_traits_test::<Secp256k1>();
 }
 
 #[test]
@@ -82,6 +145,16 @@ fn test_hash_to_curve() {
     crate::tests::curve::hash_to_curve_test::<Secp256k1>();
 }
 
+#[test]
+fn test_derive_generators() {
+    crate::tests::curve::derive_generators_test::<Secp256k1>();
+}
+
+#[test]
+fn test_hash_to_scalar() {
+    crate::tests::curve::hash_to_scalar_test::<Secp256k1>();
+}
+
 #[test]
 fn test_serialization() {
     crate::tests::curve::random_serialization_test::<Secp256k1>();
@@ -89,6 +162,39 @@ fn test_serialization() {
     crate::tests::curve::random_serde_test::<Secp256k1>();
 }
 
+#[test]
+fn test_taproot_tweak() {
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let sk = Fq::random(&mut rng);
+        let tweak = Fq::random(&mut rng);
+        let p = (Secp256k1::generator() * sk).to_affine();
+
+        let (even, negated) = p.to_even_y();
+        assert!(bool::from(even.has_even_y()));
+        assert_eq!(even.x, p.x);
+        assert_eq!(bool::from(negated), bool::from(!p.has_even_y()));
+        assert_eq!(Secp256k1Affine::lift_x(&p.x).unwrap(), even);
+
+        let (q, parity) = p.tweak_add(&tweak).unwrap();
+        assert!(bool::from(q.has_even_y()));
+        // The tweak only depends on the x-coordinate of the internal key.
+        assert_eq!((-p).tweak_add(&tweak).unwrap().0, q);
+
+        let tweaked_sk = Secp256k1Affine::tweak_secret_key(&sk, &tweak).unwrap();
+        let tweaked_pk = (Secp256k1::generator() * tweaked_sk).to_affine();
+        assert_eq!(tweaked_pk.x, q.x);
+        assert_eq!(bool::from(parity), bool::from(!tweaked_pk.has_even_y()));
+    }
+}
+
 #[test]
 fn test_endo_consistency() {
     let g = Secp256k1::generator();
@@ -182,6 +182,9 @@ impl ff::Field for Fp {
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
     fn invert(&self) -> CtOption<Self> {
+        #[cfg(feature = "op-count")]
+        crate::stats::record_invert();
+
         let tmp = self.pow_vartime([
             0xfffffffefffffc2d,
             0xffffffffffffffff,
@@ -1,5 +1,6 @@
 use crate::arithmetic::{adc, mac, sbb};
 use crate::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use crate::sqrt::SqrtPrecomputation;
 use crate::{
     field_arithmetic, field_bits, field_common, field_specific, impl_add_binop_specify_output,
     impl_binops_additive, impl_binops_additive_specify_output, impl_binops_multiplicative,
@@ -182,6 +183,9 @@ impl ff::Field for Fq {
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
     fn invert(&self) -> CtOption<Self> {
+        #[cfg(feature = "op-count")]
+        crate::stats::record_invert();
+
         let tmp = self.pow_vartime([
             0xbfd25e8cd036413f,
             0xbaaedce6af48a03b,
@@ -211,14 +215,10 @@ impl ff::Field for Fq {
     }
 
     fn sqrt(&self) -> CtOption<Self> {
-        let tm1d2 = [
-            0x777fa4bd19a06c82,
-            0xfd755db9cd5e9140,
-            0xffffffffffffffff,
-            0x01ffffffffffffff,
-        ];
-
-        ff::helpers::sqrt_tonelli_shanks(self, tm1d2)
+        lazy_static::lazy_static! {
+            static ref SQRT: SqrtPrecomputation<Fq> = SqrtPrecomputation::new();
+        }
+        SQRT.sqrt(self)
     }
 
     fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
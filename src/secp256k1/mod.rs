@@ -1,3 +1,4 @@
+pub mod bip32;
 mod curve;
 mod fp;
 mod fq;
@@ -1,6 +1,7 @@
 use crate::ff::WithSmallOrderMulGroup;
 use crate::ff::{Field, PrimeField};
 use crate::group::{prime::PrimeCurveAffine, Curve, Group as _, GroupEncoding};
+use crate::hash_to_curve::sswu_hash_to_curve_sha256;
 use crate::secp256r1::Fp;
 use crate::secp256r1::Fq;
 use crate::{Coordinates, CurveAffine, CurveExt};
@@ -14,6 +15,14 @@ use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 #[cfg(feature = "derive_serde")]
 use serde::{Deserialize, Serialize};
 
+impl Secp256r1 {
+    /// The cofactor of the prime-order subgroup, as little-endian 64-bit
+    /// limbs.
+    pub const COFACTOR: [u64; 4] = [1, 0, 0, 0];
+
+    pub(crate) const SSWU_Z: Fp = Fp::from_raw([10, 0, 0, 0]).neg();
+}
+
 impl group::cofactor::CofactorGroup for Secp256r1 {
     type Subgroup = Secp256r1;
 
@@ -75,12 +84,65 @@ new_curve_impl!(
     SECP_A,
     SECP_B,
     "secp256r1",
-    |_, _| unimplemented!(),
+    |_, domain_prefix| sswu_hash_to_curve_sha256(
+        "P256_XMD:SHA-256_SSWU_RO_",
+        domain_prefix,
+        Secp256r1::SSWU_Z,
+    ),
 );
 
+/// Returns the constants of secp256r1 and of its fields.
+pub fn params() -> crate::params::CurveParams {
+    crate::params::CurveParams::of::<Secp256r1>(None)
+}
+
 #[test]
 fn test_curve() {
     crate::tests::curve::curve_tests::<Secp256r1>();
+    crate::tests::curve::batch_add_test::<Secp256r1>();
+    crate::tests::curve::group_traits_test::<Secp256r1>();
+}
+
+#[test]
+fn test_curve_constant_a() {
+    // The doubling and addition formulas skip the product by `a = -3`.
+    assert!(SECP_A.eq_vartime(&Fp::minus_three()));
+    assert_eq!(Fp::minus_three(), -Fp::from(3));
+    assert!(!SECP_A.eq_vartime(&Fp::ZERO));
+}
+
+#[test]
+fn test_hash_to_curve() {
+    crate::tests::curve::hash_to_curve_test::<Secp256r1>();
+}
+
+#[test]
+fn test_hash_to_curve_vectors() {
+    use crate::tests::fe_from_str;
+
+    // P256_XMD:SHA-256_SSWU_RO_ test vectors from appendix J.1.1 of RFC 9380.
+    let hasher = Secp256r1::hash_to_curve("QUUX-V01-CS02");
+    for (message, x, y) in [
+        (
+            "",
+            "0x2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+            "0x8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+        ),
+        (
+            "abc",
+            "0x0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f",
+            "0x5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e",
+        ),
+        (
+            "abcdef0123456789",
+            "0x65038ac8f2b1def042a5df0b33b1f4eca6bff7cb0f9c6c1526811864e544ed80",
+            "0xcad44d40a656e7aff4002a8de287abc8ae0482b5ae825822bb870d6df9b56ca3",
+        ),
+    ] {
+        let point = hasher(message.as_bytes()).to_affine();
+        assert_eq!(point.x, fe_from_str(x));
+        assert_eq!(point.y, fe_from_str(y));
+    }
 }
 
 #[test]
@@ -200,6 +200,9 @@ impl ff::Field for Fp {
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
     fn invert(&self) -> CtOption<Self> {
+        #[cfg(feature = "op-count")]
+        crate::stats::record_invert();
+
         let tmp = self.pow_vartime([
             0xfffffffffffffffd,
             0x00000000ffffffff,
@@ -322,6 +325,19 @@ mod test {
     use ff::Field;
     use rand_core::OsRng;
 
+    #[test]
+    fn test_legendre() {
+        assert!(bool::from(Fp::zero().is_quadratic_residue()));
+        for _ in 0..1000 {
+            let a = Fp::random(OsRng);
+            assert_eq!(
+                bool::from(a.is_quadratic_residue()),
+                bool::from(a.sqrt().is_some())
+            );
+            assert!(bool::from(a.square().is_quadratic_residue()));
+        }
+    }
+
     #[test]
     fn test_sqrt() {
         // NB: TWO_INV is standing in as a "random" field element
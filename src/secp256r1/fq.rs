@@ -1,5 +1,6 @@
 use crate::arithmetic::{adc, mac, sbb};
 use crate::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use crate::sqrt::SqrtPrecomputation;
 use core::convert::TryInto;
 use core::fmt;
 use core::ops::{Add, Mul, Neg, Sub};
@@ -177,6 +178,9 @@ impl ff::Field for Fq {
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
     fn invert(&self) -> CtOption<Self> {
+        #[cfg(feature = "op-count")]
+        crate::stats::record_invert();
+
         let tmp = self.pow_vartime([
             0xf3b9cac2fc63254f,
             0xbce6faada7179e84,
@@ -206,15 +210,10 @@ impl ff::Field for Fq {
     }
 
     fn sqrt(&self) -> CtOption<Self> {
-        // 7fffffff800000007fffffffffffffffde737d56d38bcf4279dce5617e3192a
-        let tm1d2 = [
-            0x279dce5617e3192a,
-            0xfde737d56d38bcf4,
-            0x07ffffffffffffff,
-            0x7fffffff8000000,
-        ];
-
-        ff::helpers::sqrt_tonelli_shanks(self, tm1d2)
+        lazy_static::lazy_static! {
+            static ref SQRT: SqrtPrecomputation<Fq> = SqrtPrecomputation::new();
+        }
+        SQRT.sqrt(self)
     }
 
     fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
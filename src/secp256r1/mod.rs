@@ -1,6 +1,7 @@
 mod curve;
 mod fp;
 mod fq;
+pub mod oprf;
 
 pub use curve::*;
 pub use fp::*;
@@ -0,0 +1,277 @@
+//! The OPRF mode of the P256-SHA256 ciphersuite of RFC 9497.
+//!
+//! A client blinds its input with `blind`, the server evaluates the blinded
+//! element under its secret key with `blind_evaluate`, and the client removes
+//! the blind with `finalize`. The output is the one `evaluate` computes from
+//! the input and the secret key, and the server does not learn the input.
+//! Hashing to the group and to scalars follows RFC 9380 with SHA-256, and
+//! elements are serialized in the SEC1 compressed form.
+//!
+//! The verifiable (VOPRF) and partially oblivious (POPRF) modes are not
+//! implemented. The other ciphersuites of the RFC are over curves this crate
+//! does not have, and secp256k1 is not one of them.
+
+use crate::group::{prime::PrimeCurveAffine, Curve, Group};
+use crate::hash_to_curve::{hash_to_scalar_sha256, sswu_hash_to_curve_sha256_dst};
+use crate::secp256r1::{Fp, Fq, Secp256r1, Secp256r1Affine};
+use crate::CurveAffine;
+use ff::{Field, PrimeField};
+use rand_core::RngCore;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// The identifier of the ciphersuite.
+pub const IDENTIFIER: &str = "P256-SHA256";
+
+/// `"OPRFV1-" || I2OSP(modeOPRF, 1) || "-" || identifier`.
+const CONTEXT: &[u8] = b"OPRFV1-\x00-P256-SHA256";
+
+/// The errors of RFC 9497.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OprfError {
+    /// `DeriveKeyPair` found no nonzero key in 256 attempts.
+    DeriveKeyPair,
+    /// The input hashes to the identity, or is longer than 65535 bytes.
+    InvalidInput,
+    /// The bytes do not encode a nonzero scalar or a non-identity element.
+    Deserialize,
+}
+
+impl fmt::Display for OprfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OprfError::DeriveKeyPair => write!(f, "no key pair derived from the seed"),
+            OprfError::InvalidInput => write!(f, "invalid OPRF input"),
+            OprfError::Deserialize => write!(f, "invalid element or scalar encoding"),
+        }
+    }
+}
+
+impl std::error::Error for OprfError {}
+
+/// Returns `I2OSP(len(bytes), 2)`, failing for inputs longer than 65535 bytes.
+fn length_prefix(bytes: &[u8]) -> Result<[u8; 2], OprfError> {
+    u16::try_from(bytes.len())
+        .map(u16::to_be_bytes)
+        .map_err(|_| OprfError::InvalidInput)
+}
+
+fn hash_to_group(input: &[u8]) -> Secp256r1 {
+    let dst = [b"HashToGroup-", CONTEXT].concat();
+    sswu_hash_to_curve_sha256_dst(input, &dst, Secp256r1::SSWU_Z)
+}
+
+fn hash_to_scalar(input: &[u8], dst: &[u8]) -> Fq {
+    hash_to_scalar_sha256(input, dst)
+}
+
+/// Serializes `element` in the SEC1 compressed form. The identity is never
+/// a valid element, and serializes to 33 zero bytes.
+pub fn serialize_element(element: &Secp256r1Affine) -> [u8; 33] {
+    let mut bytes = [0u8; 33];
+    if !bool::from(element.is_identity()) {
+        bytes[0] = 0x02 | element.y.is_odd().unwrap_u8();
+        bytes[1..].copy_from_slice(&element.x.to_repr());
+        bytes[1..].reverse();
+    }
+    bytes
+}
+
+/// Deserializes an element in the SEC1 compressed form, rejecting the
+/// identity.
+pub fn deserialize_element(bytes: &[u8; 33]) -> Result<Secp256r1Affine, OprfError> {
+    let odd = match bytes[0] {
+        0x02 => false,
+        0x03 => true,
+        _ => return Err(OprfError::Deserialize),
+    };
+    let mut repr = [0u8; 32];
+    repr.copy_from_slice(&bytes[1..]);
+    repr.reverse();
+    let x = Option::<Fp>::from(Fp::from_repr(repr)).ok_or(OprfError::Deserialize)?;
+    let y2 = x.square() * x + Secp256r1Affine::a() * x + Secp256r1Affine::b();
+    let y = Option::<Fp>::from(y2.sqrt()).ok_or(OprfError::Deserialize)?;
+    let y = if bool::from(y.is_odd()) == odd { y } else { -y };
+    Ok(Secp256r1Affine { x, y })
+}
+
+/// Serializes `scalar` as a big-endian integer.
+pub fn serialize_scalar(scalar: &Fq) -> [u8; 32] {
+    let mut bytes = scalar.to_repr();
+    bytes.reverse();
+    bytes
+}
+
+/// Deserializes a big-endian scalar, rejecting zero and non-canonical
+/// encodings.
+pub fn deserialize_scalar(bytes: &[u8; 32]) -> Result<Fq, OprfError> {
+    let mut repr = *bytes;
+    repr.reverse();
+    Option::<Fq>::from(Fq::from_repr(repr))
+        .filter(|scalar| !bool::from(scalar.is_zero()))
+        .ok_or(OprfError::Deserialize)
+}
+
+/// Derives the secret key and the public key of the server from a 32-byte
+/// `seed` and a public `info` string.
+pub fn derive_key_pair(seed: &[u8; 32], info: &[u8]) -> Result<(Fq, Secp256r1Affine), OprfError> {
+    let dst = [b"DeriveKeyPair", CONTEXT].concat();
+    let mut derive_input = seed.to_vec();
+    derive_input.extend_from_slice(&length_prefix(info).map_err(|_| OprfError::DeriveKeyPair)?);
+    derive_input.extend_from_slice(info);
+
+    for counter in 0..=255u8 {
+        derive_input.push(counter);
+        let secret = hash_to_scalar(&derive_input, &dst);
+        derive_input.pop();
+        if !bool::from(secret.is_zero()) {
+            return Ok((secret, (Secp256r1Affine::generator() * secret).to_affine()));
+        }
+    }
+    Err(OprfError::DeriveKeyPair)
+}
+
+/// Blinds `input` with a random nonzero scalar, returning the blind, kept by
+/// the client, and the blinded element, sent to the server.
+pub fn blind(input: &[u8], mut rng: impl RngCore) -> Result<(Fq, Secp256r1Affine), OprfError> {
+    let blind = loop {
+        let blind = Fq::random(&mut rng);
+        if !bool::from(blind.is_zero()) {
+            break blind;
+        }
+    };
+    blind_with(input, &blind).map(|blinded| (blind, blinded))
+}
+
+/// Blinds `input` with the given `blind`, which must be a uniformly random
+/// nonzero scalar.
+pub fn blind_with(input: &[u8], blind: &Fq) -> Result<Secp256r1Affine, OprfError> {
+    let input_element = hash_to_group(input);
+    if bool::from(input_element.is_identity()) {
+        return Err(OprfError::InvalidInput);
+    }
+    Ok((input_element * blind).to_affine())
+}
+
+/// Evaluates the blinded element of a client under the secret key.
+pub fn blind_evaluate(secret: &Fq, blinded: &Secp256r1Affine) -> Secp256r1Affine {
+    (*blinded * secret).to_affine()
+}
+
+/// `Hash(I2OSP(len(input), 2) || input || I2OSP(len(element), 2) || element
+/// || "Finalize")`.
+fn finalize_hash(input: &[u8], element: &Secp256r1Affine) -> Result<[u8; 32], OprfError> {
+    let element = serialize_element(element);
+    Ok(Sha256::new()
+        .chain_update(length_prefix(input)?)
+        .chain_update(input)
+        .chain_update(length_prefix(&element)?)
+        .chain_update(element)
+        .chain_update(b"Finalize")
+        .finalize()
+        .into())
+}
+
+/// Removes the blind from the evaluated element and returns the output of
+/// the OPRF on `input`.
+pub fn finalize(
+    input: &[u8],
+    blind: &Fq,
+    evaluated: &Secp256r1Affine,
+) -> Result<[u8; 32], OprfError> {
+    let blind_inv = Option::<Fq>::from(blind.invert()).ok_or(OprfError::InvalidInput)?;
+    finalize_hash(input, &(*evaluated * blind_inv).to_affine())
+}
+
+/// Returns the output of the OPRF on `input` under the secret key, as the
+/// server computes it without a client.
+pub fn evaluate(secret: &Fq, input: &[u8]) -> Result<[u8; 32], OprfError> {
+    let input_element = hash_to_group(input);
+    if bool::from(input_element.is_identity()) {
+        return Err(OprfError::InvalidInput);
+    }
+    finalize_hash(input, &(input_element * secret).to_affine())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn hex<const N: usize>(s: &str) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_vectors() {
+        // The P256-SHA256 OPRF test vectors of RFC 9497, appendix A.
+        let (secret, _) = derive_key_pair(&[0xa3; 32], b"test key").unwrap();
+        assert_eq!(
+            serialize_scalar(&secret),
+            hex::<32>("159749d750713afe245d2d39ccfaae8381c53ce92d098a9375ee70739c7ac0bf")
+        );
+        let blind = deserialize_scalar(&hex(
+            "3338fa65ec36e0290022b48eb562889d89dbfa691d1cde91517fa222ed7ad364",
+        ))
+        .unwrap();
+
+        for (input, blinded, evaluated, output) in [
+            (
+                &[0x00][..],
+                "03723a1e5c09b8b9c18d1dcbca29e8007e95f14f4732d9346d490ffc195110368d",
+                "030de02ffec47a1fd53efcdd1c6faf5bdc270912b8749e783c7ca75bb412958832",
+                "a0b34de5fa4c5b6da07e72af73cc507cceeb48981b97b7285fc375345fe495dd",
+            ),
+            (
+                &[0x5a; 17][..],
+                "03cc1df781f1c2240a64d1c297b3f3d16262ef5d4cf102734882675c26231b0838",
+                "03a0395fe3828f2476ffcd1f4fe540e5a8489322d398be3c4e5a869db7fcb7c52c",
+                "c748ca6dd327f0ce85f4ae3a8cd6d4d5390bbb804c9e12dcf94f853fece3dcce",
+            ),
+        ] {
+            let blinded_element = blind_with(input, &blind).unwrap();
+            assert_eq!(serialize_element(&blinded_element), hex::<33>(blinded));
+            assert_eq!(deserialize_element(&hex(blinded)).unwrap(), blinded_element);
+
+            let evaluated_element = blind_evaluate(&secret, &blinded_element);
+            assert_eq!(serialize_element(&evaluated_element), hex::<33>(evaluated));
+
+            let output = hex::<32>(output);
+            assert_eq!(finalize(input, &blind, &evaluated_element), Ok(output));
+            assert_eq!(evaluate(&secret, input), Ok(output));
+        }
+    }
+
+    #[test]
+    fn test_oprf() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let (secret, public) = derive_key_pair(&[7; 32], b"").unwrap();
+        assert_eq!(public, (Secp256r1Affine::generator() * secret).to_affine());
+
+        let input = b"oprf input";
+        let (blind, blinded) = blind(input, &mut rng).unwrap();
+        let evaluated = blind_evaluate(&secret, &blinded);
+        let output = finalize(input, &blind, &evaluated).unwrap();
+        assert_eq!(evaluate(&secret, input), Ok(output));
+        assert_ne!(evaluate(&secret, b"other"), Ok(output));
+
+        assert_eq!(
+            evaluate(&secret, &[0; 1 << 16]),
+            Err(OprfError::InvalidInput)
+        );
+        assert_eq!(
+            deserialize_element(&serialize_element(&Secp256r1Affine::identity())),
+            Err(OprfError::Deserialize)
+        );
+        assert_eq!(deserialize_scalar(&[0; 32]), Err(OprfError::Deserialize));
+        assert_eq!(deserialize_scalar(&[0xff; 32]), Err(OprfError::Deserialize));
+    }
+}
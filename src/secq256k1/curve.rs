@@ -51,6 +51,17 @@ new_curve_impl!(
     |curve_id, domain_prefix| svdw_hash_to_curve(curve_id, domain_prefix, Secq256k1::SVDW_Z),
 );
 
+/// Returns the constants of secq256k1 and of its fields.
+pub fn params() -> crate::params::CurveParams {
+    crate::params::CurveParams::of::<Secq256k1>(None)
+}
+
+impl Secq256k1 {
+    /// The cofactor of the prime-order subgroup, as little-endian 64-bit
+    /// limbs.
+    pub const COFACTOR: [u64; 4] = [1, 0, 0, 0];
+}
+
 impl group::cofactor::CofactorGroup for Secq256k1 {
     type Subgroup = Secq256k1;
 
@@ -87,6 +98,8 @@ mod tests {
     #[test]
     fn test_curve() {
         crate::tests::curve::curve_tests::<Secq256k1>();
+        crate::tests::curve::batch_add_test::<Secq256k1>();
+        crate::tests::curve::group_traits_test::<Secq256k1>();
     }
 
     #[test]
@@ -1,4 +1,6 @@
 mod curve;
 
+// Reexported from secp256k1: a secp256k1 scalar is a secq256k1 coordinate,
+// with no conversion, and the other way around.
 pub use crate::secp256k1::{Fp as Fq, Fq as Fp};
 pub use curve::*;
@@ -0,0 +1,85 @@
+//! A wrapper marking values, typically scalars, as secret.
+//!
+//! With the `redact-secrets` feature, `Debug` and `Display` of a `Secret`
+//! print a placeholder instead of the value, so that secret keys do not end up
+//! in logs by accident. Points and other public values are not affected.
+//! Without the feature, the wrapper formats as the value it holds.
+
+use core::fmt;
+
+/// A secret value, whose formatting is redacted with `redact-secrets`.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    /// Returns a reference to the secret value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Secret<T> {
+    #[cfg(feature = "redact-secrets")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+
+    #[cfg(not(feature = "redact-secrets"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Secret").field(&self.0).finish()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Secret<T> {
+    #[cfg(feature = "redact-secrets")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+
+    #[cfg(not(feature = "redact-secrets"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Secret;
+    use crate::bn256::{Fr, G1};
+    use group::Group;
+
+    #[test]
+    fn test_secret_formatting() {
+        let scalar = Fr::from(0x1234);
+        let secret = Secret::new(scalar);
+        assert_eq!(*secret.expose(), scalar);
+
+        let debug = format!("{secret:?}");
+        let display = format!("{}", Secret::new(42));
+        if cfg!(feature = "redact-secrets") {
+            assert_eq!(debug, "Secret([REDACTED])");
+            assert_eq!(display, "[REDACTED]");
+        } else {
+            assert_eq!(debug, format!("Secret({scalar:?})"));
+            assert_eq!(display, "42");
+        }
+
+        // Public values keep their full output.
+        let point = G1::generator() * scalar;
+        assert!(!format!("{point:?}").contains("REDACTED"));
+    }
+}
@@ -0,0 +1,167 @@
+//! Deterministic pseudorandom field elements and points, for test data.
+//!
+//! `random_from_seed(seed, index)` returns the same value on every machine, so
+//! that tests and differential fuzzers can share inputs as a seed and a range
+//! of indices rather than as captured RNG state. The values are derived by
+//! hashing the seed and the index with BLAKE2b, and are not suitable as
+//! secrets.
+//!
+//! The values are not frozen across versions of this crate: points go through
+//! the hash to curve of each curve, which may change, or through a multiple of
+//! the generator where there is none yet. Golden files should pin the version
+//! or store the values themselves.
+
+use crate::{bn256, grumpkin, pasta, secp256k1, secp256r1, secq256k1, CurveExt};
+use ff::FromUniformBytes;
+use group::Group;
+
+/// Types with deterministic pseudorandom values indexed by a seed.
+pub trait SeededRandom: Sized {
+    /// Returns the `index`-th pseudorandom value for `seed`.
+    fn random_from_seed(seed: [u8; 32], index: u64) -> Self;
+}
+
+/// Domain of the hash to curve used for seeded points.
+const DOMAIN: &str = "halo2curves-seeded";
+
+/// Returns the 64-byte BLAKE2b hash of `seed || index`.
+fn seeded_bytes(seed: &[u8; 32], index: u64) -> [u8; 64] {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(64)
+        .personal(b"halo2curves_seed")
+        .to_state()
+        .update(seed)
+        .update(&index.to_le_bytes())
+        .finalize();
+    let mut bytes = [0u8; 64];
+    bytes.copy_from_slice(hash.as_bytes());
+    bytes
+}
+
+fn field_from_seed<F: FromUniformBytes<64>>(seed: &[u8; 32], index: u64) -> F {
+    F::from_uniform_bytes(&seeded_bytes(seed, index))
+}
+
+fn point_from_seed<C: CurveExt>(seed: &[u8; 32], index: u64) -> C {
+    let mut message = [0u8; 40];
+    message[..32].copy_from_slice(seed);
+    message[32..].copy_from_slice(&index.to_le_bytes());
+    C::hash_to_curve(DOMAIN)(&message)
+}
+
+macro_rules! impl_seeded_field {
+    ($($field:ty),*) => {
+        $(
+            impl SeededRandom for $field {
+                fn random_from_seed(seed: [u8; 32], index: u64) -> Self {
+                    field_from_seed(&seed, index)
+                }
+            }
+        )*
+    };
+}
+
+impl_seeded_field!(
+    bn256::Fq,
+    bn256::Fr,
+    bn256::Fq2,
+    secp256k1::Fp,
+    secp256k1::Fq,
+    secp256r1::Fp,
+    secp256r1::Fq,
+    pasta::Fp,
+    pasta::Fq
+);
+
+macro_rules! impl_seeded_point {
+    ($($curve:ty),*) => {
+        $(
+            impl SeededRandom for $curve {
+                fn random_from_seed(seed: [u8; 32], index: u64) -> Self {
+                    point_from_seed(&seed, index)
+                }
+            }
+        )*
+    };
+}
+
+impl_seeded_point!(
+    bn256::G1,
+    grumpkin::G1,
+    secp256k1::Secp256k1,
+    secp256r1::Secp256r1,
+    secq256k1::Secq256k1,
+    pasta::Ep,
+    pasta::Eq
+);
+
+// BN254 G2 has no hash to curve yet, so its points are multiples of the
+// generator by a seeded scalar.
+macro_rules! impl_seeded_point_by_scalar {
+    ($($curve:ty),*) => {
+        $(
+            impl SeededRandom for $curve {
+                fn random_from_seed(seed: [u8; 32], index: u64) -> Self {
+                    let scalar = <$curve as Group>::Scalar::random_from_seed(seed, index);
+                    <$curve as Group>::generator() * scalar
+                }
+            }
+        )*
+    };
+}
+
+impl_seeded_point_by_scalar!(bn256::G2);
+
+#[cfg(test)]
+mod tests {
+    use super::SeededRandom;
+    use crate::CurveAffine;
+    use crate::{bn256, grumpkin, pasta, secp256k1, secp256r1, secq256k1};
+    use group::Curve;
+    use std::fmt::Debug;
+
+    const SEED: [u8; 32] = [7; 32];
+
+    fn seeded_test<T: SeededRandom + PartialEq + Debug>() {
+        assert_eq!(T::random_from_seed(SEED, 3), T::random_from_seed(SEED, 3));
+        assert_ne!(T::random_from_seed(SEED, 3), T::random_from_seed(SEED, 4));
+        assert_ne!(
+            T::random_from_seed(SEED, 3),
+            T::random_from_seed([8; 32], 3)
+        );
+    }
+
+    #[test]
+    fn test_random_from_seed() {
+        seeded_test::<bn256::Fq>();
+        seeded_test::<bn256::Fr>();
+        seeded_test::<bn256::Fq2>();
+        seeded_test::<secp256k1::Fp>();
+        seeded_test::<secp256r1::Fq>();
+        seeded_test::<pasta::Fp>();
+        seeded_test::<bn256::G1>();
+        seeded_test::<bn256::G2>();
+        seeded_test::<grumpkin::G1>();
+        seeded_test::<secp256k1::Secp256k1>();
+        seeded_test::<secp256r1::Secp256r1>();
+        seeded_test::<secq256k1::Secq256k1>();
+        seeded_test::<pasta::Eq>();
+
+        let point = secp256k1::Secp256k1::random_from_seed(SEED, 0).to_affine();
+        assert!(bool::from(point.is_on_curve()));
+    }
+
+    #[test]
+    fn test_random_from_seed_golden() {
+        // BLAKE2b of 32 zero bytes and a zero index, reduced modulo r.
+        assert_eq!(
+            bn256::Fr::random_from_seed([0; 32], 0),
+            bn256::Fr::from_raw([
+                0xed2dc7dad680b781,
+                0x64a5fd1db5a11958,
+                0x2c099369adc06f9a,
+                0x1980c0be92a88907,
+            ])
+        );
+    }
+}
@@ -1,5 +1,33 @@
+//! Raw serialization of field and curve elements, and the headers of
+//! serialized precomputation tables.
+
+#![cfg_attr(
+    all(feature = "panic-lints", not(test)),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unreachable,
+        clippy::indexing_slicing
+    )
+)]
+
+use std::fmt;
 use std::io::{self, Read, Write};
 
+/// The error returned by the `TryFrom<&[u8]>` conversions when the bytes have
+/// the wrong length or do not encode a valid object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidEncoding;
+
+impl fmt::Display for InvalidEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid encoding")
+    }
+}
+
+impl std::error::Error for InvalidEncoding {}
+
 /// Trait for converting raw bytes to/from the internal representation of a type.
 /// For example, field elements are represented in Montgomery form and serialized/deserialized without Montgomery reduction.
 pub trait SerdeObject: Sized {
@@ -23,3 +51,46 @@ pub trait SerdeObject: Sized {
 
     fn write_raw<W: Write>(&self, writer: &mut W) -> io::Result<()>;
 }
+
+/// Writes the header of a serialized precomputation table: the four-byte
+/// `tag` of the table type, the format `version` as a little-endian `u16`,
+/// and the length-prefixed identifier of the curve the table belongs to.
+pub(crate) fn write_table_header<W: Write>(
+    writer: &mut W,
+    tag: &[u8; 4],
+    version: u16,
+    curve_id: &str,
+) -> io::Result<()> {
+    writer.write_all(tag)?;
+    writer.write_all(&version.to_le_bytes())?;
+    writer.write_all(&[curve_id.len() as u8])?;
+    writer.write_all(curve_id.as_bytes())
+}
+
+/// Reads a header written by `write_table_header`, failing unless it matches
+/// `tag`, `version` and `curve_id`, so that tables of another type, another
+/// format version or another curve are rejected rather than misread.
+pub(crate) fn read_table_header<R: Read>(
+    reader: &mut R,
+    tag: &[u8; 4],
+    version: u16,
+    curve_id: &str,
+) -> io::Result<()> {
+    let invalid = |msg| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+    let mut header = [0u8; 7];
+    reader.read_exact(&mut header)?;
+    let [t0, t1, t2, t3, v0, v1, id_len] = header;
+    if [t0, t1, t2, t3] != *tag {
+        return Err(invalid("unexpected table type"));
+    }
+    if u16::from_le_bytes([v0, v1]) != version {
+        return Err(invalid("unsupported table version"));
+    }
+    let mut id = vec![0u8; id_len as usize];
+    reader.read_exact(&mut id)?;
+    if id != curve_id.as_bytes() {
+        return Err(invalid("table belongs to another curve"));
+    }
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! Scalars carried as a sign and a magnitude.
+//!
+//! GLV decompositions and balance-style accounting naturally produce values
+//! like `-k`, where `k` is small. `SignedScalar` keeps the sign next to the
+//! magnitude so that multiplying a point negates the result instead of
+//! multiplying by the (large) field element `p - k`.
+
+use crate::ff::PrimeField;
+use crate::group::Group;
+use core::ops::{Mul, Neg};
+
+/// A scalar `(-1)^negative * magnitude`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SignedScalar<F> {
+    magnitude: F,
+    negative: bool,
+}
+
+impl<F: PrimeField> SignedScalar<F> {
+    pub fn new(magnitude: F, negative: bool) -> Self {
+        SignedScalar {
+            magnitude,
+            negative,
+        }
+    }
+
+    /// Returns the signed scalar from an unsigned magnitude and a sign, as
+    /// returned by `CurveEndo::decompose_scalar`.
+    pub fn from_u128(magnitude: u128, negative: bool) -> Self {
+        Self::new(F::from_u128(magnitude), negative)
+    }
+
+    pub fn from_i64(value: i64) -> Self {
+        Self::new(F::from(value.unsigned_abs()), value < 0)
+    }
+
+    pub fn magnitude(&self) -> F {
+        self.magnitude
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the field element this scalar represents.
+    pub fn to_field(&self) -> F {
+        if self.negative {
+            -self.magnitude
+        } else {
+            self.magnitude
+        }
+    }
+
+    /// Returns `self * point`, multiplying by the magnitude and negating the
+    /// result if needed.
+    pub fn mul_point<G: Group<Scalar = F>>(&self, point: &G) -> G {
+        let res = *point * self.magnitude;
+        if self.negative {
+            -res
+        } else {
+            res
+        }
+    }
+}
+
+impl<F: PrimeField> From<F> for SignedScalar<F> {
+    fn from(magnitude: F) -> Self {
+        Self::new(magnitude, false)
+    }
+}
+
+impl<F: PrimeField> Neg for SignedScalar<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(self.magnitude, !self.negative)
+    }
+}
+
+impl<F: PrimeField, G: Group<Scalar = F>> Mul<G> for SignedScalar<F> {
+    type Output = G;
+
+    fn mul(self, point: G) -> G {
+        self.mul_point(&point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SignedScalar;
+    use crate::arithmetic::CurveEndo;
+    use crate::bn256::{Fr, G1Affine, G1};
+    use crate::ff::{Field, WithSmallOrderMulGroup};
+    use crate::group::Group;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_signed_scalar() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let g = G1::generator();
+        assert_eq!(SignedScalar::<Fr>::from_i64(-5).to_field(), -Fr::from(5));
+        assert_eq!(SignedScalar::<Fr>::from_i64(-5) * g, -(g * Fr::from(5)));
+        assert_eq!(
+            G1Affine::from(g) * SignedScalar::<Fr>::from_i64(-5),
+            -(g * Fr::from(5))
+        );
+
+        for _ in 0..100 {
+            let k = Fr::random(&mut rng);
+            let (k1, k1_neg, k2, k2_neg) = G1::decompose_scalar(&k);
+            let k1 = SignedScalar::<Fr>::from_u128(k1, k1_neg);
+            let k2 = SignedScalar::<Fr>::from_u128(k2, k2_neg);
+            assert_eq!(k1.to_field() - k2.to_field() * Fr::ZETA, k);
+
+            let p = G1::random(&mut rng);
+            assert_eq!(p * k1 - p * k2 * Fr::ZETA, p * k);
+            assert_eq!(k1 * p, p * k1.to_field());
+            assert_eq!((-k1) * p, -(p * k1));
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Sinsemilla hashes and commitments, as specified in section 5.4.1.9 of the
+//! Zcash protocol specification.
+//!
+//! The message is split into `K`-bit chunks, and every chunk selects one of
+//! the `2^K` generators `S(j)`, which are shared by all the domains. Starting
+//! from the domain generator `Q(D)`, the accumulator is updated as
+//! `Acc = (Acc + S(m_i)) + Acc` with incomplete additions, so the hash fails
+//! on the (negligibly rare) inputs that hit an exceptional case.
+//!
+//! `S(j)` is the hash to curve of the little-endian `u32` encoding of `j`
+//! under the `z.cash:SinsemillaS` domain, so these are not the generators of
+//! `derive_generators`, which have one domain per index.
+//!
+//! None of the functions below are constant time with respect to the message.
+
+use crate::arithmetic::parallelize;
+use crate::{Coordinates, CurveAffine, CurveExt};
+use ff::Field;
+use group::{Curve, Group};
+use std::sync::Arc;
+
+/// The number of bits of a message chunk.
+pub const K: usize = 10;
+
+/// The maximum number of chunks of a message.
+pub const C: usize = 253;
+
+/// The personalization of the `S(j)` generators.
+pub const S_PERSONALIZATION: &str = "z.cash:SinsemillaS";
+
+/// The personalization of the domain generators `Q(D)`.
+pub const Q_PERSONALIZATION: &str = "z.cash:SinsemillaQ";
+
+/// Returns the `S(j)` generators for `j` in `0..2^K`.
+pub fn s_generators<G: CurveExt>() -> Vec<G::AffineExt> {
+    let mut points = vec![G::identity(); 1 << K];
+    parallelize(&mut points, |chunk, offset| {
+        let hasher = G::hash_to_curve(S_PERSONALIZATION);
+        for (i, point) in chunk.iter_mut().enumerate() {
+            *point = hasher(&((offset + i) as u32).to_le_bytes());
+        }
+    });
+    let mut generators = vec![G::AffineExt::identity(); 1 << K];
+    G::batch_normalize(&points, &mut generators);
+    generators
+}
+
+/// Returns `p + q` if the incomplete addition formulas are defined for `p`
+/// and `q`, i.e. if neither is the identity and their x-coordinates differ.
+fn incomplete_add<G: CurveExt>(p: G, q: G) -> Option<G> {
+    if bool::from(p.is_identity() | q.is_identity()) || p == q || p == -q {
+        None
+    } else {
+        Some(p + q)
+    }
+}
+
+/// Returns the x-coordinate of `point`, or zero for the identity, which is
+/// the `Extract_P` function of the specification.
+fn extract_p<G: CurveExt>(point: &G) -> G::Base {
+    Option::<Coordinates<G::AffineExt>>::from(point.to_affine().coordinates())
+        .map(|coordinates| *coordinates.x())
+        .unwrap_or(G::Base::ZERO)
+}
+
+/// A Sinsemilla hash domain, with its generator `Q(D)` and the shared `S(j)`.
+#[derive(Clone, Debug)]
+pub struct HashDomain<G: CurveExt> {
+    q: G,
+    s: Arc<Vec<G::AffineExt>>,
+}
+
+impl<G: CurveExt> HashDomain<G> {
+    /// Derives the domain with the given personalization.
+    pub fn new(domain: &str) -> Self {
+        let q = G::hash_to_curve(Q_PERSONALIZATION)(domain.as_bytes());
+        HashDomain {
+            q,
+            s: Arc::new(s_generators::<G>()),
+        }
+    }
+
+    /// Returns the domain generator `Q(D)`.
+    pub fn q(&self) -> G {
+        self.q
+    }
+
+    /// Returns the generator `S(j)`.
+    pub fn s(&self, j: usize) -> G::AffineExt {
+        self.s[j]
+    }
+
+    /// Returns `SinsemillaHashToPoint(D, message)`, where `message` is a
+    /// sequence of bits that is padded with zeros to a multiple of `K`, or
+    /// `None` if the message is longer than `K * C` bits or an exceptional
+    /// case is hit.
+    pub fn hash_to_point(&self, message: &[bool]) -> Option<G> {
+        if message.len() > K * C {
+            return None;
+        }
+
+        message.chunks(K).try_fold(self.q, |acc, chunk| {
+            let j = chunk
+                .iter()
+                .enumerate()
+                .fold(0, |j, (i, bit)| j | ((*bit as usize) << i));
+            let sum = incomplete_add(acc, self.s[j].into())?;
+            incomplete_add(sum, acc)
+        })
+    }
+
+    /// Returns `SinsemillaHash(D, message)`, the x-coordinate of
+    /// `hash_to_point`.
+    pub fn hash(&self, message: &[bool]) -> Option<G::Base> {
+        self.hash_to_point(message).map(|point| extract_p(&point))
+    }
+}
+
+/// A Sinsemilla commitment domain, which hides the hash of the message with
+/// a multiple of the generator `R`.
+#[derive(Clone, Debug)]
+pub struct CommitDomain<G: CurveExt> {
+    m: HashDomain<G>,
+    r: G,
+}
+
+impl<G: CurveExt> CommitDomain<G> {
+    /// Derives the domain with the given personalization. The hash domain is
+    /// `personalization-M`, and `R` is the hash to curve of the empty message
+    /// under `personalization-r`.
+    pub fn new(personalization: &str) -> Self {
+        CommitDomain {
+            m: HashDomain::new(&format!("{personalization}-M")),
+            r: G::hash_to_curve(&format!("{personalization}-r"))(&[]),
+        }
+    }
+
+    /// Returns the underlying hash domain.
+    pub fn hash_domain(&self) -> &HashDomain<G> {
+        &self.m
+    }
+
+    /// Returns the blinding generator `R`.
+    pub fn r(&self) -> G {
+        self.r
+    }
+
+    /// Returns `SinsemillaCommit_r(D, message)`.
+    pub fn commit(&self, message: &[bool], r: &G::ScalarExt) -> Option<G> {
+        self.m
+            .hash_to_point(message)
+            .map(|point| point + self.r * r)
+    }
+
+    /// Returns `SinsemillaShortCommit_r(D, message)`, the x-coordinate of
+    /// `commit`.
+    pub fn short_commit(&self, message: &[bool], r: &G::ScalarExt) -> Option<G::Base> {
+        self.commit(message, r).map(|point| extract_p(&point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommitDomain, HashDomain, K, Q_PERSONALIZATION, S_PERSONALIZATION};
+    use crate::pasta::{Ep, Eq, Fq};
+    use crate::CurveExt;
+    use ff::Field;
+    use group::Group;
+    use rand_core::OsRng;
+
+    fn bits(bytes: &[u8]) -> Vec<bool> {
+        bytes
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+            .collect()
+    }
+
+    fn hash_test<G: CurveExt>() {
+        let domain = HashDomain::<G>::new("halo2curves:test");
+        assert_eq!(
+            domain.q(),
+            G::hash_to_curve(Q_PERSONALIZATION)(b"halo2curves:test")
+        );
+        assert_eq!(
+            G::from(domain.s(1000)),
+            G::hash_to_curve(S_PERSONALIZATION)(&1000u32.to_le_bytes())
+        );
+
+        // The message is padded with zeros to a multiple of `K` bits.
+        let message = bits(b"sinsemill");
+        let mut padded = message.clone();
+        padded.resize((message.len() + K - 1) / K * K, false);
+        assert_eq!(domain.hash(&message), domain.hash(&padded));
+        assert_ne!(domain.hash(&message), domain.hash(&bits(b"Sinsemill")));
+
+        // One chunk: Acc = (Q + S(j)) + Q.
+        let chunk = [
+            true, false, true, false, false, false, false, false, false, true,
+        ];
+        let expected = domain.q() + G::from(domain.s(0b1000000101)) + domain.q();
+        assert_eq!(domain.hash_to_point(&chunk), Some(expected));
+
+        assert_eq!(domain.hash_to_point(&[]), Some(domain.q()));
+        assert_eq!(domain.hash_to_point(&vec![false; K * super::C + 1]), None);
+        assert_ne!(
+            HashDomain::<G>::new("halo2curves:other").hash(&message),
+            domain.hash(&message)
+        );
+    }
+
+    #[test]
+    fn test_hash() {
+        hash_test::<Ep>();
+        hash_test::<Eq>();
+    }
+
+    #[test]
+    fn test_commit() {
+        let domain = CommitDomain::<Ep>::new("halo2curves:test");
+        let message = bits(b"commit");
+        let r = Fq::random(OsRng);
+
+        let commitment = domain.commit(&message, &r).unwrap();
+        assert_eq!(
+            commitment,
+            domain.hash_domain().hash_to_point(&message).unwrap() + domain.r() * r
+        );
+        assert_eq!(
+            domain.commit(&message, &Fq::ZERO),
+            HashDomain::<Ep>::new("halo2curves:test-M").hash_to_point(&message)
+        );
+        assert_ne!(domain.r(), Ep::identity());
+        assert_ne!(
+            domain.short_commit(&message, &(r + Fq::ONE)),
+            domain.short_commit(&message, &r)
+        );
+    }
+}
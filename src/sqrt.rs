@@ -0,0 +1,146 @@
+//! Tonelli-Shanks square roots with reusable precomputation.
+//!
+//! A square root in a field with `p - 1 = t * 2^S`, `t` odd, is an
+//! exponentiation by `(t - 1) / 2` followed by a discrete logarithm in the
+//! subgroup of order `2^S`. [`SqrtPrecomputation`] derives the exponent's
+//! window digits and the powers `g^(2^i)` of the `2^S`-th root of unity `g`
+//! once, so that decompressing many points only pays for the per-element
+//! work.
+
+use crate::arithmetic::parallelize;
+use ff::PrimeField;
+use num_bigint::BigUint;
+use num_traits::Num;
+use subtle::{ConditionallySelectable, ConstantTimeEq, CtOption};
+
+/// Width of the exponentiation windows, in bits.
+const WINDOW: usize = 4;
+
+/// Precomputed constants for square roots in `F`.
+#[derive(Clone, Debug)]
+pub struct SqrtPrecomputation<F: PrimeField> {
+    /// Digits of `(t - 1) / 2` in base `2^WINDOW`, most significant first.
+    digits: Vec<u8>,
+    /// `g^(2^i)` for `i < S`.
+    roots: Vec<F>,
+}
+
+impl<F: PrimeField> Default for SqrtPrecomputation<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> SqrtPrecomputation<F> {
+    /// Derives the precomputation from `F::MODULUS` and `F::ROOT_OF_UNITY`.
+    pub fn new() -> Self {
+        let p = BigUint::from_str_radix(&F::MODULUS[2..], 16).unwrap();
+        // `(t - 1) / 2 = (p - 1) >> (S + 1)`, since `t` is odd.
+        let exp = (p - 1u32) >> (F::S + 1);
+        let digits = exp.to_radix_be(1 << WINDOW);
+
+        let mut roots = Vec::with_capacity(F::S as usize);
+        let mut root = F::ROOT_OF_UNITY;
+        for _ in 0..F::S {
+            roots.push(root);
+            root = root.square();
+        }
+
+        Self { digits, roots }
+    }
+
+    /// Returns `a^((t - 1) / 2)`.
+    fn pow_t_minus1_over2(&self, a: &F) -> F {
+        let mut table = [F::ONE; 1 << WINDOW];
+        for i in 1..table.len() {
+            table[i] = table[i - 1] * a;
+        }
+
+        let mut res = F::ONE;
+        for &digit in self.digits.iter() {
+            for _ in 0..WINDOW {
+                res = res.square();
+            }
+            res *= table[digit as usize];
+        }
+        res
+    }
+
+    /// Computes the square root of `a`, if it exists, in constant time.
+    pub fn sqrt(&self, a: &F) -> CtOption<F> {
+        let w = self.pow_t_minus1_over2(a);
+        // x = a^((t + 1) / 2) and b = a^t, so that x^2 = a * b. Each step
+        // below halves the order of b, keeping x^2 = a * b, until b = 1.
+        let mut x = w * a;
+        let mut b = x * w;
+
+        let s = F::S as usize;
+        for k in (1..s).rev() {
+            let mut b2k = b;
+            for _ in 1..k {
+                b2k = b2k.square();
+            }
+            let flip = !b2k.ct_eq(&F::ONE);
+            x = F::conditional_select(&x, &(x * self.roots[s - k - 1]), flip);
+            b = F::conditional_select(&b, &(b * self.roots[s - k]), flip);
+        }
+
+        CtOption::new(x, x.square().ct_eq(a))
+    }
+
+    /// Computes the square roots of all of `values`, in parallel.
+    pub fn sqrt_batch(&self, values: &[F]) -> Vec<CtOption<F>> {
+        let mut roots = vec![CtOption::new(F::ZERO, 0.into()); values.len()];
+        parallelize(&mut roots, |roots, start| {
+            for (root, value) in roots.iter_mut().zip(values[start..].iter()) {
+                *root = self.sqrt(value);
+            }
+        });
+        roots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqrtPrecomputation;
+    use crate::{bn256, pasta, secp256k1, secp256r1};
+    use ff::{Field, PrimeField};
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn sqrt_test<F: PrimeField>() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let precomputation = SqrtPrecomputation::<F>::new();
+
+        assert_eq!(precomputation.sqrt(&F::ZERO).unwrap(), F::ZERO);
+        assert!(bool::from(precomputation.sqrt(&F::ROOT_OF_UNITY).is_none()));
+
+        let values: Vec<F> = (0..100).map(|_| F::random(&mut rng)).collect();
+        let roots = precomputation.sqrt_batch(&values);
+        for (value, root) in values.iter().zip(roots) {
+            let expected = value.sqrt();
+            assert_eq!(bool::from(root.is_some()), bool::from(expected.is_some()));
+            if let Some(root) = Option::<F>::from(root) {
+                assert_eq!(root.square(), *value);
+            }
+
+            let square = value.square();
+            let root = precomputation.sqrt(&square).unwrap();
+            assert!(root == *value || root == -*value);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_precomputation() {
+        sqrt_test::<bn256::Fq>();
+        sqrt_test::<bn256::Fr>();
+        sqrt_test::<secp256k1::Fp>();
+        sqrt_test::<secp256k1::Fq>();
+        sqrt_test::<secp256r1::Fp>();
+        sqrt_test::<secp256r1::Fq>();
+        sqrt_test::<pasta::Fp>();
+    }
+}
@@ -0,0 +1,173 @@
+//! Operation counters, enabled by the `op-count` feature.
+//!
+//! Every multiplication, squaring and inversion in the prime fields
+//! implemented by this crate bumps a thread-local counter. Extension field,
+//! curve and pairing operations are counted as the base field operations they
+//! perform, and the multiplications done inside an inversion are counted as
+//! well. The pasta fields come from `pasta_curves`, which has no hook for the
+//! counters, so they are not counted. Neither is work done on other threads,
+//! e.g. by `multi_miller_loop_parallel`.
+//!
+//! With this feature the `mul` and `square` methods of the fields are no
+//! longer `const fn`, so it is meant for instrumentation builds only.
+
+use std::cell::Cell;
+use std::ops::{Add, Sub};
+
+/// Numbers of field operations.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpCounts {
+    pub mul: u64,
+    pub square: u64,
+    pub invert: u64,
+}
+
+impl Add for OpCounts {
+    type Output = OpCounts;
+
+    fn add(self, rhs: OpCounts) -> OpCounts {
+        OpCounts {
+            mul: self.mul + rhs.mul,
+            square: self.square + rhs.square,
+            invert: self.invert + rhs.invert,
+        }
+    }
+}
+
+impl Sub for OpCounts {
+    type Output = OpCounts;
+
+    fn sub(self, rhs: OpCounts) -> OpCounts {
+        OpCounts {
+            mul: self.mul - rhs.mul,
+            square: self.square - rhs.square,
+            invert: self.invert - rhs.invert,
+        }
+    }
+}
+
+thread_local! {
+    static COUNTS: Cell<OpCounts> = Cell::new(OpCounts::default());
+}
+
+fn record(f: impl FnOnce(&mut OpCounts)) {
+    COUNTS.with(|counts| {
+        let mut value = counts.get();
+        f(&mut value);
+        counts.set(value);
+    })
+}
+
+#[inline]
+pub(crate) fn record_mul() {
+    record(|counts| counts.mul += 1)
+}
+
+#[inline]
+pub(crate) fn record_square() {
+    record(|counts| counts.square += 1)
+}
+
+#[inline]
+pub(crate) fn record_invert() {
+    record(|counts| counts.invert += 1)
+}
+
+/// Returns the operations counted on this thread since the last `reset`.
+pub fn counts() -> OpCounts {
+    COUNTS.with(|counts| counts.get())
+}
+
+/// Resets the counters of this thread.
+pub fn reset() {
+    COUNTS.with(|counts| counts.set(OpCounts::default()))
+}
+
+/// Runs `f` and returns its result together with the operations it
+/// performed on this thread.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, OpCounts) {
+    let before = counts();
+    let result = f();
+    (result, counts() - before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{counts, measure, reset, OpCounts};
+    use crate::bn256::{Bn256, Fq, Fr, G1Affine, G2Affine, G1};
+    use crate::ff::Field;
+    use crate::group::prime::PrimeCurveAffine;
+    use crate::group::{Curve, Group};
+    use crate::msm::msm;
+    use pairing::Engine;
+
+    #[test]
+    fn test_op_counts() {
+        let a = Fq::from(3);
+        let b = Fq::from(5);
+
+        let (_, counts) = measure(|| a * b);
+        assert_eq!(
+            counts,
+            OpCounts {
+                mul: 1,
+                ..OpCounts::default()
+            }
+        );
+
+        let (_, counts) = measure(|| a.square());
+        assert_eq!(
+            counts,
+            OpCounts {
+                square: 1,
+                ..OpCounts::default()
+            }
+        );
+
+        let (_, counts) = measure(|| a.invert());
+        assert_eq!(counts.invert, 1);
+        assert!(counts.mul + counts.square > 0);
+
+        let (_, counts) = measure(|| G1::generator().double());
+        assert_eq!(counts.invert, 0);
+        assert!(counts.mul > 0 && counts.square > 0);
+
+        let (_, counts) = measure(|| G1::generator() * Fr::from(7));
+        assert!(counts.mul > 0);
+
+        // Nested measurements add up, and the counters only move when
+        // operations run.
+        let (_, outer) = measure(|| {
+            let (_, inner) = measure(|| a * b);
+            assert_eq!(inner.mul, 1);
+            a.square() * b
+        });
+        assert_eq!(
+            outer,
+            OpCounts {
+                mul: 2,
+                square: 1,
+                invert: 0
+            }
+        );
+        let (_, counts) = measure(|| a + b - a.double());
+        assert_eq!(counts, OpCounts::default());
+
+        let (_, counts) =
+            measure(|| Bn256::pairing(&G1Affine::generator(), &G2Affine::generator()));
+        assert!(counts.invert > 0 && counts.mul > 0);
+
+        let bases: Vec<_> = (1..=8)
+            .map(|i| (G1::generator() * Fr::from(i)).to_affine())
+            .collect();
+        let scalars: Vec<_> = (1..=8).map(Fr::from).collect();
+        let (_, small) = measure(|| msm(&scalars[..4], &bases[..4]));
+        let (_, large) = measure(|| msm(&scalars, &bases));
+        assert!(small.mul > 0 && large.mul > small.mul);
+
+        reset();
+        assert_eq!(counts(), OpCounts::default());
+        let _ = a * b;
+        assert_eq!(counts().mul, 1);
+    }
+}
@@ -1,22 +1,28 @@
 #![allow(clippy::eq_op)]
 
-use crate::ff::Field;
-use crate::group::prime::PrimeCurveAffine;
+use crate::ff::{Field, FromUniformBytes};
+use crate::group::cofactor::CofactorCurve;
+use crate::group::prime::{PrimeCurve, PrimeCurveAffine};
+use crate::group::UncompressedEncoding;
 use crate::tests::fe_from_str;
 use crate::{group::GroupEncoding, serde::SerdeObject};
-use crate::{hash_to_curve, CurveAffine, CurveExt};
+use crate::{hash_to_curve, CurveAffine, CurveAffineExt, CurveDoubling, CurveExt};
 use rand_core::{OsRng, RngCore};
 use std::iter;
+use subtle::{Choice, ConditionallySelectable};
 
 #[cfg(feature = "derive_serde")]
 use serde::{Deserialize, Serialize};
 
-pub fn curve_tests<G: CurveExt>() {
+pub fn curve_tests<G: CurveDoubling>() {
     is_on_curve::<G>();
     equality::<G>();
+    conditional_selection::<G>();
     projective_to_affine_affine_to_projective::<G>();
     projective_addition::<G>();
     mixed_addition::<G>();
+    affine_formulas::<G>();
+    double_n::<G>();
     multiplication::<G>();
     batch_normalize::<G>();
     serdes::<G>();
@@ -109,6 +115,62 @@ where
     }
 }
 
+/// Exercises the `group` traits that generic protocol code bounds on, through
+/// those bounds only.
+pub fn group_traits_test<G>()
+where
+    G: PrimeCurve,
+    G::Affine: UncompressedEncoding,
+{
+    for _ in 0..10 {
+        let point = G::random(OsRng);
+        let affine = point.to_affine();
+        assert_eq!(affine.to_curve(), point);
+
+        assert_eq!(G::from_bytes(&point.to_bytes()).unwrap(), point);
+        assert_eq!(G::Affine::from_bytes(&affine.to_bytes()).unwrap(), affine);
+        assert_eq!(
+            G::Affine::from_uncompressed(&affine.to_uncompressed()).unwrap(),
+            affine
+        );
+    }
+}
+
+pub fn cofactor_group_traits_test<G>()
+where
+    G: CofactorCurve,
+    G::Affine: UncompressedEncoding,
+{
+    use crate::group::cofactor::{CofactorCurveAffine, CofactorGroup};
+
+    for _ in 0..10 {
+        let point = G::random(OsRng);
+        let affine = point.to_affine();
+        assert_eq!(affine.to_curve(), point);
+
+        assert_eq!(G::from_bytes(&point.to_bytes()).unwrap(), point);
+        assert_eq!(G::Affine::from_bytes(&affine.to_bytes()).unwrap(), affine);
+        assert_eq!(
+            G::Affine::from_uncompressed(&affine.to_uncompressed()).unwrap(),
+            affine
+        );
+
+        // The full curve may return points outside of the subgroup.
+        assert_eq!(
+            bool::from(point.into_subgroup().is_some()),
+            bool::from(point.is_torsion_free())
+        );
+        let subgroup = point.clear_cofactor();
+        let full: G = subgroup.into();
+        assert!(bool::from(full.is_torsion_free()));
+        assert_eq!(full.into_subgroup().unwrap(), subgroup);
+        assert_eq!(
+            G::Subgroup::from_bytes(&subgroup.to_bytes()).unwrap(),
+            subgroup
+        );
+    }
+}
+
 fn is_on_curve<G: CurveExt>() {
     assert!(bool::from(G::identity().is_on_curve()));
     assert!(bool::from(G::generator().is_on_curve()));
@@ -151,6 +213,30 @@ fn equality<G: CurveExt>() {
     }
 }
 
+fn conditional_selection<G: CurveExt>() {
+    let a = G::random(OsRng);
+    let b = G::random(OsRng);
+    assert_eq!(G::conditional_select(&a, &b, Choice::from(0)), a);
+    assert_eq!(G::conditional_select(&a, &b, Choice::from(1)), b);
+
+    let (mut c, mut d) = (a, b);
+    G::conditional_swap(&mut c, &mut d, Choice::from(0));
+    assert_eq!((c, d), (a, b));
+    G::conditional_swap(&mut c, &mut d, Choice::from(1));
+    assert_eq!((c, d), (b, a));
+
+    let a = a.to_affine();
+    let b = G::AffineExt::identity();
+    assert_eq!(G::AffineExt::conditional_select(&a, &b, Choice::from(0)), a);
+    assert_eq!(G::AffineExt::conditional_select(&a, &b, Choice::from(1)), b);
+
+    let (mut c, mut d) = (a, b);
+    G::AffineExt::conditional_swap(&mut c, &mut d, Choice::from(0));
+    assert_eq!((c, d), (a, b));
+    G::AffineExt::conditional_swap(&mut c, &mut d, Choice::from(1));
+    assert_eq!((c, d), (b, a));
+}
+
 fn projective_to_affine_affine_to_projective<G: CurveExt>() {
     let a = G::generator();
     let b = G::identity();
@@ -258,6 +344,45 @@ fn mixed_addition<G: CurveExt>() {
     assert_eq!(c0, c1);
 }
 
+// Checks doubling and addition against the chord and tangent formulas in
+// affine coordinates, which hold for any curve constant `a`.
+fn affine_formulas<G: CurveExt>() {
+    let a = G::a();
+    let xy = |p: G| {
+        let c = p.to_affine().coordinates().unwrap();
+        (*c.x(), *c.y())
+    };
+    for _ in 0..10 {
+        let p = G::random(OsRng);
+        let q = G::random(OsRng);
+        let (x1, y1) = xy(p);
+        let (x2, y2) = xy(q);
+
+        let lambda = (x1.square().double() + x1.square() + a) * y1.double().invert().unwrap();
+        let x3 = lambda.square() - x1.double();
+        let y3 = lambda * (x1 - x3) - y1;
+        assert_eq!(xy(p.double()), (x3, y3));
+
+        let lambda = (y2 - y1) * (x2 - x1).invert().unwrap();
+        let x3 = lambda.square() - x1 - x2;
+        let y3 = lambda * (x1 - x3) - y1;
+        assert_eq!(xy(p + q), (x3, y3));
+        assert_eq!(xy(p + q.to_affine()), (x3, y3));
+    }
+}
+
+fn double_n<G: CurveDoubling>() {
+    assert!(bool::from(G::identity().double_n(5).is_identity()));
+
+    let a = G::random(OsRng);
+    assert_eq!(a.double_n(0), a);
+    let mut b = a;
+    for k in 1..10 {
+        b = b.double();
+        assert_eq!(a.double_n(k), b);
+    }
+}
+
 fn batch_normalize<G: CurveExt>() {
     let a = G::generator().double();
     let b = a.double();
@@ -296,6 +421,86 @@ fn batch_normalize<G: CurveExt>() {
     }
 }
 
+pub fn batch_add_test<G: CurveExt>()
+where
+    G::AffineExt: CurveAffineExt,
+{
+    let random = || G::AffineExt::from(G::random(OsRng));
+    let mut bases: Vec<G::AffineExt> = (0..8).map(|_| random()).collect();
+    bases.push(G::AffineExt::identity());
+    let negate = 0x80000000;
+
+    // Distinct pairs, doublings, opposite points and the identity.
+    let base_positions = [
+        0,
+        1,
+        2,
+        2,
+        3,
+        3 | negate,
+        8,
+        4,
+        5 | negate,
+        8,
+        8,
+        8,
+        6 | negate,
+        7,
+    ];
+    let get_point = |position: u32| {
+        let base = bases[(position & !negate) as usize];
+        if position & negate != 0 {
+            -base
+        } else {
+            base
+        }
+    };
+    let num_points = base_positions.len();
+    let output_indices: Vec<u32> = (0..num_points / 2)
+        .map(|i| (num_points + i) as u32)
+        .collect();
+
+    let mut points = vec![G::AffineExt::identity(); num_points + num_points / 2];
+    G::AffineExt::batch_add::<true, true>(
+        &mut points,
+        &output_indices,
+        num_points,
+        0,
+        &bases,
+        &base_positions,
+    );
+    for (i, pair) in base_positions.chunks(2).enumerate() {
+        let expected = get_point(pair[0]) + get_point(pair[1]);
+        assert_eq!(G::from(points[num_points + i]), expected);
+    }
+
+    // Without COMPLETE or LOAD_POINTS, on distinct points already in place,
+    // with output indices shifted by an offset.
+    let inputs: Vec<_> = (0..num_points).map(|_| random()).collect();
+    let mut points = inputs.clone();
+    points.resize(num_points + num_points / 2, G::AffineExt::identity());
+    let offset = 3;
+    let output_indices: Vec<u32> = (0..num_points / 2)
+        .map(|i| (offset + num_points + i) as u32)
+        .collect();
+    G::AffineExt::batch_add::<false, false>(
+        &mut points,
+        &output_indices,
+        num_points,
+        offset,
+        &[],
+        &[],
+    );
+    for (i, pair) in inputs.chunks(2).enumerate() {
+        assert_eq!(G::from(points[num_points + i]), pair[0] + pair[1]);
+    }
+
+    for point in inputs {
+        let (x, y) = point.into_coordinates();
+        assert_eq!(G::AffineExt::from_xy(x, y).unwrap(), point);
+    }
+}
+
 fn multiplication<G: CurveExt>() {
     for _ in 1..1000 {
         let s1 = G::ScalarExt::random(OsRng);
@@ -339,6 +544,40 @@ pub fn hash_to_curve_test<G: CurveExt>() {
     }
 }
 
+pub fn derive_generators_test<G: CurveExt>() {
+    let generators = hash_to_curve::derive_generators::<G>("test", 16).unwrap();
+    assert_eq!(generators.len(), 16);
+    for (i, generator) in generators.iter().enumerate() {
+        let domain = format!("test-{i:08x}");
+        assert_eq!(*generator, G::hash_to_curve(&domain)(&[]).to_affine());
+        assert!(bool::from(!generator.is_identity()));
+    }
+    for (i, a) in generators.iter().enumerate() {
+        assert!(generators[i + 1..].iter().all(|b| a != b));
+    }
+    assert_eq!(
+        generators[..4],
+        hash_to_curve::derive_generators::<G>("test", 4).unwrap()[..]
+    );
+    assert!(hash_to_curve::derive_generators::<G>("test", 0)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        hash_to_curve::generator_domain("test", u32::MAX as usize).unwrap(),
+        "test-ffffffff"
+    );
+}
+
+pub fn hash_to_scalar_test<G: CurveExt>()
+where
+    G::ScalarExt: FromUniformBytes<64>,
+{
+    let a = hash_to_curve::hash_to_scalar::<G>(b"message", "test");
+    assert_eq!(a, hash_to_curve::hash_to_scalar::<G>(b"message", "test"));
+    assert_ne!(a, hash_to_curve::hash_to_scalar::<G>(b"message", "test2"));
+    assert_ne!(a, hash_to_curve::hash_to_scalar::<G>(b"message2", "test"));
+}
+
 pub fn svdw_map_to_curve_test<G: CurveExt>(
     z: G::Base,
     precomputed_constants: [&'static str; 4],
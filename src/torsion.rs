@@ -0,0 +1,117 @@
+//! Points of small order on curves with a cofactor.
+//!
+//! A point has small order if its order divides the cofactor, so that it is
+//! killed by clearing the cofactor. Protocols that forget a subgroup check
+//! accept such points from a malicious party, and `SmallOrder` gives tests a
+//! way to produce them: the generators of the torsion components of the
+//! cofactor, and a check for points lying in them.
+//!
+//! Baby Jubjub has a cyclic torsion of order 8. The cofactor of BN254 G2 is
+//! `10069 * 5864401 * 1875725156269 * q` for a 177-bit prime `q`, and only the
+//! components of the three small primes have generators here.
+
+use group::cofactor::CofactorGroup;
+use group::Group;
+use subtle::Choice;
+
+/// A group with points of small order.
+pub trait SmallOrder: CofactorGroup {
+    /// Returns true if the order of this point divides the cofactor, which
+    /// includes the identity.
+    fn is_small_order(&self) -> Choice {
+        self.clear_cofactor().is_identity()
+    }
+
+    /// Returns a generator of each cyclic torsion component of small order,
+    /// together with its order.
+    fn torsion_generators() -> Vec<(u64, Self)>;
+}
+
+/// Returns `p` multiplied by the little-endian integer `limbs`, in variable
+/// time. This is meant for public scalars such as group orders and cofactors,
+/// and works on points outside of the prime-order subgroup.
+pub(crate) fn mul_by_limbs<G: Group>(p: &G, limbs: &[u64]) -> G {
+    let mut acc = G::identity();
+    for limb in limbs.iter().rev() {
+        for i in (0..64).rev() {
+            acc = acc.double();
+            if (limb >> i) & 1 == 1 {
+                acc += p;
+            }
+        }
+    }
+    acc
+}
+
+/// Returns `limbs / divisor`, for a nonzero `divisor`.
+pub(crate) fn div_limbs(limbs: &[u64; 4], divisor: u64) -> [u64; 4] {
+    let mut quotient = [0u64; 4];
+    let mut rem = 0u128;
+    for (q, limb) in quotient.iter_mut().zip(limbs).rev() {
+        let n = (rem << 64) | *limb as u128;
+        *q = (n / divisor as u128) as u64;
+        rem = n % divisor as u128;
+    }
+    quotient
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{div_limbs, mul_by_limbs, SmallOrder};
+    use crate::babyjubjub::{BabyJubjub, Fr};
+    use crate::bn256::G2;
+    use ff::Field;
+    use group::cofactor::CofactorGroup;
+    use group::Group;
+    use rand_core::OsRng;
+
+    /// Checks that every generator has exactly its order and small order,
+    /// and that points of the prime-order subgroup do not.
+    fn small_order_test<G: SmallOrder + From<<G as CofactorGroup>::Subgroup>>() {
+        let generators = G::torsion_generators();
+        assert!(!generators.is_empty());
+        for (order, t) in generators {
+            assert!(bool::from(t.is_small_order()));
+            assert!(!bool::from(t.is_torsion_free()));
+            assert!(bool::from(mul_by_limbs(&t, &[order]).is_identity()));
+            for q in 2..order.min(100) {
+                if order % q == 0 {
+                    assert!(!bool::from(mul_by_limbs(&t, &[order / q]).is_identity()));
+                }
+            }
+        }
+        assert!(bool::from(G::identity().is_small_order()));
+        let p: G = G::random(OsRng).clear_cofactor().into();
+        assert!(!bool::from(p.is_small_order()));
+    }
+
+    #[test]
+    fn test_small_order() {
+        small_order_test::<BabyJubjub>();
+        small_order_test::<G2>();
+
+        // Baby Jubjub lists all its 8 points of small order.
+        let points = BabyJubjub::small_order_points();
+        assert_eq!(points.len(), 8);
+        assert_eq!(points[0], BabyJubjub::identity());
+        for (i, p) in points.iter().enumerate() {
+            assert!(bool::from(p.is_small_order()));
+            assert!(!points[i + 1..].contains(p));
+        }
+        let p = BabyJubjub::generator() * Fr::random(OsRng);
+        assert!(!bool::from((p + points[3]).is_small_order()));
+        assert!(!bool::from((p + points[3]).is_torsion_free()));
+    }
+
+    #[test]
+    fn test_div_limbs() {
+        let limbs = [0x345f2299c0f9fa8d, 0x06ceecda572a2489, 0, 0];
+        let quotient = div_limbs(&limbs, 10069);
+        let n = (limbs[1] as u128) << 64 | limbs[0] as u128;
+        assert_eq!(
+            quotient,
+            [(n / 10069) as u64, ((n / 10069) >> 64) as u64, 0, 0]
+        );
+        assert_eq!(div_limbs(&G2::COFACTOR, 1), G2::COFACTOR);
+    }
+}
@@ -0,0 +1,98 @@
+//! Feeds malformed inputs to the APIs meant for untrusted data, and checks
+//! that they return errors instead of panicking. Run with
+//! `cargo test --test no_panic`.
+
+use halo2curves::bn256::{self, Fr};
+use halo2curves::decode::{decode_any, read_points_batched_report, read_points_report, Format};
+use halo2curves::id::CurveId;
+use halo2curves::normalize::Normalization;
+use halo2curves::order::{
+    add_mod_order, is_square_mod_order, mul_mod_order, reduce_wide, sqrt_mod_order, sub_mod_order,
+};
+use rand_core::{RngCore, SeedableRng};
+use rand_xorshift::XorShiftRng;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+const FORMATS: [Format; 6] = [
+    Format::CompressedPoint,
+    Format::UncompressedPoint,
+    Format::RawPoint,
+    Format::Scalar,
+    Format::Gnark,
+    Format::Arkworks,
+];
+
+fn inputs(rng: &mut XorShiftRng) -> Vec<Vec<u8>> {
+    let mut inputs = vec![
+        vec![],
+        vec![0; 1],
+        vec![0xff; 32],
+        vec![0xff; 64],
+        vec![0; 200],
+    ];
+    for _ in 0..200 {
+        let mut bytes = vec![0; (rng.next_u32() % 300) as usize];
+        rng.fill_bytes(&mut bytes);
+        inputs.push(bytes);
+    }
+    inputs
+}
+
+fn assert_no_panic(f: impl FnOnce()) {
+    assert!(catch_unwind(AssertUnwindSafe(f)).is_ok());
+}
+
+#[test]
+fn test_decode_no_panic() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for bytes in inputs(&mut rng) {
+        for format in FORMATS {
+            for id in CurveId::ALL {
+                assert_no_panic(|| {
+                    let _ = decode_any(id, &bytes, format);
+                });
+            }
+            assert_no_panic(|| {
+                let _ = read_points_report::<bn256::G1Affine>(&bytes, format);
+                let _ = read_points_report::<bn256::G2Affine>(&bytes, format);
+            });
+            assert_no_panic(|| {
+                let _ = read_points_batched_report::<bn256::G2Affine>(&bytes, format, 2, &mut rng);
+            });
+        }
+    }
+}
+
+#[test]
+fn test_order_no_panic() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let order = bn256::G1::ORDER_LIMBS;
+    let mut limbs = || {
+        [
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+        ]
+    };
+    for _ in 0..100 {
+        let (a, b) = (limbs(), limbs());
+        assert_no_panic(|| {
+            let _ = add_mod_order(&a, &b, &order);
+            let _ = sub_mod_order(&a, &b, &order);
+            let _ = mul_mod_order(&a, &b, &order);
+            let _ = reduce_wide(&[a[0], a[1], a[2], a[3], b[0], b[1], b[2], b[3]], &order);
+            let _ = is_square_mod_order::<bn256::G1>(&a);
+            let _ = sqrt_mod_order::<bn256::G1>(&a);
+            let _ = Normalization::LowS.normalize(&Fr::from_raw(a));
+        });
+    }
+}